@@ -0,0 +1,62 @@
+-- Schéma initial de SentinelRisk
+
+CREATE TABLE IF NOT EXISTS risks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    description TEXT,
+    impact INTEGER NOT NULL,
+    probability INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Identified'
+        CONSTRAINT risks_status_check CHECK (status IN (
+            'Identified',
+            'Assessed',
+            'InTreatment',
+            'Monitoring',
+            'Accepted',
+            'Rejected',
+            'Transferred',
+            'Closed'
+        )),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    external_id TEXT,
+    category TEXT,
+    location TEXT,
+    regulation TEXT,
+    control_measure_id TEXT
+);
+
+CREATE TABLE IF NOT EXISTS risk_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    old_status TEXT NOT NULL,
+    new_status TEXT NOT NULL,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_risk_status_history_risk_id ON risk_status_history(risk_id);
+
+CREATE TABLE IF NOT EXISTS risk_evaluation (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    severity INTEGER NOT NULL,
+    likelihood INTEGER NOT NULL,
+    detectability INTEGER NOT NULL,
+    score INTEGER GENERATED ALWAYS AS (severity * likelihood * detectability) STORED,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_risk_evaluation_risk_id ON risk_evaluation(risk_id);
+
+CREATE TABLE IF NOT EXISTS incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    title TEXT NOT NULL,
+    description TEXT,
+    severity TEXT NOT NULL,
+    status TEXT NOT NULL,
+    related_risk_id UUID REFERENCES risks(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incidents_related_risk_id ON incidents(related_risk_id);
@@ -0,0 +1,48 @@
+-- Champs descriptifs complémentaires des risques
+
+ALTER TABLE risks
+    ADD COLUMN IF NOT EXISTS risk_map TEXT,
+    ADD COLUMN IF NOT EXISTS threat_map TEXT,
+    ADD COLUMN IF NOT EXISTS technology TEXT,
+    ADD COLUMN IF NOT EXISTS team TEXT,
+    ADD COLUMN IF NOT EXISTS owner TEXT,
+    ADD COLUMN IF NOT EXISTS owner_delegate TEXT,
+    ADD COLUMN IF NOT EXISTS source TEXT,
+    ADD COLUMN IF NOT EXISTS evaluation_method TEXT,
+    ADD COLUMN IF NOT EXISTS evaluation_notes TEXT,
+    ADD COLUMN IF NOT EXISTS additional_notes TEXT;
+
+-- Tags, actifs et parties prenantes (relations plusieurs-à-plusieurs)
+
+CREATE TABLE IF NOT EXISTS tags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS risk_tags (
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (risk_id, tag_id)
+);
+
+CREATE TABLE IF NOT EXISTS assets (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS risk_assets (
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    asset_id UUID NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    PRIMARY KEY (risk_id, asset_id)
+);
+
+CREATE TABLE IF NOT EXISTS stakeholders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL UNIQUE
+);
+
+CREATE TABLE IF NOT EXISTS risk_stakeholders (
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    stakeholder_id UUID NOT NULL REFERENCES stakeholders(id) ON DELETE CASCADE,
+    PRIMARY KEY (risk_id, stakeholder_id)
+);
@@ -0,0 +1,17 @@
+-- Pièces jointes (preuves) des risques et incidents
+
+CREATE TABLE IF NOT EXISTS attachments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    risk_id UUID REFERENCES risks(id) ON DELETE CASCADE,
+    incident_id UUID REFERENCES incidents(id) ON DELETE CASCADE,
+    filename TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    sha256 TEXT NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT attachments_owner_check CHECK (num_nonnulls(risk_id, incident_id) = 1)
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_risk_id ON attachments(risk_id);
+CREATE INDEX IF NOT EXISTS idx_attachments_incident_id ON attachments(incident_id);
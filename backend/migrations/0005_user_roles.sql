@@ -0,0 +1,8 @@
+-- Rôles utilisateurs (contrôle d'accès)
+
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS role TEXT NOT NULL DEFAULT 'Viewer'
+        CONSTRAINT users_role_check CHECK (role IN ('Viewer', 'Analyst', 'RiskOwner', 'Admin'));
+
+-- Avant l'introduction des rôles, tout compte avait un accès complet
+UPDATE users SET role = 'Admin';
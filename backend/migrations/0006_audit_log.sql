@@ -0,0 +1,15 @@
+-- Journal d'audit des modifications (risques, incidents)
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    entity_type TEXT NOT NULL,
+    entity_id UUID NOT NULL,
+    action TEXT NOT NULL
+        CONSTRAINT audit_log_action_check CHECK (action IN ('Create', 'Update', 'Delete')),
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    actor_username TEXT NOT NULL,
+    changes JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at);
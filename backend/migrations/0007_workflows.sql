@@ -0,0 +1,51 @@
+-- Workflows de statut configurables par unité métier
+
+ALTER TABLE risks ADD COLUMN IF NOT EXISTS business_unit TEXT;
+
+CREATE TABLE IF NOT EXISTS workflows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    -- NULL : workflow par défaut, appliqué aux risques sans workflow dédié
+    business_unit TEXT,
+    states TEXT[] NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Un seul workflow actif par unité métier
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflows_active_unit
+    ON workflows (COALESCE(business_unit, '')) WHERE is_active;
+
+CREATE TABLE IF NOT EXISTS workflow_transitions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    workflow_id UUID NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    required_fields TEXT[] NOT NULL DEFAULT '{}',
+    UNIQUE (workflow_id, from_status, to_status)
+);
+
+ALTER TABLE risk_status_history ADD COLUMN IF NOT EXISTS justification TEXT;
+
+-- Workflow par défaut : reprend les transitions historiquement codées en dur
+WITH default_workflow AS (
+    INSERT INTO workflows (name, states, is_active)
+    VALUES (
+        'Workflow par défaut',
+        ARRAY['Identified', 'Assessed', 'InTreatment', 'Monitoring', 'Accepted', 'Rejected', 'Transferred', 'Closed'],
+        true
+    )
+    RETURNING id
+)
+INSERT INTO workflow_transitions (workflow_id, from_status, to_status)
+SELECT default_workflow.id, t.from_status, t.to_status
+FROM default_workflow, (VALUES
+    ('Identified', 'Assessed'),
+    ('Assessed', 'InTreatment'),
+    ('InTreatment', 'Monitoring'),
+    ('Monitoring', 'Accepted'),
+    ('Monitoring', 'Rejected'),
+    ('Monitoring', 'Transferred'),
+    ('InTreatment', 'Closed')
+) AS t(from_status, to_status);
@@ -0,0 +1,7 @@
+-- Auteur et approbateur des changements de statut
+
+ALTER TABLE risk_status_history
+    ADD COLUMN IF NOT EXISTS changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS changed_by_username TEXT,
+    ADD COLUMN IF NOT EXISTS approved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS approved_by_username TEXT;
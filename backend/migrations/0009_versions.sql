@@ -0,0 +1,5 @@
+-- Numéro de version pour le contrôle de concurrence optimiste (ETag / If-Match)
+
+ALTER TABLE risks ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE incidents ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE workflows ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,50 @@
+-- Gravité et statut des incidents typés, cycle de vie et historique des changements de statut
+
+UPDATE incidents SET severity = CASE severity
+    WHEN 'Faible' THEN 'Low'
+    WHEN 'Moyenne' THEN 'Medium'
+    WHEN 'Élevée' THEN 'High'
+    WHEN 'Haute' THEN 'High'
+    WHEN 'Critique' THEN 'Critical'
+    ELSE severity
+END;
+
+UPDATE incidents SET severity = 'Medium'
+WHERE severity NOT IN ('Low', 'Medium', 'High', 'Critical');
+
+UPDATE incidents SET status = CASE status
+    WHEN 'Nouveau' THEN 'New'
+    WHEN 'En cours' THEN 'Investigating'
+    WHEN 'Résolu' THEN 'Resolved'
+    WHEN 'Clos' THEN 'Closed'
+    WHEN 'Fermé' THEN 'Closed'
+    ELSE status
+END;
+
+UPDATE incidents SET status = 'New'
+WHERE status NOT IN ('New', 'Triaged', 'Investigating', 'Contained', 'Resolved', 'Closed');
+
+ALTER TABLE incidents
+    ALTER COLUMN status SET DEFAULT 'New',
+    ADD CONSTRAINT incidents_severity_check CHECK (severity IN ('Low', 'Medium', 'High', 'Critical')),
+    ADD CONSTRAINT incidents_status_check CHECK (status IN (
+        'New',
+        'Triaged',
+        'Investigating',
+        'Contained',
+        'Resolved',
+        'Closed'
+    ));
+
+CREATE TABLE IF NOT EXISTS incident_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    old_status TEXT NOT NULL,
+    new_status TEXT NOT NULL,
+    comment TEXT,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    changed_by_username TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_status_history_incident_id ON incident_status_history(incident_id);
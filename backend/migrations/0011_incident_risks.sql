@@ -0,0 +1,16 @@
+-- Liens multiples entre incidents et risques ; `related_risk_id` reste le risque principal
+
+CREATE TABLE IF NOT EXISTS incident_risks (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (incident_id, risk_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_risks_risk_id ON incident_risks(risk_id);
+
+INSERT INTO incident_risks (incident_id, risk_id, linked_at)
+SELECT id, related_risk_id, created_at
+FROM incidents
+WHERE related_risk_id IS NOT NULL
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,18 @@
+-- Recherche plein texte sur les risques et les incidents
+
+ALTER TABLE risks ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('french', coalesce(title, '')), 'A')
+        || setweight(to_tsvector('french', coalesce(description, '')), 'B')
+        || setweight(to_tsvector('french', coalesce(evaluation_notes, '') || ' ' || coalesce(additional_notes, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_risks_search_vector ON risks USING GIN (search_vector);
+
+ALTER TABLE incidents ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('french', coalesce(title, '')), 'A')
+        || setweight(to_tsvector('french', coalesce(description, '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_incidents_search_vector ON incidents USING GIN (search_vector);
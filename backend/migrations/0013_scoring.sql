@@ -0,0 +1,10 @@
+-- Score canonique des risques, calculé par le service de notation selon la méthode du risque
+
+ALTER TABLE risks
+    ADD COLUMN IF NOT EXISTS scoring_method TEXT NOT NULL DEFAULT 'ImpactProbability'
+        CONSTRAINT risks_scoring_method_check CHECK (scoring_method IN ('ImpactProbability', 'Fmea', 'Weighted')),
+    ADD COLUMN IF NOT EXISTS score INTEGER;
+
+UPDATE risks SET score = impact * probability;
+
+CREATE INDEX IF NOT EXISTS idx_risks_score ON risks(score);
@@ -0,0 +1,23 @@
+-- Niveau de risque (Low, Medium, High, Critical...) déduit du score et des seuils configurés
+
+ALTER TABLE risks ADD COLUMN IF NOT EXISTS level TEXT;
+
+-- Seuils par défaut ; le serveur recalcule les niveaux au démarrage selon RISK_LEVELS
+UPDATE risks SET level = CASE
+    WHEN score >= 15 THEN 'Critical'
+    WHEN score >= 10 THEN 'High'
+    WHEN score >= 5 THEN 'Medium'
+    WHEN score >= 1 THEN 'Low'
+END;
+
+-- Niveaux de risque (seuils par méthode, couleurs) modifiables par un administrateur ;
+-- ligne unique, enregistrée au premier démarrage à partir de la configuration (`RISK_LEVELS`...)
+
+CREATE TABLE IF NOT EXISTS risk_level_settings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    levels JSONB NOT NULL,
+    version INTEGER NOT NULL DEFAULT 1,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_risk_level_settings_single_row ON risk_level_settings((true));
@@ -0,0 +1,9 @@
+-- Historique des évaluations : évaluateur, notes et score du risque après chaque évaluation
+
+ALTER TABLE risk_evaluation
+    ADD COLUMN IF NOT EXISTS evaluated_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS evaluated_by_username TEXT,
+    ADD COLUMN IF NOT EXISTS notes TEXT,
+    ADD COLUMN IF NOT EXISTS risk_score INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_risk_evaluation_risk_created ON risk_evaluation(risk_id, created_at);
@@ -0,0 +1,21 @@
+-- Risque inhérent (colonnes existantes), résiduel (après mesures) et cible, chacun noté séparément
+
+ALTER TABLE risks
+    ADD COLUMN IF NOT EXISTS residual_impact INTEGER,
+    ADD COLUMN IF NOT EXISTS residual_probability INTEGER,
+    ADD COLUMN IF NOT EXISTS residual_score INTEGER,
+    ADD COLUMN IF NOT EXISTS residual_level TEXT,
+    ADD COLUMN IF NOT EXISTS target_impact INTEGER,
+    ADD COLUMN IF NOT EXISTS target_probability INTEGER,
+    ADD COLUMN IF NOT EXISTS target_score INTEGER,
+    ADD COLUMN IF NOT EXISTS target_level TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_risks_residual_score ON risks(residual_score);
+CREATE INDEX IF NOT EXISTS idx_risks_target_score ON risks(target_score);
+
+-- Les évaluations existantes portent sur le risque inhérent
+ALTER TABLE risk_evaluation
+    ADD COLUMN IF NOT EXISTS basis TEXT NOT NULL DEFAULT 'Inherent'
+        CONSTRAINT risk_evaluation_basis_check CHECK (basis IN ('Inherent', 'Residual', 'Target'));
+
+CREATE INDEX IF NOT EXISTS idx_risk_evaluation_risk_basis ON risk_evaluation(risk_id, basis, created_at);
@@ -0,0 +1,44 @@
+-- Bibliothèque de mesures de maîtrise (contrôles), liées aux risques
+
+CREATE TABLE IF NOT EXISTS controls (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    description TEXT,
+    control_type TEXT NOT NULL
+        CONSTRAINT controls_type_check CHECK (control_type IN ('Preventive', 'Detective', 'Corrective', 'Directive')),
+    owner TEXT,
+    frequency TEXT
+        CONSTRAINT controls_frequency_check
+        CHECK (frequency IN ('Continuous', 'Daily', 'Weekly', 'Monthly', 'Quarterly', 'Annually', 'AdHoc')),
+    design_effectiveness TEXT NOT NULL DEFAULT 'NotTested'
+        CONSTRAINT controls_design_effectiveness_check
+        CHECK (design_effectiveness IN ('Effective', 'PartiallyEffective', 'Ineffective', 'NotTested')),
+    operating_effectiveness TEXT NOT NULL DEFAULT 'NotTested'
+        CONSTRAINT controls_operating_effectiveness_check
+        CHECK (operating_effectiveness IN ('Effective', 'PartiallyEffective', 'Ineffective', 'NotTested')),
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS risk_controls (
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    control_id UUID NOT NULL REFERENCES controls(id) ON DELETE CASCADE,
+    linked_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (risk_id, control_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_risk_controls_control_id ON risk_controls(control_id);
+
+-- Reprise de l'ancien champ libre `control_measure_id` : un contrôle (non testé) par valeur distincte
+INSERT INTO controls (name, control_type)
+SELECT DISTINCT trim(control_measure_id), 'Preventive'
+FROM risks
+WHERE trim(coalesce(control_measure_id, '')) <> ''
+    AND NOT EXISTS (SELECT 1 FROM controls c WHERE c.name = trim(risks.control_measure_id));
+
+INSERT INTO risk_controls (risk_id, control_id)
+SELECT r.id, c.id
+FROM risks r
+JOIN controls c ON c.name = trim(r.control_measure_id)
+ON CONFLICT DO NOTHING;
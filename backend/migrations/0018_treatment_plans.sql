@@ -0,0 +1,39 @@
+-- Plans de traitement des risques et actions associées
+
+CREATE TABLE IF NOT EXISTS treatment_plans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    strategy TEXT NOT NULL
+        CONSTRAINT treatment_plans_strategy_check CHECK (strategy IN ('Mitigate', 'Avoid', 'Transfer', 'Accept')),
+    description TEXT,
+    owner TEXT,
+    due_date DATE,
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_treatment_plans_risk_id ON treatment_plans(risk_id);
+
+CREATE TABLE IF NOT EXISTS action_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    plan_id UUID NOT NULL REFERENCES treatment_plans(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    description TEXT,
+    assignee_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    due_date DATE,
+    progress INTEGER NOT NULL DEFAULT 0
+        CONSTRAINT action_items_progress_check CHECK (progress BETWEEN 0 AND 100),
+    status TEXT NOT NULL DEFAULT 'Open'
+        CONSTRAINT action_items_status_check CHECK (status IN ('Open', 'InProgress', 'Done', 'Cancelled')),
+    -- Une action obligatoire encore ouverte empêche le passage du risque en suivi
+    mandatory BOOLEAN NOT NULL DEFAULT true,
+    completed_at TIMESTAMPTZ,
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_action_items_plan_id ON action_items(plan_id);
+CREATE INDEX IF NOT EXISTS idx_action_items_assignee_due ON action_items(assignee_id, due_date);
@@ -0,0 +1,10 @@
+-- Revue périodique des risques : dernière revue, prochaine échéance et retard
+
+ALTER TABLE risks
+    ADD COLUMN IF NOT EXISTS last_reviewed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS next_review_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS review_overdue BOOLEAN NOT NULL DEFAULT false;
+
+-- Échéances calculées au démarrage du serveur selon les fréquences configurées
+CREATE INDEX IF NOT EXISTS idx_risks_next_review_at ON risks(next_review_at);
+CREATE INDEX IF NOT EXISTS idx_risks_review_overdue ON risks(review_overdue) WHERE review_overdue;
@@ -0,0 +1,44 @@
+-- Appétence et tolérance au risque par catégorie, et dépassements de tolérance constatés
+
+CREATE TABLE IF NOT EXISTS risk_appetites (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    category TEXT NOT NULL,
+    -- Seuils exprimés sur le score inhérent canonique (impact × probabilité)
+    appetite INTEGER NOT NULL,
+    tolerance INTEGER NOT NULL,
+    -- Seuils propres aux scores AMDEC et pondérés, dont l'échelle diffère ; sans seuils pour sa méthode, un risque n'est pas comparé
+    fmea_appetite INTEGER,
+    fmea_tolerance INTEGER,
+    weighted_appetite INTEGER,
+    weighted_tolerance INTEGER,
+    version INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CONSTRAINT risk_appetites_thresholds_check CHECK (appetite >= 0 AND appetite <= tolerance),
+    CONSTRAINT risk_appetites_fmea_thresholds_check
+        CHECK ((fmea_appetite IS NULL) = (fmea_tolerance IS NULL) AND fmea_appetite >= 0 AND fmea_appetite <= fmea_tolerance),
+    CONSTRAINT risk_appetites_weighted_thresholds_check
+        CHECK ((weighted_appetite IS NULL) = (weighted_tolerance IS NULL) AND weighted_appetite >= 0 AND weighted_appetite <= weighted_tolerance)
+);
+
+-- Une seule définition par catégorie, sans tenir compte de la casse
+CREATE UNIQUE INDEX IF NOT EXISTS idx_risk_appetites_category ON risk_appetites(lower(category));
+
+CREATE TABLE IF NOT EXISTS appetite_breaches (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    risk_id UUID NOT NULL REFERENCES risks(id) ON DELETE CASCADE,
+    -- Catégorie, méthode de notation, score et seuils au moment du dépassement
+    category TEXT NOT NULL,
+    scoring_method TEXT NOT NULL,
+    score INTEGER NOT NULL,
+    appetite INTEGER NOT NULL,
+    tolerance INTEGER NOT NULL,
+    trigger TEXT NOT NULL
+        CONSTRAINT appetite_breaches_trigger_check CHECK (trigger IN ('Create', 'Update', 'Evaluation')),
+    triggered_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    triggered_by_username TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS idx_appetite_breaches_risk_id ON appetite_breaches(risk_id);
+CREATE INDEX IF NOT EXISTS idx_appetite_breaches_created_at ON appetite_breaches(created_at);
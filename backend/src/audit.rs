@@ -0,0 +1,67 @@
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use sqlx::Postgres;
+use uuid::Uuid;
+
+use crate::auth::AuthenticatedUser;
+use crate::models::audit::AuditAction;
+
+// Champs techniques mis à jour à chaque écriture, sans intérêt dans le journal
+const IGNORED_FIELDS: &[&str] = &["updated_at", "version"];
+
+fn to_object<T: Serialize>(entity: Option<&T>) -> Map<String, Value> {
+    match entity.map(serde_json::to_value) {
+        Some(Ok(Value::Object(map))) => map,
+        _ => Map::new(),
+    }
+}
+
+// Différences champ par champ entre deux états d'une entité (`None` = inexistante)
+pub fn diff<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Value {
+    let before = to_object(before);
+    let after = to_object(after);
+
+    let mut changes = Map::new();
+    for key in before.keys().chain(after.keys()) {
+        if IGNORED_FIELDS.contains(&key.as_str()) || changes.contains_key(key) {
+            continue;
+        }
+        let old = before.get(key).cloned().unwrap_or(Value::Null);
+        let new = after.get(key).cloned().unwrap_or(Value::Null);
+        if old != new {
+            changes.insert(key.clone(), json!({ "before": old, "after": new }));
+        }
+    }
+    Value::Object(changes)
+}
+
+pub async fn record<'e, E, T>(
+    executor: E,
+    actor: &AuthenticatedUser,
+    entity_type: &str,
+    entity_id: Uuid,
+    action: AuditAction,
+    before: Option<&T>,
+    after: Option<&T>,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+    T: Serialize,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO audit_log (entity_type, entity_id, action, actor_id, actor_username, changes)
+        VALUES ($1, $2, $3, $4, $5, $6);
+        "#
+    )
+    .bind(entity_type)
+    .bind(entity_id)
+    .bind(action)
+    .bind(actor.id)
+    .bind(&actor.username)
+    .bind(diff(before, after))
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{Payload, ServiceRequest, ServiceResponse};
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::middleware::Next;
+use actix_web::{web, Error, FromRequest, HttpMessage, HttpRequest, ResponseError};
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::env;
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::LazyLock;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::user::{Role, User};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: Uuid,
+    pub username: String,
+    // Rôle à l'émission, indicatif : les permissions suivent le rôle courant en base
+    pub role: Role,
+    pub iat: i64,
+    pub exp: i64,
+}
+
+#[derive(Clone)]
+pub struct JwtConfig {
+    secret: String,
+    ttl: Duration,
+}
+
+// Valeurs d'exemple qui ne doivent jamais servir de clé de signature
+const PLACEHOLDER_SECRETS: &[&str] = &["change-me-in-production", "change-me", "changeme", "secret"];
+
+impl JwtConfig {
+    // Refuse de démarrer sans clé de signature propre au déploiement
+    pub fn from_env() -> Self {
+        let secret = env::var("JWT_SECRET").expect("JWT_SECRET non trouvé dans l'environnement");
+        let trimmed = secret.trim();
+        if trimmed.is_empty() || PLACEHOLDER_SECRETS.iter().any(|p| p.eq_ignore_ascii_case(trimmed)) {
+            panic!("JWT_SECRET doit être une clé secrète propre au déploiement (ni vide, ni valeur d'exemple)");
+        }
+        let ttl_hours = env::var("JWT_TTL_HOURS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(8);
+
+        JwtConfig { secret, ttl: Duration::hours(ttl_hours) }
+    }
+
+    pub fn issue(&self, user: &User) -> Result<(String, DateTime<Utc>), jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let expires_at = now + self.ttl;
+        let claims = Claims {
+            sub: user.id,
+            username: user.username.clone(),
+            role: user.role,
+            iat: now.timestamp(),
+            exp: expires_at.timestamp(),
+        };
+        let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(self.secret.as_bytes()))?;
+        Ok((token, expires_at))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+        decode::<Claims>(token, &DecodingKey::from_secret(self.secret.as_bytes()), &Validation::default())
+            .map(|data| data.claims)
+    }
+}
+
+// Longueur minimale d'un mot de passe, compte initial compris
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+
+// Motif de refus d'un mot de passe : trop court, identique au nom d'utilisateur ou trivial
+pub fn password_problem(username: &str, password: &str) -> Option<String> {
+    if password.trim().chars().count() < PASSWORD_MIN_LENGTH {
+        Some(format!("Au moins {} caractères requis", PASSWORD_MIN_LENGTH))
+    } else if password == username || password.eq_ignore_ascii_case("admin") {
+        Some("Ne doit être ni le nom d'utilisateur, ni « admin »".to_string())
+    } else {
+        None
+    }
+}
+
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
+
+// Empreinte sans compte associé, calculée une fois avec les paramètres des vraies empreintes
+static UNKNOWN_USER_HASH: LazyLock<String> =
+    LazyLock::new(|| hash_password("utilisateur-inconnu").expect("Calcul de l'empreinte de référence impossible"));
+
+// Vérification à blanc pour un utilisateur inconnu : la réponse prend le même temps que pour un
+// utilisateur existant, ce qui empêche de deviner les noms d'utilisateur
+pub fn verify_unknown_user(password: &str) -> bool {
+    verify_password(password, &UNKNOWN_USER_HASH);
+    false
+}
+
+// Utilisateur authentifié, placé dans les extensions de la requête par `require_auth`
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: Uuid,
+    pub username: String,
+    pub role: Role,
+}
+
+impl FromRequest for AuthenticatedUser {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions()
+                .get::<AuthenticatedUser>()
+                .cloned()
+                .ok_or_else(|| ApiError::unauthorized("Authentification requise")),
+        )
+    }
+}
+
+// Permissions vérifiées par l'extracteur `Authorized<P>`
+pub trait Permission {
+    fn allows(role: Role) -> bool;
+}
+
+// Consultation des risques, incidents et pièces jointes
+pub struct Read;
+// Création et modification des risques, évaluations, incidents et pièces jointes
+pub struct Edit;
+// Changement de statut d'un risque
+pub struct ChangeStatus;
+// Suppression et administration
+pub struct Admin;
+
+impl Permission for Read {
+    fn allows(_: Role) -> bool {
+        true
+    }
+}
+
+impl Permission for Edit {
+    fn allows(role: Role) -> bool {
+        role >= Role::Analyst
+    }
+}
+
+impl Permission for ChangeStatus {
+    fn allows(role: Role) -> bool {
+        role >= Role::RiskOwner
+    }
+}
+
+impl Permission for Admin {
+    fn allows(role: Role) -> bool {
+        role == Role::Admin
+    }
+}
+
+// Extracteur : utilisateur authentifié disposant de la permission `P` (sinon 401 / 403)
+pub struct Authorized<P: Permission> {
+    pub user: AuthenticatedUser,
+    _permission: PhantomData<P>,
+}
+
+impl<P: Permission> Deref for Authorized<P> {
+    type Target = AuthenticatedUser;
+
+    fn deref(&self) -> &Self::Target {
+        &self.user
+    }
+}
+
+impl<P: Permission> FromRequest for Authorized<P> {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let result = match req.extensions().get::<AuthenticatedUser>().cloned() {
+            None => Err(ApiError::unauthorized("Authentification requise")),
+            Some(user) if P::allows(user.role) => Ok(Authorized { user, _permission: PhantomData }),
+            Some(user) => Err(ApiError::forbidden(format!("Permission insuffisante pour le rôle {}", user.role))),
+        };
+        ready(result)
+    }
+}
+
+fn bearer_token(req: &ServiceRequest) -> Option<&str> {
+    req.headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+// Compte courant du porteur du jeton : un rôle modifié ou un compte supprimé
+// prend effet immédiatement, sans attendre l'expiration du jeton
+async fn current_account(pool: &PgPool, claims: &Claims) -> Result<Option<AuthenticatedUser>, ApiError> {
+    let account = sqlx::query_as::<_, (String, Role)>("SELECT username, role FROM users WHERE id = $1")
+        .bind(claims.sub)
+        .fetch_optional(pool)
+        .await?;
+    Ok(account.map(|(username, role)| AuthenticatedUser { id: claims.sub, username, role }))
+}
+
+// Middleware : rejette (401) toute requête sans jeton JWT valide ou dont le compte n'existe plus
+pub async fn require_auth<B: MessageBody>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let claims = req
+        .app_data::<web::Data<JwtConfig>>()
+        .and_then(|config| bearer_token(&req).and_then(|token| config.verify(token).ok()));
+
+    let account = match (claims, req.app_data::<web::Data<PgPool>>()) {
+        (Some(claims), Some(pool)) => current_account(pool, &claims).await,
+        _ => Ok(None),
+    };
+
+    match account {
+        Ok(Some(user)) => {
+            req.extensions_mut().insert(user);
+            next.call(req).await.map(ServiceResponse::map_into_left_body)
+        }
+        Ok(None) => {
+            let response = ApiError::unauthorized("Authentification requise").error_response();
+            Ok(req.into_response(response).map_into_right_body())
+        }
+        Err(error) => Ok(req.into_response(error.error_response()).map_into_right_body()),
+    }
+}
+
+// Crée le premier compte à partir de ADMIN_USERNAME / ADMIN_PASSWORD si aucun utilisateur n'existe ;
+// le mot de passe doit être fourni explicitement, il n'a pas de valeur par défaut
+pub async fn bootstrap_user(pool: &PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let has_users = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM users)")
+        .fetch_one(pool)
+        .await?;
+    if has_users {
+        return Ok(());
+    }
+
+    let username = env::var("ADMIN_USERNAME")
+        .ok()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .ok_or("ADMIN_USERNAME requis pour créer le premier compte")?;
+    let password = env::var("ADMIN_PASSWORD").map_err(|_| "ADMIN_PASSWORD requis pour créer le premier compte")?;
+    if let Some(problem) = password_problem(&username, &password) {
+        return Err(format!("ADMIN_PASSWORD refusé : {}", problem).into());
+    }
+
+    let password_hash = hash_password(&password).map_err(|e| e.to_string())?;
+    sqlx::query("INSERT INTO users (username, password_hash, role) VALUES ($1, $2, $3)")
+        .bind(&username)
+        .bind(&password_hash)
+        .bind(Role::Admin)
+        .execute(pool)
+        .await?;
+
+    log::info!("Utilisateur initial « {} » créé", username);
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use actix_web::dev::Payload;
+use actix_web::http::header::{EntityTag, ETag, IF_MATCH};
+use actix_web::{FromRequest, HttpRequest};
+use std::future::{ready, Ready};
+
+use crate::error::{ApiError, ErrorCode};
+
+// ETag d'une entité versionnée : "<version>"
+pub fn etag(version: i32) -> ETag {
+    ETag(EntityTag::new_strong(version.to_string()))
+}
+
+fn parse_version(value: &str) -> Option<i32> {
+    value.trim().trim_start_matches("W/").trim_matches('"').parse().ok()
+}
+
+// Extracteur : condition de l'en-tête If-Match (absent : 428, vide : 412) ; `*` accepte toute
+// version existante, une liste accepte chacune des versions citées (RFC 9110, § 13.1.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfMatch {
+    Any,
+    Versions(Vec<i32>),
+}
+
+impl IfMatch {
+    fn parse<'a>(values: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let tags: Vec<&str> = values
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .collect();
+        if tags.is_empty() {
+            None
+        } else if tags.contains(&"*") {
+            Some(IfMatch::Any)
+        } else {
+            // Une étiquette qui n'est pas une de nos versions ne correspond simplement à aucune
+            Some(IfMatch::Versions(tags.into_iter().filter_map(parse_version).collect()))
+        }
+    }
+
+    // La version courante de la ressource satisfait-elle la condition ?
+    pub fn matches(&self, version: i32) -> bool {
+        match self {
+            IfMatch::Any => true,
+            IfMatch::Versions(versions) => versions.contains(&version),
+        }
+    }
+}
+
+impl FromRequest for IfMatch {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let headers: Vec<_> = req.headers().get_all(IF_MATCH).collect();
+        let result = if headers.is_empty() {
+            Err(ApiError::new(ErrorCode::PreconditionRequired, "En-tête If-Match requis"))
+        } else {
+            headers
+                .iter()
+                .map(|value| value.to_str().ok())
+                .collect::<Option<Vec<&str>>>()
+                .and_then(|values| IfMatch::parse(values.into_iter()))
+                .ok_or_else(|| ApiError::new(ErrorCode::VersionMismatch, "En-tête If-Match invalide"))
+        };
+        ready(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: &str) -> Option<IfMatch> {
+        IfMatch::parse(std::iter::once(value))
+    }
+
+    #[test]
+    fn star_matches_any_version() {
+        assert_eq!(parse("*"), Some(IfMatch::Any));
+        assert!(parse(" * ").unwrap().matches(42));
+    }
+
+    #[test]
+    fn lists_match_any_listed_version() {
+        let condition = parse("\"3\", W/\"5\", \"autre-serveur\"").unwrap();
+        assert_eq!(condition, IfMatch::Versions(vec![3, 5]));
+        assert!(condition.matches(3));
+        assert!(condition.matches(5));
+        assert!(!condition.matches(4));
+    }
+
+    #[test]
+    fn empty_headers_are_invalid() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse(" , "), None);
+        assert!(!parse("\"abc\"").unwrap().matches(1));
+    }
+}
@@ -0,0 +1,320 @@
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+use crate::audit;
+use crate::auth::{Admin, AuthenticatedUser, Authorized, Read};
+use crate::concurrency::{etag, IfMatch};
+use crate::error::ApiError;
+use crate::listing;
+use crate::models::appetite::{AppetiteBreach, BreachTrigger, NewRiskAppetite, RiskAppetite, ToleranceExcess};
+use crate::models::audit::AuditAction;
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+// Seuils de la catégorie d'un risque (`r`), sans tenir compte de la casse ni des espaces ;
+// les risques clos ou rejetés ne sont plus comparés à la tolérance
+const APPETITE_JOIN: &str = r#"
+    JOIN risk_appetites a ON lower(a.category) = lower(trim(r.category))
+    WHERE r.status NOT IN ('Closed', 'Rejected')
+"#;
+
+// Seuils de la catégorie sur l'échelle de la méthode de notation du risque ; `NULL` si la catégorie
+// n'en fixe pas pour cette méthode, le risque n'étant alors jamais au-delà de la tolérance
+const METHOD_APPETITE: &str =
+    "CASE r.scoring_method WHEN 'Fmea' THEN a.fmea_appetite WHEN 'Weighted' THEN a.weighted_appetite ELSE a.appetite END";
+const METHOD_TOLERANCE: &str =
+    "CASE r.scoring_method WHEN 'Fmea' THEN a.fmea_tolerance WHEN 'Weighted' THEN a.weighted_tolerance ELSE a.tolerance END";
+
+// Le score inhérent du risque dépasse-t-il la tolérance de sa catégorie ?
+pub async fn exceeds_tolerance(conn: &mut PgConnection, risk_id: Uuid) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>(&format!(
+        "SELECT EXISTS (SELECT 1 FROM risks r {APPETITE_JOIN} AND r.id = $1 AND r.score > {METHOD_TOLERANCE})"
+    ))
+    .bind(risk_id)
+    .fetch_one(conn)
+    .await
+}
+
+// Enregistre un dépassement si le risque est au-delà de la tolérance de sa catégorie ;
+// à appeler seulement lorsque l'écriture vient de faire franchir le seuil
+pub async fn record_breach(
+    conn: &mut PgConnection,
+    risk_id: Uuid,
+    trigger: BreachTrigger,
+    actor: &AuthenticatedUser,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar::<_, Uuid>(&format!(
+        r#"
+        INSERT INTO appetite_breaches (
+            risk_id, category, scoring_method, score, appetite, tolerance, trigger, triggered_by, triggered_by_username
+        )
+        SELECT r.id, a.category, r.scoring_method, r.score, {METHOD_APPETITE}, {METHOD_TOLERANCE}, $2, $3, $4
+        FROM risks r {APPETITE_JOIN} AND r.id = $1 AND r.score > {METHOD_TOLERANCE}
+        RETURNING id
+        "#
+    ))
+    .bind(risk_id)
+    .bind(trigger)
+    .bind(actor.id)
+    .bind(&actor.username)
+    .fetch_optional(conn)
+    .await
+}
+
+fn appetite_not_found() -> ApiError {
+    ApiError::not_found("Appétence non trouvée")
+}
+
+async fn fetch_appetite<'e, E>(executor: E, id: Uuid) -> Result<Option<RiskAppetite>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, RiskAppetite>("SELECT * FROM risk_appetites WHERE id = $1")
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+}
+
+async fn fetch_appetite_for_update(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<RiskAppetite>, sqlx::Error> {
+    sqlx::query_as::<_, RiskAppetite>("SELECT * FROM risk_appetites WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+}
+
+#[get("/risk-appetites")]
+pub async fn get_all_appetites(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+) -> Result<HttpResponse, ApiError> {
+    let appetites = sqlx::query_as::<_, RiskAppetite>("SELECT * FROM risk_appetites ORDER BY lower(category)")
+        .fetch_all(db_pool.get_ref())
+        .await?;
+    Ok(HttpResponse::Ok().json(appetites))
+}
+
+async fn insert_appetite(
+    db_pool: &PgPool,
+    data: &NewRiskAppetite,
+    actor: &AuthenticatedUser,
+) -> Result<RiskAppetite, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let appetite = sqlx::query_as::<_, RiskAppetite>(
+        r#"
+        INSERT INTO risk_appetites (
+            category, appetite, tolerance, fmea_appetite, fmea_tolerance, weighted_appetite, weighted_tolerance
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#
+    )
+    .bind(data.category.trim())
+    .bind(data.appetite)
+    .bind(data.tolerance)
+    .bind(data.fmea_appetite)
+    .bind(data.fmea_tolerance)
+    .bind(data.weighted_appetite)
+    .bind(data.weighted_tolerance)
+    .fetch_one(&mut *tx)
+    .await?;
+    audit::record(&mut *tx, actor, "risk_appetite", appetite.id, AuditAction::Create, None, Some(&appetite)).await?;
+
+    tx.commit().await?;
+    Ok(appetite)
+}
+
+#[post("/risk-appetites")]
+pub async fn create_appetite(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    rules: web::Data<ValidationConfig>,
+    data: web::Json<NewRiskAppetite>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let appetite = insert_appetite(db_pool.get_ref(), &data, &user).await?;
+    Ok(HttpResponse::Created().insert_header(etag(appetite.version)).json(appetite))
+}
+
+#[get("/risk-appetites/{id}")]
+pub async fn get_appetite_by_id(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let appetite = fetch_appetite(db_pool.get_ref(), *id).await?.ok_or_else(appetite_not_found)?;
+    Ok(HttpResponse::Ok().insert_header(etag(appetite.version)).json(appetite))
+}
+
+async fn save_appetite(
+    db_pool: &PgPool,
+    id: Uuid,
+    data: &NewRiskAppetite,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<RiskAppetite, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_appetite_for_update(&mut tx, id).await?.ok_or_else(appetite_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    let updated = sqlx::query_as::<_, RiskAppetite>(
+        r#"
+        UPDATE risk_appetites
+        SET category = $1, appetite = $2, tolerance = $3, fmea_appetite = $4, fmea_tolerance = $5,
+            weighted_appetite = $6, weighted_tolerance = $7, version = version + 1, updated_at = now()
+        WHERE id = $8
+        RETURNING *
+        "#
+    )
+    .bind(data.category.trim())
+    .bind(data.appetite)
+    .bind(data.tolerance)
+    .bind(data.fmea_appetite)
+    .bind(data.fmea_tolerance)
+    .bind(data.weighted_appetite)
+    .bind(data.weighted_tolerance)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+    audit::record(&mut *tx, actor, "risk_appetite", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[put("/risk-appetites/{id}")]
+pub async fn update_appetite(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    id: web::Path<Uuid>,
+    data: web::Json<NewRiskAppetite>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let appetite = save_appetite(db_pool.get_ref(), *id, &data, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(appetite.version)).json(appetite))
+}
+
+async fn remove_appetite(
+    db_pool: &PgPool,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<RiskAppetite, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_appetite_for_update(&mut tx, id).await?.ok_or_else(appetite_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    sqlx::query("DELETE FROM risk_appetites WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    audit::record(&mut *tx, actor, "risk_appetite", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(before)
+}
+
+#[delete("/risk-appetites/{id}")]
+pub async fn delete_appetite(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    remove_appetite(db_pool.get_ref(), *id, &if_match, &user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[derive(serde::Deserialize)]
+pub struct ToleranceQuery {
+    pub category: Option<String>,
+}
+
+// Risques actuellement au-delà de la tolérance de leur catégorie ; les échelles différant d'une méthode
+// à l'autre, ils sont classés par écart relatif à la tolérance, du plus grand au plus petit
+#[get("/risks/out-of-tolerance")]
+pub async fn get_out_of_tolerance_risks(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<ToleranceQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let category = query.category.as_deref().map(str::trim).filter(|c| !c.is_empty());
+    let risks = sqlx::query_as::<_, ToleranceExcess>(&format!(
+        r#"
+        SELECT * FROM (
+            SELECT r.id AS risk_id, r.title, r.status, a.category, r.scoring_method, r.score, r.level,
+                {METHOD_APPETITE} AS appetite, {METHOD_TOLERANCE} AS tolerance, r.score - {METHOD_TOLERANCE} AS excess
+            FROM risks r {APPETITE_JOIN}
+                AND r.score > {METHOD_TOLERANCE}
+                AND ($1::text IS NULL OR lower(a.category) = lower($1))
+        ) e
+        ORDER BY e.excess::float8 / GREATEST(e.tolerance, 1) DESC, e.score DESC, e.risk_id
+        "#
+    ))
+    .bind(category)
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(risks))
+}
+
+// Filtres et pagination de `GET /appetite-breaches`
+#[derive(serde::Deserialize)]
+pub struct BreachQuery {
+    pub risk_id: Option<Uuid>,
+    pub category: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+fn push_breach_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &BreachQuery) {
+    builder.push(" WHERE TRUE");
+    if let Some(risk_id) = query.risk_id {
+        builder.push(" AND b.risk_id = ").push_bind(risk_id);
+    }
+    if let Some(category) = query.category.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+        builder.push(" AND lower(b.category) = lower(").push_bind(category.to_string()).push(")");
+    }
+}
+
+// Dépassements de tolérance enregistrés, du plus récent au plus ancien
+#[get("/appetite-breaches")]
+pub async fn get_appetite_breaches(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<BreachQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mut errors = FieldErrors::new();
+    let (limit, offset) = listing::page(query.page, query.per_page, &mut errors);
+    errors.into_result()?;
+
+    let mut count = QueryBuilder::new("SELECT COUNT(*) FROM appetite_breaches b");
+    push_breach_filters(&mut count, &query);
+    let total = count.build_query_scalar::<i64>().fetch_one(db_pool.get_ref()).await?;
+
+    let mut select = QueryBuilder::new(
+        "SELECT b.*, r.title AS risk_title FROM appetite_breaches b JOIN risks r ON r.id = b.risk_id",
+    );
+    push_breach_filters(&mut select, &query);
+    select
+        .push(" ORDER BY b.created_at DESC, b.id LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let breaches = select.build_query_as::<AppetiteBreach>().fetch_all(db_pool.get_ref()).await?;
+
+    Ok(listing::paginated(&breaches, total))
+}
@@ -0,0 +1,322 @@
+use actix_multipart::Multipart;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{delete, get, post, web, HttpResponse};
+use futures_util::StreamExt;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::audit;
+use crate::auth::{Authorized, AuthenticatedUser, Read, Edit, Admin};
+use crate::error::{ApiError, ErrorCode};
+use crate::models::attachment::Attachment;
+use crate::models::audit::AuditAction;
+use crate::storage::{content_matches, AttachmentConfig, AttachmentStorage};
+
+// Entité à laquelle une pièce jointe est rattachée
+#[derive(Clone, Copy)]
+enum AttachmentOwner {
+    Risk(Uuid),
+    Incident(Uuid),
+}
+
+impl AttachmentOwner {
+    fn table(&self) -> &'static str {
+        match self {
+            AttachmentOwner::Risk(_) => "risks",
+            AttachmentOwner::Incident(_) => "incidents",
+        }
+    }
+
+    fn column(&self) -> &'static str {
+        match self {
+            AttachmentOwner::Risk(_) => "risk_id",
+            AttachmentOwner::Incident(_) => "incident_id",
+        }
+    }
+
+    fn id(&self) -> Uuid {
+        match self {
+            AttachmentOwner::Risk(id) | AttachmentOwner::Incident(id) => *id,
+        }
+    }
+}
+
+struct UploadedFile {
+    filename: String,
+    content_type: String,
+    data: Vec<u8>,
+}
+
+// Ne conserve que le nom de fichier, sans chemin fourni par le client
+fn sanitize_filename(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or("").trim();
+    if name.is_empty() {
+        "fichier".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+async fn read_upload(mut payload: Multipart, config: &AttachmentConfig) -> Result<UploadedFile, ApiError> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| ApiError::bad_request(e.to_string()))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let filename = sanitize_filename(
+            field
+                .content_disposition()
+                .and_then(|cd| cd.get_filename())
+                .unwrap_or(""),
+        );
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+
+        if !config.is_allowed(&content_type) {
+            return Err(ApiError::new(
+                ErrorCode::UnsupportedMediaType,
+                format!("Type de fichier non autorisé : {}", content_type),
+            ));
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| ApiError::bad_request(e.to_string()))?;
+            if data.len() + chunk.len() > config.max_size {
+                return Err(ApiError::new(
+                    ErrorCode::PayloadTooLarge,
+                    format!("Fichier trop volumineux (max {} octets)", config.max_size),
+                )
+                .with_details(serde_json::json!({ "max_size": config.max_size })));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        // Le type déclaré ne suffit pas : extension et premiers octets doivent concorder
+        if !content_matches(&content_type, &filename, &data) {
+            return Err(ApiError::new(
+                ErrorCode::UnsupportedMediaType,
+                format!("Le contenu ou l'extension de « {} » ne correspond pas au type {}", filename, content_type),
+            ));
+        }
+
+        return Ok(UploadedFile { filename, content_type, data });
+    }
+
+    Err(ApiError::bad_request("Champ 'file' manquant"))
+}
+
+async fn upload_attachment(
+    db_pool: &PgPool,
+    storage: &dyn AttachmentStorage,
+    config: &AttachmentConfig,
+    owner: AttachmentOwner,
+    payload: Multipart,
+    actor: &AuthenticatedUser,
+) -> Result<HttpResponse, ApiError> {
+    let exists = sqlx::query_scalar::<_, bool>(&format!(
+        "SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)",
+        owner.table()
+    ))
+    .bind(owner.id())
+    .fetch_one(db_pool)
+    .await?;
+
+    if !exists {
+        return Err(ApiError::not_found("Entité non trouvée"));
+    }
+
+    let file = read_upload(payload, config).await?;
+
+    let checksum = hex::encode(Sha256::digest(&file.data));
+    let storage_key = Uuid::new_v4().to_string();
+
+    storage.put(&storage_key, &file.data).await.map_err(ApiError::internal)?;
+
+    let result = insert_attachment(db_pool, owner, &file, &checksum, &storage_key, actor).await;
+
+    match result {
+        Ok(attachment) => Ok(HttpResponse::Created().json(attachment)),
+        Err(e) => {
+            let _ = storage.delete(&storage_key).await;
+            Err(e.into())
+        }
+    }
+}
+
+// Métadonnées et entrée d'audit enregistrées ensemble
+async fn insert_attachment(
+    db_pool: &PgPool,
+    owner: AttachmentOwner,
+    file: &UploadedFile,
+    checksum: &str,
+    storage_key: &str,
+    actor: &AuthenticatedUser,
+) -> Result<Attachment, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+
+    let attachment = sqlx::query_as::<_, Attachment>(&format!(
+        r#"
+        INSERT INTO attachments ({}, filename, content_type, size_bytes, sha256, storage_key)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *;
+        "#,
+        owner.column()
+    ))
+    .bind(owner.id())
+    .bind(&file.filename)
+    .bind(&file.content_type)
+    .bind(file.data.len() as i64)
+    .bind(checksum)
+    .bind(storage_key)
+    .fetch_one(&mut *tx)
+    .await?;
+    audit::record(&mut *tx, actor, "attachment", attachment.id, AuditAction::Create, None, Some(&attachment)).await?;
+
+    tx.commit().await?;
+    Ok(attachment)
+}
+
+// Clés de stockage des pièces jointes d'une entité, à purger après sa suppression
+// (les lignes de `attachments` sont supprimées en cascade)
+async fn storage_keys(db_pool: &PgPool, owner: AttachmentOwner) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar::<_, String>(&format!(
+        "SELECT storage_key FROM attachments WHERE {} = $1",
+        owner.column()
+    ))
+    .bind(owner.id())
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn risk_storage_keys(db_pool: &PgPool, risk_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    storage_keys(db_pool, AttachmentOwner::Risk(risk_id)).await
+}
+
+pub async fn incident_storage_keys(db_pool: &PgPool, incident_id: Uuid) -> Result<Vec<String>, sqlx::Error> {
+    storage_keys(db_pool, AttachmentOwner::Incident(incident_id)).await
+}
+
+pub async fn purge_files(storage: &dyn AttachmentStorage, storage_keys: &[String]) {
+    for key in storage_keys {
+        if let Err(e) = storage.delete(key).await {
+            log::error!("Fichier {} non supprimé du stockage : {:?}", key, e);
+        }
+    }
+}
+
+async fn list_attachments(db_pool: &PgPool, owner: AttachmentOwner) -> Result<HttpResponse, ApiError> {
+    let attachments = sqlx::query_as::<_, Attachment>(&format!(
+        "SELECT * FROM attachments WHERE {} = $1 ORDER BY created_at ASC",
+        owner.column()
+    ))
+    .bind(owner.id())
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(HttpResponse::Ok().json(attachments))
+}
+
+#[post("/risks/{id}/attachments")]
+pub async fn upload_risk_attachment(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    storage: web::Data<dyn AttachmentStorage>,
+    config: web::Data<AttachmentConfig>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+) -> Result<HttpResponse, ApiError> {
+    let owner = AttachmentOwner::Risk(path.into_inner());
+    upload_attachment(db_pool.get_ref(), storage.get_ref(), &config, owner, payload, &user).await
+}
+
+#[get("/risks/{id}/attachments")]
+pub async fn get_risk_attachments(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    list_attachments(db_pool.get_ref(), AttachmentOwner::Risk(path.into_inner())).await
+}
+
+#[post("/incidents/{id}/attachments")]
+pub async fn upload_incident_attachment(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    storage: web::Data<dyn AttachmentStorage>,
+    config: web::Data<AttachmentConfig>,
+    path: web::Path<Uuid>,
+    payload: Multipart,
+) -> Result<HttpResponse, ApiError> {
+    let owner = AttachmentOwner::Incident(path.into_inner());
+    upload_attachment(db_pool.get_ref(), storage.get_ref(), &config, owner, payload, &user).await
+}
+
+#[get("/incidents/{id}/attachments")]
+pub async fn get_incident_attachments(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    list_attachments(db_pool.get_ref(), AttachmentOwner::Incident(path.into_inner())).await
+}
+
+#[get("/attachments/{id}/download")]
+pub async fn download_attachment(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    storage: web::Data<dyn AttachmentStorage>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+
+    let attachment = sqlx::query_as::<_, Attachment>("SELECT * FROM attachments WHERE id = $1")
+        .bind(id)
+        .fetch_optional(db_pool.get_ref())
+        .await?
+        .ok_or_else(attachment_not_found)?;
+
+    let data = storage.get(&attachment.storage_key).await.map_err(ApiError::internal)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(attachment.content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(attachment.filename)],
+        })
+        .insert_header(("X-Checksum-Sha256", attachment.sha256))
+        .body(data))
+}
+
+#[delete("/attachments/{id}")]
+pub async fn delete_attachment(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    storage: web::Data<dyn AttachmentStorage>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+    let mut tx = db_pool.begin().await?;
+
+    let attachment = sqlx::query_as::<_, Attachment>("DELETE FROM attachments WHERE id = $1 RETURNING *")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(attachment_not_found)?;
+    audit::record(&mut *tx, &user, "attachment", id, AuditAction::Delete, Some(&attachment), None).await?;
+
+    tx.commit().await?;
+
+    if let Err(e) = storage.delete(&attachment.storage_key).await {
+        log::error!("Fichier {} non supprimé du stockage : {:?}", attachment.storage_key, e);
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+fn attachment_not_found() -> ApiError {
+    ApiError::not_found("Pièce jointe non trouvée")
+}
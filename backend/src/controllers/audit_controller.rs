@@ -0,0 +1,36 @@
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{Authorized, Read};
+use crate::error::ApiError;
+use crate::models::audit::AuditEntry;
+
+#[derive(Deserialize)]
+pub struct AuditQuery {
+    // Type d'entité : "risk", "incident", "workflow", "control", "treatment_plan", "action_item", "risk_appetite" ou "attachment"
+    pub entity: String,
+    pub id: Uuid,
+}
+
+#[get("/audit")]
+pub async fn get_audit_log(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<AuditQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let entries = sqlx::query_as::<_, AuditEntry>(
+        r#"
+        SELECT * FROM audit_log
+        WHERE entity_type = $1 AND entity_id = $2
+        ORDER BY created_at ASC;
+        "#
+    )
+    .bind(&query.entity)
+    .bind(query.id)
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(entries))
+}
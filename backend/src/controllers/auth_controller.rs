@@ -0,0 +1,58 @@
+use actix_web::{get, post, web, HttpResponse};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+
+use crate::auth::{verify_password, verify_unknown_user, AuthenticatedUser, JwtConfig};
+use crate::error::ApiError;
+use crate::models::user::User;
+
+#[derive(Deserialize)]
+pub struct LoginPayload {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Serialize)]
+pub struct LoginResponse {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    pub user: User,
+}
+
+#[post("/auth/login")]
+pub async fn login(
+    db_pool: web::Data<PgPool>,
+    jwt: web::Data<JwtConfig>,
+    payload: web::Json<LoginPayload>,
+) -> Result<HttpResponse, ApiError> {
+    let user = sqlx::query_as::<_, User>("SELECT * FROM users WHERE username = $1")
+        .bind(payload.username.trim())
+        .fetch_optional(db_pool.get_ref())
+        .await?;
+    // Mot de passe vérifié même sans utilisateur, voir `verify_unknown_user`
+    let verified = match &user {
+        Some(user) => verify_password(&payload.password, &user.password_hash),
+        None => verify_unknown_user(&payload.password),
+    };
+    let user = user
+        .filter(|_| verified)
+        .ok_or_else(|| ApiError::unauthorized("Identifiants invalides"))?;
+
+    let (token, expires_at) = jwt.issue(&user).map_err(ApiError::internal)?;
+    Ok(HttpResponse::Ok().json(LoginResponse { token, expires_at, user }))
+}
+
+#[get("/auth/me")]
+pub async fn current_user(
+    db_pool: web::Data<PgPool>,
+    user: AuthenticatedUser,
+) -> Result<HttpResponse, ApiError> {
+    let current = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
+        .bind(user.id)
+        .fetch_optional(db_pool.get_ref())
+        .await?
+        .ok_or_else(|| ApiError::unauthorized(format!("Utilisateur « {} » introuvable", user.username)))?;
+
+    Ok(HttpResponse::Ok().json(current))
+}
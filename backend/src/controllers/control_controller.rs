@@ -0,0 +1,437 @@
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+use crate::audit;
+use crate::auth::{Admin, AuthenticatedUser, Authorized, Edit, Read};
+use crate::concurrency::{etag, IfMatch};
+use crate::controllers::risk_controller::{fetch_risk, lock_risk};
+use crate::error::ApiError;
+use crate::listing;
+use crate::models::audit::AuditAction;
+use crate::models::control::{Control, ControlType, Effectiveness, NewControl};
+use crate::models::risk::Risk;
+use crate::scoring::{ScoreBasis, ScoringService};
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+// Colonnes de `controls` enrichies des risques couverts
+const CONTROL_SELECT: &str = r#"
+    SELECT c.*,
+        ARRAY(
+            SELECT rc.risk_id FROM risk_controls rc
+            WHERE rc.control_id = c.id ORDER BY rc.linked_at, rc.risk_id
+        ) AS risk_ids
+    FROM controls c
+"#;
+
+// Filtres, tri et pagination de `GET /controls`
+#[derive(serde::Deserialize)]
+pub struct ControlQuery {
+    // Types et efficacités (de fonctionnement) séparés par des virgules
+    pub control_type: Option<String>,
+    pub operating_effectiveness: Option<String>,
+    pub owner: Option<String>,
+    // Contrôles liés à un risque donné
+    pub risk_id: Option<Uuid>,
+    // Recherche dans le nom et la description
+    pub q: Option<String>,
+    pub sort: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+const CONTROL_SORT_COLUMNS: &[(&str, &str)] = &[
+    ("name", "c.name"),
+    ("control_type", "c.control_type"),
+    ("owner", "c.owner"),
+    (
+        "operating_effectiveness",
+        "array_position(ARRAY['Effective', 'PartiallyEffective', 'Ineffective', 'NotTested'], c.operating_effectiveness)",
+    ),
+    ("created_at", "c.created_at"),
+    ("updated_at", "c.updated_at"),
+];
+
+fn push_control_filters(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    query: &ControlQuery,
+    types: &[ControlType],
+    effectiveness: &[Effectiveness],
+) {
+    builder.push(" WHERE TRUE");
+    if !types.is_empty() {
+        let types: Vec<String> = types.iter().map(ToString::to_string).collect();
+        builder.push(" AND c.control_type = ANY(").push_bind(types).push(")");
+    }
+    if !effectiveness.is_empty() {
+        let effectiveness: Vec<String> = effectiveness.iter().map(ToString::to_string).collect();
+        builder.push(" AND c.operating_effectiveness = ANY(").push_bind(effectiveness).push(")");
+    }
+    if let Some(owner) = query.owner.as_deref().map(str::trim).filter(|o| !o.is_empty()) {
+        builder.push(" AND lower(c.owner) = lower(").push_bind(owner.to_string()).push(")");
+    }
+    if let Some(risk_id) = query.risk_id {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM risk_controls rc WHERE rc.control_id = c.id AND rc.risk_id = ")
+            .push_bind(risk_id)
+            .push(")");
+    }
+    if let Some(text) = query.q.as_deref().filter(|q| !q.trim().is_empty()) {
+        let pattern = listing::contains_pattern(text);
+        builder
+            .push(" AND (c.name ILIKE ").push_bind(pattern.clone())
+            .push(" OR c.description ILIKE ").push_bind(pattern)
+            .push(")");
+    }
+}
+
+#[get("/controls")]
+pub async fn get_all_controls(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<ControlQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mut errors = FieldErrors::new();
+    let types = listing::parse_list::<ControlType>("control_type", query.control_type.as_deref(), &mut errors);
+    let effectiveness = listing::parse_list::<Effectiveness>(
+        "operating_effectiveness",
+        query.operating_effectiveness.as_deref(),
+        &mut errors,
+    );
+    let (limit, offset) = listing::page(query.page, query.per_page, &mut errors);
+    let order_by = listing::order_by(query.sort.as_deref(), "name", CONTROL_SORT_COLUMNS, "c.id", &mut errors);
+    errors.into_result()?;
+
+    let mut count = QueryBuilder::new("SELECT COUNT(*) FROM controls c");
+    push_control_filters(&mut count, &query, &types, &effectiveness);
+    let total = count.build_query_scalar::<i64>().fetch_one(db_pool.get_ref()).await?;
+
+    let mut select = QueryBuilder::new(CONTROL_SELECT);
+    push_control_filters(&mut select, &query, &types, &effectiveness);
+    select.push(order_by).push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+    let controls = select.build_query_as::<Control>().fetch_all(db_pool.get_ref()).await?;
+
+    Ok(listing::paginated(&controls, total))
+}
+
+async fn fetch_control<'e, E>(executor: E, id: Uuid) -> Result<Option<Control>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, Control>(&format!("{CONTROL_SELECT} WHERE c.id = $1"))
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+}
+
+async fn fetch_control_for_update(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<Control>, sqlx::Error> {
+    sqlx::query_as::<_, Control>(&format!("{CONTROL_SELECT} WHERE c.id = $1 FOR UPDATE OF c"))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+}
+
+fn control_not_found() -> ApiError {
+    ApiError::not_found("Contrôle non trouvé")
+}
+
+// Le risque résiduel des risques couverts dépend de l'efficacité de leurs contrôles ;
+// un risque dont le résiduel change prend une nouvelle version et une entrée d'audit
+async fn refresh_residuals(
+    tx: &mut Transaction<'_, Postgres>,
+    scoring: &ScoringService,
+    risk_ids: impl IntoIterator<Item = Uuid>,
+    actor: &AuthenticatedUser,
+) -> Result<(), sqlx::Error> {
+    let mut risk_ids: Vec<Uuid> = risk_ids.into_iter().collect();
+    risk_ids.sort();
+    risk_ids.dedup();
+    for risk_id in risk_ids {
+        if lock_risk(tx, risk_id).await?.is_none() {
+            continue;
+        }
+        let before = Risk::from(fetch_risk(&mut **tx, risk_id).await?.ok_or(sqlx::Error::RowNotFound)?);
+        scoring.refresh_basis(tx, risk_id, ScoreBasis::Residual).await?;
+
+        let refreshed = fetch_risk(&mut **tx, risk_id).await?.ok_or(sqlx::Error::RowNotFound)?;
+        if (refreshed.residual_score, &refreshed.residual_level) == (before.residual_score, &before.residual_level) {
+            continue;
+        }
+        sqlx::query("UPDATE risks SET version = version + 1, updated_at = now() WHERE id = $1")
+            .bind(risk_id)
+            .execute(&mut **tx)
+            .await?;
+        let after = Risk::from(fetch_risk(&mut **tx, risk_id).await?.ok_or(sqlx::Error::RowNotFound)?);
+        audit::record(&mut **tx, actor, "risk", risk_id, AuditAction::Update, Some(&before), Some(&after)).await?;
+    }
+    Ok(())
+}
+
+// Remplace les risques couverts par le contrôle
+async fn link_risks(tx: &mut Transaction<'_, Postgres>, control_id: Uuid, risk_ids: &[Uuid]) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM risk_controls WHERE control_id = $1 AND NOT (risk_id = ANY($2))")
+        .bind(control_id)
+        .bind(risk_ids)
+        .execute(&mut **tx)
+        .await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO risk_controls (risk_id, control_id)
+        SELECT DISTINCT unnest($2::uuid[]), $1
+        ON CONFLICT DO NOTHING
+        "#
+    )
+    .bind(control_id)
+    .bind(risk_ids)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_control(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    data: &NewControl,
+    actor: &AuthenticatedUser,
+) -> Result<Control, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO controls (
+            name, description, control_type, owner, frequency, design_effectiveness, operating_effectiveness
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#
+    )
+    .bind(data.name.trim())
+    .bind(&data.description)
+    .bind(data.control_type)
+    .bind(&data.owner)
+    .bind(data.frequency)
+    .bind(data.design_effectiveness.unwrap_or(Effectiveness::NotTested))
+    .bind(data.operating_effectiveness.unwrap_or(Effectiveness::NotTested))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let risk_ids = data.risk_ids.clone().unwrap_or_default();
+    link_risks(&mut tx, id, &risk_ids).await?;
+    refresh_residuals(&mut tx, scoring, risk_ids, actor).await?;
+
+    let control = fetch_control(&mut *tx, id).await?.ok_or_else(control_not_found)?;
+    audit::record(&mut *tx, actor, "control", id, AuditAction::Create, None, Some(&control)).await?;
+
+    tx.commit().await?;
+    Ok(control)
+}
+
+#[post("/controls")]
+pub async fn create_control(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    rules: web::Data<ValidationConfig>,
+    scoring: web::Data<ScoringService>,
+    data: web::Json<NewControl>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let control = insert_control(db_pool.get_ref(), &scoring, &data, &user).await?;
+    Ok(HttpResponse::Created().insert_header(etag(control.version)).json(control))
+}
+
+#[get("/controls/{id}")]
+pub async fn get_control_by_id(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let control = fetch_control(db_pool.get_ref(), *id).await?.ok_or_else(control_not_found)?;
+    Ok(HttpResponse::Ok().insert_header(etag(control.version)).json(control))
+}
+
+async fn save_control(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    id: Uuid,
+    data: &NewControl,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Control, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_control_for_update(&mut tx, id).await?.ok_or_else(control_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE controls
+        SET name = $1, description = $2, control_type = $3, owner = $4, frequency = $5,
+            design_effectiveness = $6, operating_effectiveness = $7,
+            version = version + 1, updated_at = now()
+        WHERE id = $8
+        "#
+    )
+    .bind(data.name.trim())
+    .bind(&data.description)
+    .bind(data.control_type)
+    .bind(&data.owner)
+    .bind(data.frequency)
+    .bind(data.design_effectiveness.unwrap_or(Effectiveness::NotTested))
+    .bind(data.operating_effectiveness.unwrap_or(Effectiveness::NotTested))
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    if let Some(risk_ids) = &data.risk_ids {
+        link_risks(&mut tx, id, risk_ids).await?;
+    }
+    let updated = fetch_control(&mut *tx, id).await?.ok_or_else(control_not_found)?;
+    refresh_residuals(&mut tx, scoring, before.risk_ids.iter().chain(&updated.risk_ids).copied(), actor).await?;
+
+    audit::record(&mut *tx, actor, "control", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[put("/controls/{id}")]
+pub async fn update_control(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    scoring: web::Data<ScoringService>,
+    id: web::Path<Uuid>,
+    data: web::Json<NewControl>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let control = save_control(db_pool.get_ref(), &scoring, *id, &data, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(control.version)).json(control))
+}
+
+async fn remove_control(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Control, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_control_for_update(&mut tx, id).await?.ok_or_else(control_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    sqlx::query("DELETE FROM controls WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    refresh_residuals(&mut tx, scoring, before.risk_ids.iter().copied(), actor).await?;
+
+    audit::record(&mut *tx, actor, "control", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(before)
+}
+
+#[delete("/controls/{id}")]
+pub async fn delete_control(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    scoring: web::Data<ScoringService>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    remove_control(db_pool.get_ref(), &scoring, *id, &if_match, &user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Contrôles couvrant un risque
+#[get("/risks/{id}/controls")]
+pub async fn get_risk_controls(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM risks WHERE id = $1)")
+        .bind(*id)
+        .fetch_one(db_pool.get_ref())
+        .await?;
+    if !exists {
+        return Err(ApiError::not_found("Risque non trouvé"));
+    }
+
+    let controls = sqlx::query_as::<_, Control>(&format!(
+        "{CONTROL_SELECT} WHERE EXISTS (SELECT 1 FROM risk_controls rc WHERE rc.control_id = c.id AND rc.risk_id = $1) ORDER BY c.name, c.id"
+    ))
+    .bind(*id)
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(controls))
+}
+
+// Lie (`attach`) ou délie un contrôle d'un risque, puis recalcule le risque résiduel
+async fn set_risk_control(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    risk_id: Uuid,
+    control_id: Uuid,
+    attach: bool,
+    actor: &AuthenticatedUser,
+) -> Result<Control, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_control_for_update(&mut tx, control_id).await?.ok_or_else(control_not_found)?;
+    let query = if attach {
+        "INSERT INTO risk_controls (risk_id, control_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+    } else {
+        "DELETE FROM risk_controls WHERE risk_id = $1 AND control_id = $2"
+    };
+    sqlx::query(query)
+        .bind(risk_id)
+        .bind(control_id)
+        .execute(&mut *tx)
+        .await?;
+    refresh_residuals(&mut tx, scoring, [risk_id], actor).await?;
+
+    let after = fetch_control(&mut *tx, control_id).await?.ok_or_else(control_not_found)?;
+    audit::record(&mut *tx, actor, "control", control_id, AuditAction::Update, Some(&before), Some(&after)).await?;
+
+    tx.commit().await?;
+    Ok(after)
+}
+
+#[post("/risks/{risk_id}/controls/{control_id}")]
+pub async fn attach_control(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    scoring: web::Data<ScoringService>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (risk_id, control_id) = path.into_inner();
+    let control = set_risk_control(db_pool.get_ref(), &scoring, risk_id, control_id, true, &user).await?;
+    Ok(HttpResponse::Ok().json(control))
+}
+
+#[delete("/risks/{risk_id}/controls/{control_id}")]
+pub async fn detach_control(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    scoring: web::Data<ScoringService>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let (risk_id, control_id) = path.into_inner();
+    let control = set_risk_control(db_pool.get_ref(), &scoring, risk_id, control_id, false, &user).await?;
+    Ok(HttpResponse::Ok().json(control))
+}
@@ -1,154 +1,461 @@
-use actix_web::{get, post, web, HttpResponse, Responder, put, delete};
-use sqlx::PgPool;
-use crate::models::incident::Incident;
+use actix_web::{get, post, web, HttpResponse, put, delete, patch};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
+use crate::audit;
+use crate::concurrency::{etag, IfMatch};
+use crate::error::ApiError;
+use crate::listing;
+use serde_json::json;
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+use crate::auth::{AuthenticatedUser, Authorized, Read, Edit, Admin};
+use crate::models::audit::AuditAction;
+use crate::models::incident::{Incident, IncidentSeverity, IncidentStatus, IncidentStatusHistory};
+use crate::controllers::attachment_controller::{incident_storage_keys, purge_files};
+use crate::storage::AttachmentStorage;
 
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use uuid::Uuid;
 
 #[derive(serde::Deserialize)]
 pub struct NewIncident {
     pub title: String,
     pub description: Option<String>,
-    pub severity: String,
-    pub status: String,
+    pub severity: IncidentSeverity,
     pub related_risk_id: Option<Uuid>,
+    // Risques liés en plus du risque principal ; absent en modification : liens inchangés
+    pub risk_ids: Option<Vec<Uuid>>,
 }
 
-#[get("/incidents")]
-pub async fn get_all_incidents(db_pool: web::Data<PgPool>) -> impl Responder {
-    let result = sqlx::query_as!(
-        Incident,
-        r#"
-        SELECT id, title, description, severity, status, related_risk_id, created_at
-        FROM incidents
-        ORDER BY created_at DESC
-        "#
-    )
-    .fetch_all(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(incidents) => HttpResponse::Ok().json(incidents),
-        Err(e) => {
-            eprintln!("Erreur lors de la récupération des incidents: {}", e);
-            HttpResponse::InternalServerError().body("Erreur serveur")
-        }
+// Le statut suit le cycle de vie de l'incident : il ne change que via `PATCH /incidents/{id}/status`
+#[derive(serde::Deserialize)]
+pub struct UpdateIncidentStatusPayload {
+    pub status: IncidentStatus,
+    pub comment: Option<String>,
+}
+
+impl Validate for NewIncident {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors
+            .length("title", &self.title, config.title_min_length, config.title_max_length);
+        errors.into_result()
     }
 }
 
+// Colonnes de `incidents` enrichies des risques liés
+const INCIDENT_SELECT: &str = r#"
+    SELECT i.id, i.title, i.description, i.severity, i.status, i.related_risk_id, i.created_at, i.version,
+        ARRAY(
+            SELECT ir.risk_id FROM incident_risks ir
+            WHERE ir.incident_id = i.id ORDER BY ir.linked_at, ir.risk_id
+        ) AS risk_ids
+    FROM incidents i
+"#;
+
+// Filtres, tri et pagination de `GET /incidents`
+#[derive(serde::Deserialize)]
+pub struct IncidentQuery {
+    // Incidents liés à un risque donné
+    pub related_risk_id: Option<Uuid>,
+    // Gravités et statuts séparés par des virgules
+    pub severity: Option<String>,
+    pub status: Option<String>,
+    // Dates de déclaration incluses (AAAA-MM-JJ)
+    pub created_from: Option<NaiveDate>,
+    pub created_to: Option<NaiveDate>,
+    // Recherche dans le titre et la description
+    pub q: Option<String>,
+    pub sort: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+// Colonnes triables ; gravité et statut suivent leur ordre naturel plutôt que l'ordre alphabétique
+const INCIDENT_SORT_COLUMNS: &[(&str, &str)] = &[
+    ("title", "i.title"),
+    ("severity", "array_position(ARRAY['Low', 'Medium', 'High', 'Critical'], i.severity)"),
+    (
+        "status",
+        "array_position(ARRAY['New', 'Triaged', 'Investigating', 'Contained', 'Resolved', 'Closed'], i.status)",
+    ),
+    ("created_at", "i.created_at"),
+];
+
+fn push_incident_filters(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    query: &IncidentQuery,
+    severities: &[IncidentSeverity],
+    statuses: &[IncidentStatus],
+) {
+    builder.push(" WHERE TRUE");
+    if let Some(risk_id) = query.related_risk_id {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM incident_risks ir WHERE ir.incident_id = i.id AND ir.risk_id = ")
+            .push_bind(risk_id)
+            .push(")");
+    }
+    if !severities.is_empty() {
+        let severities: Vec<String> = severities.iter().map(ToString::to_string).collect();
+        builder.push(" AND i.severity = ANY(").push_bind(severities).push(")");
+    }
+    if !statuses.is_empty() {
+        let statuses: Vec<String> = statuses.iter().map(ToString::to_string).collect();
+        builder.push(" AND i.status = ANY(").push_bind(statuses).push(")");
+    }
+    if let Some(from) = query.created_from {
+        builder.push(" AND i.created_at >= ").push_bind(from);
+    }
+    if let Some(to) = query.created_to {
+        builder.push(" AND i.created_at < ").push_bind(to).push(" + 1");
+    }
+    if let Some(text) = query.q.as_deref().filter(|q| !q.trim().is_empty()) {
+        let pattern = listing::contains_pattern(text);
+        builder
+            .push(" AND (i.title ILIKE ").push_bind(pattern.clone())
+            .push(" OR i.description ILIKE ").push_bind(pattern)
+            .push(")");
+    }
+}
+
+#[get("/incidents")]
+pub async fn get_all_incidents(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<IncidentQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mut errors = FieldErrors::new();
+    let severities = listing::parse_list::<IncidentSeverity>("severity", query.severity.as_deref(), &mut errors);
+    let statuses = listing::parse_list::<IncidentStatus>("status", query.status.as_deref(), &mut errors);
+    let (limit, offset) = listing::page(query.page, query.per_page, &mut errors);
+    let order_by = listing::order_by(query.sort.as_deref(), "-created_at", INCIDENT_SORT_COLUMNS, "i.id", &mut errors);
+    errors.into_result()?;
+
+    let mut count = QueryBuilder::new("SELECT COUNT(*) FROM incidents i");
+    push_incident_filters(&mut count, &query, &severities, &statuses);
+    let total = count.build_query_scalar::<i64>().fetch_one(db_pool.get_ref()).await?;
+
+    let mut select = QueryBuilder::new(INCIDENT_SELECT);
+    push_incident_filters(&mut select, &query, &severities, &statuses);
+    select.push(order_by).push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+    let incidents = select.build_query_as::<Incident>().fetch_all(db_pool.get_ref()).await?;
+
+    Ok(listing::paginated(&incidents, total))
+}
+
 #[get("/incidents/{id}")]
 pub async fn get_incident_by_id(
     db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
     id: web::Path<Uuid>,
-) -> impl Responder {
-    let result = sqlx::query_as!(
-        Incident,
+) -> Result<HttpResponse, ApiError> {
+    let incident = fetch_incident(db_pool.get_ref(), *id)
+        .await?
+        .ok_or_else(incident_not_found)?;
+
+    Ok(HttpResponse::Ok().insert_header(etag(incident.version)).json(incident))
+}
+
+async fn fetch_incident<'e, E>(executor: E, id: Uuid) -> Result<Option<Incident>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, Incident>(&format!("{INCIDENT_SELECT} WHERE i.id = $1"))
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+}
+
+fn incident_not_found() -> ApiError {
+    ApiError::not_found("Incident non trouvé")
+}
+
+// Met à jour les risques liés : le risque principal en fait toujours partie. Sans `risk_ids`,
+// les autres liens sont conservés, mais l'ancien risque principal (`previous_primary`) est délié
+// quand il est remplacé
+async fn link_risks(
+    tx: &mut Transaction<'_, Postgres>,
+    incident_id: Uuid,
+    data: &NewIncident,
+    previous_primary: Option<Uuid>,
+) -> Result<(), sqlx::Error> {
+    let mut risk_ids = data.risk_ids.clone().unwrap_or_default();
+    risk_ids.extend(data.related_risk_id);
+
+    if data.risk_ids.is_some() {
+        sqlx::query("DELETE FROM incident_risks WHERE incident_id = $1 AND NOT (risk_id = ANY($2))")
+            .bind(incident_id)
+            .bind(&risk_ids)
+            .execute(&mut **tx)
+            .await?;
+    } else if let Some(previous) = previous_primary.filter(|previous| Some(*previous) != data.related_risk_id) {
+        sqlx::query("DELETE FROM incident_risks WHERE incident_id = $1 AND risk_id = $2")
+            .bind(incident_id)
+            .bind(previous)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    sqlx::query(
         r#"
-        SELECT id, title, description, severity, status, related_risk_id, created_at
-        FROM incidents
-        WHERE id = $1
-        "#,
-        *id
+        INSERT INTO incident_risks (incident_id, risk_id)
+        SELECT DISTINCT $1, unnest($2::uuid[])
+        ON CONFLICT DO NOTHING
+        "#
     )
-    .fetch_optional(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(Some(incident)) => HttpResponse::Ok().json(incident),
-        Ok(None) => HttpResponse::NotFound().body("Incident non trouvé"),
-        Err(e) => {
-            eprintln!("Erreur : {}", e);
-            HttpResponse::InternalServerError().body("Erreur serveur")
-        }
-    }
+    .bind(incident_id)
+    .bind(&risk_ids)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn insert_incident(
+    db_pool: &PgPool,
+    data: &NewIncident,
+    actor: &AuthenticatedUser,
+) -> Result<Incident, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO incidents (id, title, description, severity, status, related_risk_id, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#
+    )
+    .bind(Uuid::new_v4())
+    .bind(&data.title)
+    .bind(&data.description)
+    .bind(data.severity)
+    .bind(IncidentStatus::New)
+    .bind(data.related_risk_id)
+    .bind(Utc::now().naive_utc())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    link_risks(&mut tx, id, data, None).await?;
+    let incident = fetch_incident(&mut *tx, id).await?.ok_or_else(incident_not_found)?;
+
+    audit::record(&mut *tx, actor, "incident", incident.id, AuditAction::Create, None, Some(&incident)).await?;
+
+    tx.commit().await?;
+    Ok(incident)
 }
 
 #[post("/incidents")]
 pub async fn create_incident(
     db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    rules: web::Data<ValidationConfig>,
     incident_data: web::Json<NewIncident>,
-) -> impl Responder {
-    let new_id = Uuid::new_v4();
-    let now = Utc::now().naive_utc();
+) -> Result<HttpResponse, ApiError> {
+    incident_data.validate(&rules)?;
+
+    let incident = insert_incident(db_pool.get_ref(), &incident_data, &user).await?;
+    Ok(HttpResponse::Created().json(serde_json::json!({ "id": incident.id })))
+}
+
+async fn fetch_incident_for_update(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<Option<Incident>, sqlx::Error> {
+    sqlx::query_as::<_, Incident>(&format!("{INCIDENT_SELECT} WHERE i.id = $1 FOR UPDATE OF i"))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+}
+
+async fn save_incident(
+    db_pool: &PgPool,
+    id: Uuid,
+    data: &NewIncident,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Incident, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_incident_for_update(&mut tx, id).await?.ok_or_else(incident_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
 
-    let result = sqlx::query!(
+    sqlx::query(
         r#"
-        INSERT INTO incidents (id, title, description, severity, status, related_risk_id, created_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
-        "#,
-        new_id,
-        incident_data.title,
-        incident_data.description,
-        incident_data.severity,
-        incident_data.status,
-        incident_data.related_risk_id,
-        now
+        UPDATE incidents
+        SET title = $1, description = $2, severity = $3, related_risk_id = $4,
+            version = version + 1
+        WHERE id = $5
+        "#
     )
-    .execute(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(_) => HttpResponse::Created().json(serde_json::json!({ "id": new_id })),
-        Err(e) => {
-            eprintln!("Erreur lors de la création de l'incident: {}", e);
-            HttpResponse::InternalServerError().body("Erreur lors de la création")
-        }
-    }
+    .bind(&data.title)
+    .bind(&data.description)
+    .bind(data.severity)
+    .bind(data.related_risk_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    link_risks(&mut tx, id, data, before.related_risk_id).await?;
+    let updated = fetch_incident(&mut *tx, id).await?.ok_or_else(incident_not_found)?;
+
+    audit::record(&mut *tx, actor, "incident", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
 }
 
 #[put("/incidents/{id}")]
 pub async fn update_incident(
     db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
     id: web::Path<Uuid>,
     updated_data: web::Json<NewIncident>,
-) -> impl Responder {
-    let result = sqlx::query!(
+) -> Result<HttpResponse, ApiError> {
+    updated_data.validate(&rules)?;
+
+    let incident = save_incident(db_pool.get_ref(), *id, &updated_data, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(incident.version)).json(incident))
+}
+
+async fn remove_incident(
+    db_pool: &PgPool,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Incident, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_incident_for_update(&mut tx, id).await?.ok_or_else(incident_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    sqlx::query(
         r#"
-        UPDATE incidents
-        SET title = $1, description = $2, severity = $3, status = $4, related_risk_id = $5
-        WHERE id = $6
-        "#,
-        updated_data.title,
-        updated_data.description,
-        updated_data.severity,
-        updated_data.status,
-        updated_data.related_risk_id,
-        *id
+        DELETE FROM incidents
+        WHERE id = $1
+        "#
     )
-    .execute(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(res) if res.rows_affected() == 1 => HttpResponse::Ok().body("Incident mis à jour"),
-        Ok(_) => HttpResponse::NotFound().body("Incident introuvable"),
-        Err(e) => {
-            eprintln!("Erreur lors de la mise à jour : {}", e);
-            HttpResponse::InternalServerError().body("Erreur serveur")
-        }
-    }
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(&mut *tx, actor, "incident", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(before)
 }
 
 #[delete("/incidents/{id}")]
 pub async fn delete_incident(
     db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    storage: web::Data<dyn AttachmentStorage>,
     id: web::Path<Uuid>,
-) -> impl Responder {
-    let result = sqlx::query!(
+) -> Result<HttpResponse, ApiError> {
+    let storage_keys = incident_storage_keys(db_pool.get_ref(), *id).await?;
+    remove_incident(db_pool.get_ref(), *id, &if_match, &user).await?;
+    purge_files(storage.get_ref(), &storage_keys).await;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+async fn change_incident_status(
+    db_pool: &PgPool,
+    id: Uuid,
+    payload: &UpdateIncidentStatusPayload,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Incident, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = fetch_incident_for_update(&mut tx, id).await?.ok_or_else(incident_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+    if !before.status.can_transition_to(payload.status) {
+        return Err(ApiError::invalid_transition(format!(
+            "Transition {} → {} non autorisée",
+            before.status, payload.status
+        ))
+        .with_details(json!({ "allowed": before.status.next_statuses() })));
+    }
+
+    sqlx::query(
         r#"
-        DELETE FROM incidents
-        WHERE id = $1
-        "#,
-        *id
+        UPDATE incidents
+        SET status = $1, version = version + 1
+        WHERE id = $2
+        "#
     )
-    .execute(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(res) if res.rows_affected() == 1 => HttpResponse::Ok().body("Incident supprimé"),
-        Ok(_) => HttpResponse::NotFound().body("Incident introuvable"),
-        Err(e) => {
-            eprintln!("Erreur lors de la suppression : {}", e);
-            HttpResponse::InternalServerError().body("Erreur serveur")
-        }
-    }
+    .bind(payload.status)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    let updated = fetch_incident(&mut *tx, id).await?.ok_or_else(incident_not_found)?;
+
+    let comment = payload.comment.as_deref().map(str::trim).filter(|text| !text.is_empty());
+    sqlx::query(
+        r#"
+        INSERT INTO incident_status_history (incident_id, old_status, new_status, comment, changed_by, changed_by_username)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#
+    )
+    .bind(id)
+    .bind(before.status.to_string())
+    .bind(payload.status.to_string())
+    .bind(comment)
+    .bind(actor.id)
+    .bind(&actor.username)
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        actor,
+        "incident",
+        id,
+        AuditAction::Update,
+        Some(&json!({ "status": before.status })),
+        Some(&json!({ "status": updated.status })),
+    )
+    .await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[patch("/incidents/{id}/status")]
+pub async fn update_incident_status(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    id: web::Path<Uuid>,
+    payload: web::Json<UpdateIncidentStatusPayload>,
+) -> Result<HttpResponse, ApiError> {
+    let incident = change_incident_status(db_pool.get_ref(), *id, &payload, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(incident.version)).json(incident))
+}
+
+#[get("/incidents/{id}/history")]
+pub async fn get_incident_history(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let history = sqlx::query_as::<_, IncidentStatusHistory>(
+        r#"
+        SELECT * FROM incident_status_history
+        WHERE incident_id = $1
+        ORDER BY changed_at ASC
+        "#
+    )
+    .bind(*id)
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(history))
 }
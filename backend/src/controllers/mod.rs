@@ -1,3 +1,14 @@
 pub mod health_controller;
 pub mod risk_controller;
-pub mod incident_handler;
\ No newline at end of file
+pub mod incident_handler;
+pub mod attachment_controller;
+pub mod auth_controller;
+pub mod user_controller;
+pub mod audit_controller;
+pub mod workflow_controller;
+pub mod validation_controller;
+pub mod search_controller;
+pub mod scoring_controller;
+pub mod control_controller;
+pub mod treatment_controller;
+pub mod appetite_controller;
@@ -3,7 +3,11 @@ pub struct CriticalRisk {
     pub id: Uuid,
     pub title: String,
     pub status: RiskStatus,
+    pub scoring_method: ScoringMethod,
+    // Base de classement ; `score` et `level` sont ceux de cette base
+    pub basis: ScoreBasis,
     pub score: i32,
+    pub level: Option<String>,
     pub external_id: Option<String>,
     pub category: Option<String>,
     pub location: Option<String>,
@@ -11,85 +15,270 @@ pub struct CriticalRisk {
     pub control_measure_id: Option<String>,
 }
 
-use actix_web::{get, web, HttpResponse, Responder, post, put, delete, patch};
-use sqlx::PgPool;
+use actix_web::{get, web, HttpResponse, post, put, delete, patch};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
+use crate::auth::{Authorized, Read, Edit, ChangeStatus, Admin, Permission};
 use crate::models::risk::{Risk, NewRisk, DbRisk, RiskStatus};
 use crate::models::risk_history::RiskStatusHistory;
+use crate::models::user::UserSummary;
 use crate::models::evaluation::RiskEvaluation;
-use actix_web::web::Json;
+use crate::controllers::attachment_controller::{purge_files, risk_storage_keys};
+use crate::controllers::treatment_controller::open_mandatory_actions;
+use crate::controllers::appetite_controller::{exceeds_tolerance, record_breach};
+use crate::models::appetite::BreachTrigger;
+use crate::storage::AttachmentStorage;
+use crate::audit;
+use crate::concurrency::{etag, IfMatch};
+use crate::error::{ApiError, ErrorCode};
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+use crate::likelihood::{self, LikelihoodConfig, SeverityCount};
+use crate::listing;
+use crate::scoring::{RiskLevel, ScoreBasis, ScoringMethod, ScoringService};
+use crate::workflow;
+use crate::auth::AuthenticatedUser;
+use crate::models::audit::AuditAction;
+use serde_json::json;
+use chrono::{Duration, NaiveDate, Utc};
 use serde::Deserialize;
 use serde::Serialize;
-use chrono::Utc;
 
 #[derive(Deserialize)]
 pub struct UpdateStatusPayload {
     pub status: RiskStatus,
+    pub justification: Option<String>,
+    pub approver_id: Option<Uuid>,
 }
 
-fn is_valid_transition(current: &RiskStatus, next: &RiskStatus) -> bool {
-    matches!(
-        (current, next),
-        (RiskStatus::Identified, RiskStatus::Assessed)
-        | (RiskStatus::Assessed, RiskStatus::InTreatment)
-        | (RiskStatus::InTreatment, RiskStatus::Monitoring)
-        | (RiskStatus::Monitoring, RiskStatus::Accepted)
-        | (RiskStatus::Monitoring, RiskStatus::Rejected)
-        | (RiskStatus::Monitoring, RiskStatus::Transferred)
-        | (RiskStatus::InTreatment, RiskStatus::Closed)
-    )
+// Colonnes de `risks` enrichies des tags, actifs et parties prenantes liés
+const RISK_SELECT: &str = r#"
+    SELECT r.*,
+        ARRAY(
+            SELECT t.name FROM risk_tags rt JOIN tags t ON t.id = rt.tag_id
+            WHERE rt.risk_id = r.id ORDER BY t.name
+        ) AS tags,
+        ARRAY(
+            SELECT a.name FROM risk_assets ra JOIN assets a ON a.id = ra.asset_id
+            WHERE ra.risk_id = r.id ORDER BY a.name
+        ) AS assets,
+        ARRAY(
+            SELECT s.name FROM risk_stakeholders rs JOIN stakeholders s ON s.id = rs.stakeholder_id
+            WHERE rs.risk_id = r.id ORDER BY s.name
+        ) AS stakeholders
+    FROM risks r
+"#;
+
+pub async fn fetch_risk<'e, E>(executor: E, id: Uuid) -> Result<Option<DbRisk>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, DbRisk>(&format!("{RISK_SELECT} WHERE r.id = $1"))
+        .bind(id)
+        .fetch_optional(executor)
+        .await
 }
 
-#[get("/risks")]
-pub async fn get_all_risks(db_pool: web::Data<PgPool>) -> impl Responder {
-    let result = sqlx::query_as::<_, DbRisk>("SELECT * FROM risks")
-        .fetch_all(db_pool.get_ref())
-        .await;
-
-    match result {
-        Ok(db_risks) => {
-            let risks: Vec<Risk> = db_risks
-                .into_iter()
-                .map(|r| Risk {
-                    score: Some(r.impact * r.probability),
-                    id: r.id,
-                    title: r.title,
-                    description: r.description,
-                    impact: r.impact,
-                    probability: r.probability,
-                    status: r.status,
-                    created_at: r.created_at,
-                    updated_at: r.updated_at,
-                    external_id: r.external_id,
-                    category: r.category,
-                    location: r.location,
-                    regulation: r.regulation,
-                    control_measure_id: r.control_measure_id,
-                })
-                .collect();
-
-            HttpResponse::Ok().json(risks)
-        },
-        Err(err) => {
-            eprintln!("Erreur lors de la récupération des risques : {:?}", err);
-            HttpResponse::InternalServerError().finish()
+fn risk_not_found() -> ApiError {
+    ApiError::not_found("Risque non trouvé")
+}
+
+// Verrouille le risque jusqu'à la fin de la transaction et renvoie sa version courante
+pub async fn lock_risk(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Option<i32>, sqlx::Error> {
+    sqlx::query_scalar::<_, i32>("SELECT version FROM risks WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+}
+
+// Remplace les liens d'un risque vers une table de référence (tags, actifs, parties prenantes)
+async fn replace_links(
+    tx: &mut Transaction<'_, Postgres>,
+    risk_id: Uuid,
+    table: &str,
+    link_table: &str,
+    link_column: &str,
+    names: Option<&Vec<String>>,
+) -> Result<(), sqlx::Error> {
+    let names: Vec<String> = names
+        .map(|values| {
+            values
+                .iter()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    sqlx::query(&format!("DELETE FROM {link_table} WHERE risk_id = $1"))
+        .bind(risk_id)
+        .execute(&mut **tx)
+        .await?;
+
+    if names.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query(&format!(
+        "INSERT INTO {table} (name) SELECT DISTINCT unnest($1::text[]) ON CONFLICT (name) DO NOTHING"
+    ))
+    .bind(&names)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(&format!(
+        "INSERT INTO {link_table} (risk_id, {link_column}) SELECT $1, id FROM {table} WHERE name = ANY($2)"
+    ))
+    .bind(risk_id)
+    .bind(&names)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+async fn replace_all_links(
+    tx: &mut Transaction<'_, Postgres>,
+    risk_id: Uuid,
+    risk: &NewRisk,
+) -> Result<(), sqlx::Error> {
+    replace_links(tx, risk_id, "tags", "risk_tags", "tag_id", risk.tags.as_ref()).await?;
+    replace_links(tx, risk_id, "assets", "risk_assets", "asset_id", risk.assets.as_ref()).await?;
+    replace_links(tx, risk_id, "stakeholders", "risk_stakeholders", "stakeholder_id", risk.stakeholders.as_ref()).await?;
+    Ok(())
+}
+
+// Filtres, tri et pagination de `GET /risks`
+#[derive(Deserialize)]
+pub struct RiskQuery {
+    // Statuts séparés par des virgules
+    pub status: Option<String>,
+    pub category: Option<String>,
+    pub location: Option<String>,
+    pub regulation: Option<String>,
+    pub owner: Option<String>,
+    pub score_min: Option<i32>,
+    pub score_max: Option<i32>,
+    // Dates de création incluses (AAAA-MM-JJ)
+    pub created_from: Option<NaiveDate>,
+    pub created_to: Option<NaiveDate>,
+    // Risques dont la revue périodique est (ou n'est pas) en retard
+    pub review_overdue: Option<bool>,
+    // Recherche dans le titre, la description et la référence externe
+    pub q: Option<String>,
+    pub sort: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+// Colonnes triables : nom accepté dans `sort` et expression SQL
+const RISK_SORT_COLUMNS: &[(&str, &str)] = &[
+    ("title", "r.title"),
+    ("impact", "r.impact"),
+    ("probability", "r.probability"),
+    ("score", "r.score"),
+    ("residual_score", "r.residual_score"),
+    ("target_score", "r.target_score"),
+    ("status", "r.status"),
+    ("created_at", "r.created_at"),
+    ("updated_at", "r.updated_at"),
+    ("next_review_at", "r.next_review_at"),
+    ("external_id", "r.external_id"),
+    ("category", "r.category"),
+    ("location", "r.location"),
+    ("regulation", "r.regulation"),
+    ("control_measure_id", "r.control_measure_id"),
+    ("technology", "r.technology"),
+    ("team", "r.team"),
+    ("business_unit", "r.business_unit"),
+    ("owner", "r.owner"),
+    ("source", "r.source"),
+];
+
+fn push_risk_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &RiskQuery, statuses: &[RiskStatus]) {
+    builder.push(" WHERE TRUE");
+    if !statuses.is_empty() {
+        builder.push(" AND r.status = ANY(").push_bind(statuses.to_vec()).push(")");
+    }
+    for (column, value) in [
+        ("r.category", &query.category),
+        ("r.location", &query.location),
+        ("r.regulation", &query.regulation),
+        ("r.owner", &query.owner),
+    ] {
+        if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+            builder.push(format!(" AND lower({column}) = lower(")).push_bind(value.to_string()).push(")");
         }
     }
+    if let Some(min) = query.score_min {
+        builder.push(" AND r.score >= ").push_bind(min);
+    }
+    if let Some(max) = query.score_max {
+        builder.push(" AND r.score <= ").push_bind(max);
+    }
+    if let Some(from) = query.created_from {
+        builder.push(" AND r.created_at >= ").push_bind(from);
+    }
+    if let Some(to) = query.created_to {
+        builder.push(" AND r.created_at < ").push_bind(to).push(" + 1");
+    }
+    if let Some(overdue) = query.review_overdue {
+        builder.push(" AND r.review_overdue = ").push_bind(overdue);
+    }
+    if let Some(text) = query.q.as_deref().filter(|q| !q.trim().is_empty()) {
+        let pattern = listing::contains_pattern(text);
+        builder
+            .push(" AND (r.title ILIKE ").push_bind(pattern.clone())
+            .push(" OR r.description ILIKE ").push_bind(pattern.clone())
+            .push(" OR r.external_id ILIKE ").push_bind(pattern)
+            .push(")");
+    }
 }
 
-#[post("/risks")]
-pub async fn create_risk(
+#[get("/risks")]
+pub async fn get_all_risks(
     db_pool: web::Data<PgPool>,
-    risk: web::Json<NewRisk>,
-) -> impl Responder {
-    let result = sqlx::query_as::<_, DbRisk>(
+    _user: Authorized<Read>,
+    query: web::Query<RiskQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mut errors = FieldErrors::new();
+    let statuses = listing::parse_list::<RiskStatus>("status", query.status.as_deref(), &mut errors);
+    let (limit, offset) = listing::page(query.page, query.per_page, &mut errors);
+    let order_by = listing::order_by(query.sort.as_deref(), "-created_at", RISK_SORT_COLUMNS, "r.id", &mut errors);
+    errors.into_result()?;
+
+    let mut count = QueryBuilder::new("SELECT COUNT(*) FROM risks r");
+    push_risk_filters(&mut count, &query, &statuses);
+    let total = count.build_query_scalar::<i64>().fetch_one(db_pool.get_ref()).await?;
+
+    let mut select = QueryBuilder::new(RISK_SELECT);
+    push_risk_filters(&mut select, &query, &statuses);
+    select.push(order_by).push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+    let db_risks = select.build_query_as::<DbRisk>().fetch_all(db_pool.get_ref()).await?;
+
+    let risks: Vec<Risk> = db_risks.into_iter().map(Risk::from).collect();
+    Ok(listing::paginated(&risks, total))
+}
+
+async fn insert_risk(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    risk: &NewRisk,
+    actor: &AuthenticatedUser,
+) -> Result<Risk, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
         r#"
         INSERT INTO risks (
             title, description, external_id, category, location, regulation,
-            control_measure_id, impact, probability, status
+            control_measure_id, impact, probability, status,
+            risk_map, threat_map, technology, team, owner, owner_delegate,
+            source, evaluation_method, evaluation_notes, additional_notes, business_unit, scoring_method,
+            residual_impact, residual_probability, target_impact, target_probability
         )
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
-        RETURNING *;
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+            $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
+        RETURNING id;
         "#
     )
     .bind(&risk.title)
@@ -101,46 +290,75 @@ pub async fn create_risk(
     .bind(&risk.control_measure_id)
     .bind(risk.impact)
     .bind(risk.probability)
-    .bind(&risk.status)
-    .fetch_one(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(db_risk) => {
-            let full_risk = Risk {
-                id: db_risk.id,
-                title: db_risk.title,
-                description: db_risk.description,
-                impact: db_risk.impact,
-                probability: db_risk.probability,
-                status: db_risk.status,
-                created_at: db_risk.created_at,
-                updated_at: db_risk.updated_at,
-                score: Some(db_risk.impact * db_risk.probability),
-                external_id: db_risk.external_id,
-                category: db_risk.category,
-                location: db_risk.location,
-                regulation: db_risk.regulation,
-                control_measure_id: db_risk.control_measure_id,
-            };
-            HttpResponse::Ok().json(full_risk)
-        }
-        Err(e) => {
-            eprintln!("Erreur création risque : {:?}", e);
-            HttpResponse::InternalServerError().finish()
-        }
-    }
+    .bind(RiskStatus::INITIAL)
+    .bind(&risk.risk_map)
+    .bind(&risk.threat_map)
+    .bind(&risk.technology)
+    .bind(&risk.team)
+    .bind(&risk.owner)
+    .bind(&risk.owner_delegate)
+    .bind(&risk.source)
+    .bind(&risk.evaluation_method)
+    .bind(&risk.evaluation_notes)
+    .bind(&risk.additional_notes)
+    .bind(&risk.business_unit)
+    .bind(risk.scoring_method.unwrap_or(scoring.default_method))
+    .bind(risk.residual_impact)
+    .bind(risk.residual_probability)
+    .bind(risk.target_impact)
+    .bind(risk.target_probability)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    replace_all_links(&mut tx, id, risk).await?;
+    scoring.refresh(&mut tx, id).await?;
+    record_breach(&mut tx, id, BreachTrigger::Create, actor).await?;
+
+    let created = Risk::from(fetch_risk(&mut *tx, id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    audit::record(&mut *tx, actor, "risk", id, AuditAction::Create, None, Some(&created)).await?;
+
+    tx.commit().await?;
+    Ok(created)
 }
 
-#[put("/risks/{id}")]
-pub async fn update_risk(
+#[post("/risks")]
+pub async fn create_risk(
     db_pool: web::Data<PgPool>,
-    path: web::Path<Uuid>,
-    updated_risk: web::Json<NewRisk>,
-) -> impl Responder {
-    let id = path.into_inner();
+    user: Authorized<Edit>,
+    rules: web::Data<ValidationConfig>,
+    scoring: web::Data<ScoringService>,
+    risk: web::Json<NewRisk>,
+) -> Result<HttpResponse, ApiError> {
+    risk.validate(&rules)?;
+    // Créer un risque directement Accepted ou Closed contournerait transitions, justification et rôle
+    if let Some(status) = risk.status.filter(|status| *status != RiskStatus::INITIAL) {
+        let mut errors = FieldErrors::new();
+        errors.add("status", format!("Un risque est créé au statut {}, pas {}", RiskStatus::INITIAL, status));
+        errors.into_result()?;
+    }
+
+    let risk = insert_risk(db_pool.get_ref(), &scoring, &risk, &user).await?;
+    Ok(HttpResponse::Ok().json(risk))
+}
 
-    let result = sqlx::query_as::<_, DbRisk>(
+async fn save_risk(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    id: Uuid,
+    risk: &NewRisk,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Risk, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let version = lock_risk(&mut tx, id).await?.ok_or_else(risk_not_found)?;
+    let before = Risk::from(fetch_risk(&mut *tx, id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    if !if_match.matches(version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+    let was_exceeded = exceeds_tolerance(&mut tx, id).await?;
+
+    sqlx::query_scalar::<_, Uuid>(
         r#"
         UPDATE risks
         SET title = $1,
@@ -152,164 +370,282 @@ pub async fn update_risk(
             control_measure_id = $7,
             impact = $8,
             probability = $9,
+            risk_map = $10,
+            threat_map = $11,
+            technology = $12,
+            team = $13,
+            owner = $14,
+            owner_delegate = $15,
+            source = $16,
+            evaluation_method = $17,
+            evaluation_notes = $18,
+            additional_notes = $19,
+            business_unit = $20,
+            scoring_method = COALESCE($21, scoring_method),
+            residual_impact = $22,
+            residual_probability = $23,
+            target_impact = $24,
+            target_probability = $25,
+            version = version + 1,
             updated_at = NOW()
-        WHERE id = $10
-        RETURNING id, title, description, impact, probability, status,
-          created_at, updated_at,
-          external_id, category, location, regulation, control_measure_id;
+        WHERE id = $26
+        RETURNING id;
         "#,
     )
-    .bind(&updated_risk.title)
-    .bind(&updated_risk.description)
-    .bind(&updated_risk.external_id)
-    .bind(&updated_risk.category)
-    .bind(&updated_risk.location)
-    .bind(&updated_risk.regulation)
-    .bind(&updated_risk.control_measure_id)
-    .bind(updated_risk.impact)
-    .bind(updated_risk.probability)
+    .bind(&risk.title)
+    .bind(&risk.description)
+    .bind(&risk.external_id)
+    .bind(&risk.category)
+    .bind(&risk.location)
+    .bind(&risk.regulation)
+    .bind(&risk.control_measure_id)
+    .bind(risk.impact)
+    .bind(risk.probability)
+    .bind(&risk.risk_map)
+    .bind(&risk.threat_map)
+    .bind(&risk.technology)
+    .bind(&risk.team)
+    .bind(&risk.owner)
+    .bind(&risk.owner_delegate)
+    .bind(&risk.source)
+    .bind(&risk.evaluation_method)
+    .bind(&risk.evaluation_notes)
+    .bind(&risk.additional_notes)
+    .bind(&risk.business_unit)
+    .bind(risk.scoring_method)
+    .bind(risk.residual_impact)
+    .bind(risk.residual_probability)
+    .bind(risk.target_impact)
+    .bind(risk.target_probability)
     .bind(id)
-    .fetch_one(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(db_risk) => {
-            let risk = Risk {
-                id: db_risk.id,
-                title: db_risk.title,
-                description: db_risk.description,
-                impact: db_risk.impact,
-                probability: db_risk.probability,
-                status: db_risk.status,
-                created_at: db_risk.created_at,
-                updated_at: db_risk.updated_at,
-                score: Some(db_risk.impact * db_risk.probability),
-                external_id: db_risk.external_id,
-                category: db_risk.category,
-                location: db_risk.location,
-                regulation: db_risk.regulation,
-                control_measure_id: db_risk.control_measure_id,
-            };
-            HttpResponse::Ok().json(risk)
-        },
-        Err(e) => {
-            eprintln!("Erreur mise à jour : {:?}", e);
-            HttpResponse::InternalServerError().finish()
-        }
+    .fetch_one(&mut *tx)
+    .await?;
+
+    replace_all_links(&mut tx, id, risk).await?;
+    scoring.refresh(&mut tx, id).await?;
+    // Seul le franchissement de la tolérance est enregistré, pas chaque écriture au-delà
+    if !was_exceeded {
+        record_breach(&mut tx, id, BreachTrigger::Update, actor).await?;
     }
+
+    let updated = Risk::from(fetch_risk(&mut *tx, id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    audit::record(&mut *tx, actor, "risk", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
 }
 
-#[delete("/risks/{id}")]
-pub async fn delete_risk(
+#[put("/risks/{id}")]
+pub async fn update_risk(
     db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    scoring: web::Data<ScoringService>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    let id = path.into_inner();
+    updated_risk: web::Json<NewRisk>,
+) -> Result<HttpResponse, ApiError> {
+    updated_risk.validate(&rules)?;
+
+    let risk = save_risk(
+        db_pool.get_ref(),
+        &scoring,
+        path.into_inner(),
+        &updated_risk,
+        &if_match,
+        &user,
+    )
+    .await?;
+    Ok(HttpResponse::Ok().insert_header(etag(risk.version)).json(risk))
+}
 
-    let result = sqlx::query("DELETE FROM risks WHERE id = $1")
-        .bind(id)
-        .execute(db_pool.get_ref())
-        .await;
-
-    match result {
-        Ok(_) => HttpResponse::Ok().body("Risque supprimé"),
-        Err(e) => {
-            eprintln!("Erreur suppression : {:?}", e);
-            HttpResponse::InternalServerError().finish()
-        }
+async fn remove_risk(
+    db_pool: &PgPool,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Risk, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let version = lock_risk(&mut tx, id).await?.ok_or_else(risk_not_found)?;
+    let before = Risk::from(fetch_risk(&mut *tx, id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    if !if_match.matches(version) {
+        return Err(ApiError::stale(&before, before.version));
     }
+
+    sqlx::query("DELETE FROM risks WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    audit::record(&mut *tx, actor, "risk", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(before)
 }
 
-#[patch("/risks/{id}/status")]
-pub async fn update_risk_status(
+#[delete("/risks/{id}")]
+pub async fn delete_risk(
     db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    storage: web::Data<dyn AttachmentStorage>,
     path: web::Path<Uuid>,
-    payload: web::Json<UpdateStatusPayload>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let id = path.into_inner();
 
-    let current_status = sqlx::query_scalar::<_, RiskStatus>(
-        "SELECT status FROM risks WHERE id = $1"
+    let storage_keys = risk_storage_keys(db_pool.get_ref(), id).await?;
+    remove_risk(db_pool.get_ref(), id, &if_match, &user).await?;
+    purge_files(storage.get_ref(), &storage_keys).await;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Champs requis absents, qu'ils soient exigés par le statut visé ou par le workflow
+fn missing_fields_error(missing: &[String]) -> ApiError {
+    ApiError::new(
+        ErrorCode::InvalidTransition,
+        format!("Champs requis pour cette transition : {}", missing.join(", ")),
     )
-    .bind(id)
-    .fetch_one(db_pool.get_ref())
-    .await;
-
-    let current_status = match current_status {
-        Ok(status) => status,
-        Err(e) => {
-            eprintln!("Erreur récupération statut courant : {:?}", e);
-            return HttpResponse::InternalServerError().finish();
+    .with_details(json!({ "missing_fields": missing }))
+}
+
+// Lecture, contrôle, mise à jour et historisation dans une même transaction :
+// le verrou posé sur le risque sérialise les changements de statut concurrents
+async fn change_status(
+    db_pool: &PgPool,
+    id: Uuid,
+    payload: &UpdateStatusPayload,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<Risk, ApiError> {
+    let justification = payload.justification.as_deref().map(str::trim).filter(|text| !text.is_empty());
+    if payload.status.requires_justification() && justification.is_none() {
+        return Err(missing_fields_error(&["justification".to_string()]));
+    }
+    if payload.approver_id == Some(actor.id) {
+        return Err(ApiError::bad_request("L'approbateur doit être différent de l'auteur du changement"));
+    }
+
+    let mut tx = db_pool.begin().await?;
+
+    let version = lock_risk(&mut tx, id).await?.ok_or_else(risk_not_found)?;
+    let risk = Risk::from(fetch_risk(&mut *tx, id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    if !if_match.matches(version) {
+        return Err(ApiError::stale(&risk, risk.version));
+    }
+    let current_status = risk.status;
+
+    let approver = match payload.approver_id {
+        Some(approver_id) => {
+            let approver = sqlx::query_as::<_, UserSummary>("SELECT id, username, role FROM users WHERE id = $1")
+                .bind(approver_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            match approver {
+                Some(approver) if ChangeStatus::allows(approver.role) => Some(approver),
+                _ => return Err(ApiError::bad_request("Approbateur inconnu ou non habilité")),
+            }
         }
+        None => None,
+    };
+
+    // Transitions autorisées : workflow actif de l'unité métier du risque
+    let Some(workflow) = workflow::active_workflow(&mut *tx, risk.business_unit.as_deref()).await? else {
+        return Err(ApiError::invalid_transition("Aucun workflow actif pour ce risque"));
+    };
+    let Some(transition) = workflow::find_transition(&mut *tx, workflow.id, current_status, payload.status).await? else {
+        return Err(ApiError::invalid_transition("Transition de statut non autorisée"));
     };
 
-    if !is_valid_transition(&current_status, &payload.status) {
-        return HttpResponse::BadRequest().body("Transition de statut non autorisée");
+    let missing = workflow::missing_fields(&transition, &risk, justification);
+    if !missing.is_empty() {
+        return Err(missing_fields_error(&missing));
+    }
+
+    // Passage en suivi : le traitement doit être achevé
+    if payload.status == RiskStatus::Monitoring {
+        let open_actions = open_mandatory_actions(&mut tx, id).await?;
+        if !open_actions.is_empty() {
+            return Err(ApiError::new(
+                ErrorCode::InvalidTransition,
+                format!("{} action(s) obligatoire(s) du plan de traitement encore ouverte(s)", open_actions.len()),
+            )
+            .with_details(json!({ "open_actions": open_actions })));
+        }
     }
 
-    let result = sqlx::query_as::<_, DbRisk>(
+    sqlx::query(
         r#"
         UPDATE risks
         SET status = $1,
+            version = version + 1,
             updated_at = now()
-        WHERE id = $2
-        RETURNING *;
+        WHERE id = $2;
         "#,
     )
-    .bind(&payload.status)
+    .bind(payload.status)
     .bind(id)
-    .fetch_one(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(db_risk) => {
-            let risk = Risk {
-                score: Some(db_risk.impact * db_risk.probability),
-                id: db_risk.id,
-                title: db_risk.title,
-                description: db_risk.description,
-                impact: db_risk.impact,
-                probability: db_risk.probability,
-                status: db_risk.status,
-                created_at: db_risk.created_at,
-                updated_at: db_risk.updated_at,
-                external_id: db_risk.external_id,
-                category: db_risk.category,
-                location: db_risk.location,
-                regulation: db_risk.regulation,
-                control_measure_id: db_risk.control_measure_id,
-            };
-
-            // Historiser le changement de statut
-            let _ = sqlx::query(
-                r#"
-                INSERT INTO risk_status_history (risk_id, old_status, new_status)
-                VALUES ($1, $2, $3);
-                "#
-            )
-            .bind(id)
-            .bind(current_status.to_string())
-            .bind(payload.status.to_string())
-            .execute(db_pool.get_ref())
-            .await;
+    .execute(&mut *tx)
+    .await?;
 
-            HttpResponse::Ok().json(risk)
-        }
-        Err(e) => {
-            eprintln!("Erreur mise à jour statut : {:?}", e);
-            HttpResponse::InternalServerError().finish()
-        }
-    }
+    sqlx::query(
+        r#"
+        INSERT INTO risk_status_history (
+            risk_id, old_status, new_status, justification,
+            changed_by, changed_by_username, approved_by, approved_by_username
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
+        "#
+    )
+    .bind(id)
+    .bind(current_status.to_string())
+    .bind(payload.status.to_string())
+    .bind(justification)
+    .bind(actor.id)
+    .bind(&actor.username)
+    .bind(approver.as_ref().map(|a| a.id))
+    .bind(approver.as_ref().map(|a| a.username.as_str()))
+    .execute(&mut *tx)
+    .await?;
+
+    audit::record(
+        &mut *tx,
+        actor,
+        "risk",
+        id,
+        AuditAction::Update,
+        Some(&json!({ "status": current_status })),
+        Some(&json!({ "status": payload.status })),
+    )
+    .await?;
+
+    let updated = Risk::from(fetch_risk(&mut *tx, id).await?.ok_or(sqlx::Error::RowNotFound)?);
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[patch("/risks/{id}/status")]
+pub async fn update_risk_status(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<ChangeStatus>,
+    if_match: IfMatch,
+    path: web::Path<Uuid>,
+    payload: web::Json<UpdateStatusPayload>,
+) -> Result<HttpResponse, ApiError> {
+    let risk = change_status(db_pool.get_ref(), path.into_inner(), &payload, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(risk.version)).json(risk))
 }
 
 #[get("/risks/{id}/history")]
 pub async fn get_risk_history(
     db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let id = path.into_inner();
 
-    let result = sqlx::query_as::<_, RiskStatusHistory>(
+    let history = sqlx::query_as::<_, RiskStatusHistory>(
         r#"
         SELECT * FROM risk_status_history
         WHERE risk_id = $1
@@ -318,15 +654,9 @@ pub async fn get_risk_history(
     )
     .bind(id)
     .fetch_all(db_pool.get_ref())
-    .await;
+    .await?;
 
-    match result {
-        Ok(history) => HttpResponse::Ok().json(history),
-        Err(err) => {
-            eprintln!("Erreur récupération historique : {:?}", err);
-            HttpResponse::InternalServerError().finish()
-        }
-    }
+    Ok(HttpResponse::Ok().json(history))
 }
 
 #[derive(Deserialize)]
@@ -334,129 +664,317 @@ pub struct RiskEvaluationInput {
     pub severity: i32,
     pub likelihood: i32,
     pub detectability: i32,
+    // Absente : évaluation du risque inhérent
+    pub basis: Option<ScoreBasis>,
+    pub notes: Option<String>,
+}
+
+impl Validate for RiskEvaluationInput {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors
+            .range("severity", self.severity, config.evaluation_scale)
+            .range("likelihood", self.likelihood, config.evaluation_scale)
+            .range("detectability", self.detectability, config.evaluation_scale);
+        errors.into_result()
+    }
 }
 
+// Ajoute une évaluation à l'historique du risque
 #[post("/risks/{id}/evaluation")]
 pub async fn create_evaluation(
     db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    rules: web::Data<ValidationConfig>,
+    scoring: web::Data<ScoringService>,
     path: web::Path<Uuid>,
     payload: web::Json<RiskEvaluationInput>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
+    payload.validate(&rules)?;
     let risk_id = path.into_inner();
-
-    let result = sqlx::query_as::<_, RiskEvaluation>(
+    let mut tx = db_pool.begin().await?;
+    lock_risk(&mut tx, risk_id).await?.ok_or_else(risk_not_found)?;
+    let before = Risk::from(fetch_risk(&mut *tx, risk_id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    let was_exceeded = exceeds_tolerance(&mut tx, risk_id).await?;
+
+    let basis = payload.basis.unwrap_or(ScoreBasis::Inherent);
+    let notes = payload.notes.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let evaluation_id = sqlx::query_scalar::<_, Uuid>(
         r#"
-        INSERT INTO risk_evaluation (risk_id, severity, likelihood, detectability)
-        VALUES ($1, $2, $3, $4)
-        RETURNING *;
+        INSERT INTO risk_evaluation (
+            risk_id, basis, severity, likelihood, detectability, notes, evaluated_by, evaluated_by_username
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id;
         "#
     )
     .bind(risk_id)
+    .bind(basis)
     .bind(payload.severity)
     .bind(payload.likelihood)
     .bind(payload.detectability)
-    .fetch_one(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(evaluation) => HttpResponse::Ok().json(evaluation),
-        Err(err) => {
-            eprintln!("Erreur insertion évaluation : {:?}", err);
-            HttpResponse::InternalServerError().finish()
-        }
+    .bind(notes)
+    .bind(user.id)
+    .bind(&user.username)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Le score des risques notés en AMDEC suit la dernière évaluation de la même base ; il est conservé avec elle
+    let risk_score = scoring.refresh_basis(&mut tx, risk_id, basis).await?;
+    let evaluation = sqlx::query_as::<_, RiskEvaluation>(
+        "UPDATE risk_evaluation SET risk_score = $1 WHERE id = $2 RETURNING *"
+    )
+    .bind(risk_score)
+    .bind(evaluation_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Score, niveau ou échéance de revue modifiés : nouvelle version du risque, périmant son ETag, et entrée d'audit
+    let refreshed = Risk::from(fetch_risk(&mut *tx, risk_id).await?.ok_or(sqlx::Error::RowNotFound)?);
+    if audit::diff(Some(&before), Some(&refreshed)).as_object().is_some_and(|changes| !changes.is_empty()) {
+        sqlx::query("UPDATE risks SET version = version + 1, updated_at = now() WHERE id = $1")
+            .bind(risk_id)
+            .execute(&mut *tx)
+            .await?;
+        let after = Risk::from(fetch_risk(&mut *tx, risk_id).await?.ok_or(sqlx::Error::RowNotFound)?);
+        audit::record(&mut *tx, &user, "risk", risk_id, AuditAction::Update, Some(&before), Some(&after)).await?;
     }
+    if !was_exceeded {
+        record_breach(&mut tx, risk_id, BreachTrigger::Evaluation, &user).await?;
+    }
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(evaluation))
 }
 
-#[get("/risks/{id}/evaluation")]
-pub async fn get_evaluation(
+// Historique des évaluations du risque, de la plus ancienne à la plus récente
+#[get("/risks/{id}/evaluations")]
+pub async fn get_evaluations(
     db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<HttpResponse, ApiError> {
     let risk_id = path.into_inner();
 
-    let result = sqlx::query_as::<_, RiskEvaluation>(
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM risks WHERE id = $1)")
+        .bind(risk_id)
+        .fetch_one(db_pool.get_ref())
+        .await?;
+    if !exists {
+        return Err(risk_not_found());
+    }
+
+    let evaluations = sqlx::query_as::<_, RiskEvaluation>(
         r#"
         SELECT * FROM risk_evaluation
-        WHERE risk_id = $1;
+        WHERE risk_id = $1
+        ORDER BY created_at, id;
         "#
     )
     .bind(risk_id)
-    .fetch_optional(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(Some(evaluation)) => HttpResponse::Ok().json(evaluation),
-        Ok(None) => HttpResponse::NotFound().body("Aucune évaluation trouvée"),
-        Err(err) => {
-            eprintln!("Erreur récupération évaluation : {:?}", err);
-            HttpResponse::InternalServerError().finish()
-        }
-    }
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(evaluations))
+}
+
+async fn latest_evaluation(db_pool: &PgPool, risk_id: Uuid) -> Result<HttpResponse, ApiError> {
+    let evaluation = sqlx::query_as::<_, RiskEvaluation>(
+        r#"
+        SELECT * FROM risk_evaluation
+        WHERE risk_id = $1
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1;
+        "#
+    )
+    .bind(risk_id)
+    .fetch_optional(db_pool)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Aucune évaluation trouvée"))?;
+
+    Ok(HttpResponse::Ok().json(evaluation))
+}
+
+#[get("/risks/{id}/evaluations/latest")]
+pub async fn get_latest_evaluation(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    latest_evaluation(db_pool.get_ref(), path.into_inner()).await
 }
 
+// Ancienne route, conservée : dernière évaluation
+#[get("/risks/{id}/evaluation")]
+pub async fn get_evaluation(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    latest_evaluation(db_pool.get_ref(), path.into_inner()).await
+}
+
+#[derive(Deserialize)]
+pub struct CriticalRiskQuery {
+    // Nom d'un niveau (`High`) ou score minimal (`12`)
+    pub threshold: Option<String>,
+    // Score de classement : inhérent (par défaut), résiduel ou cible
+    pub basis: Option<ScoreBasis>,
+}
+
+// Risques dont le score (de la base demandée) atteint le seuil, par défaut celui du niveau le plus élevé
 #[get("/risks/critical")]
 pub async fn get_critical_risks(
     db_pool: web::Data<PgPool>,
-) -> impl Responder {
-    let result = sqlx::query_as::<_, CriticalRisk>(
+    _user: Authorized<Read>,
+    scoring: web::Data<ScoringService>,
+    query: web::Query<CriticalRiskQuery>,
+) -> Result<HttpResponse, ApiError> {
+    scoring.sync_levels(&mut *db_pool.acquire().await?).await?;
+
+    let mut errors = FieldErrors::new();
+    // Seuil par méthode de notation : un score explicite vaut pour toutes, un niveau a ses seuils propres
+    let thresholds = |level: Option<&RiskLevel>| {
+        ScoringMethod::ALL.map(|method| level.map_or(i32::MIN, |level| level.threshold(method)))
+    };
+    let [impact_probability, fmea, weighted] = match query.threshold.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        None => thresholds(scoring.critical_level().as_ref()),
+        Some(threshold) => match (threshold.parse::<i32>(), scoring.level_named(threshold)) {
+            (Ok(score), _) => [score; 3],
+            (Err(_), Some(level)) => thresholds(Some(&level)),
+            (Err(_), None) => {
+                let names: Vec<String> = scoring.levels().into_iter().map(|level| level.name).collect();
+                errors.add("threshold", format!("Score ou niveau attendu, niveaux possibles : {}", names.join(", ")));
+                [0; 3]
+            }
+        },
+    };
+    errors.into_result()?;
+
+    let basis = query.basis.unwrap_or(ScoreBasis::Inherent);
+    let prefix = basis.column_prefix();
+    let risks = sqlx::query_as::<_, CriticalRisk>(&format!(
         r#"
-        SELECT r.id, r.title, r.status, e.score
+        SELECT r.id, r.title, r.status, r.scoring_method, $2 AS basis,
+            r.{prefix}score AS score, r.{prefix}level AS level, r.external_id, r.category,
+            r.location, r.regulation, r.control_measure_id
         FROM risks r
-        JOIN risk_evaluation e ON r.id = e.risk_id
-        ORDER BY e.score DESC;
+        WHERE r.{prefix}score >= CASE r.scoring_method WHEN 'Fmea' THEN $3 WHEN 'Weighted' THEN $4 ELSE $1 END
+        ORDER BY r.{prefix}score DESC, r.id;
         "#
-    )
+    ))
+    .bind(impact_probability)
+    .bind(basis)
+    .bind(fmea)
+    .bind(weighted)
     .fetch_all(db_pool.get_ref())
-    .await;
+    .await?;
 
-    match result {
-        Ok(risks) => HttpResponse::Ok().json(risks),
-        Err(err) => {
-            eprintln!("Erreur récupération risques critiques : {:?}", err);
-            HttpResponse::InternalServerError().finish()
-        }
+    Ok(HttpResponse::Ok().json(risks))
+}
+
+// Risques dont la revue périodique est en retard, du plus ancien retard au plus récent
+#[get("/risks/overdue")]
+pub async fn get_overdue_risks(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+) -> Result<HttpResponse, ApiError> {
+    let risks = sqlx::query_as::<_, DbRisk>(&format!(
+        "{RISK_SELECT} WHERE r.review_overdue ORDER BY r.next_review_at, r.id"
+    ))
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(risks.into_iter().map(Risk::from).collect::<Vec<_>>()))
+}
+
+// Enregistre la revue du risque et reporte sa prochaine échéance
+#[post("/risks/{id}/review")]
+pub async fn review_risk(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    scoring: web::Data<ScoringService>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let risk_id = path.into_inner();
+    let mut tx = db_pool.begin().await?;
+    let version = lock_risk(&mut tx, risk_id).await?.ok_or_else(risk_not_found)?;
+    let before = Risk::from(fetch_risk(&mut *tx, risk_id).await?.ok_or_else(risk_not_found)?);
+    if !if_match.matches(version) {
+        return Err(ApiError::stale(&before, before.version));
     }
+
+    sqlx::query("UPDATE risks SET last_reviewed_at = now(), version = version + 1, updated_at = now() WHERE id = $1")
+        .bind(risk_id)
+        .execute(&mut *tx)
+        .await?;
+    scoring.schedule_review(&mut tx, risk_id, before.level.as_deref()).await?;
+
+    let after = Risk::from(fetch_risk(&mut *tx, risk_id).await?.ok_or_else(risk_not_found)?);
+    audit::record(&mut *tx, &user, "risk", risk_id, AuditAction::Update, Some(&before), Some(&after)).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().insert_header(etag(after.version)).json(after))
 }
-#[get("/risks/{id}")]
-pub async fn get_risk_by_id(
+
+#[derive(Deserialize)]
+pub struct ProbabilitySuggestionQuery {
+    pub window_days: Option<i32>,
+}
+
+// Probabilité suggérée d'après la fréquence et la gravité des incidents liés au risque
+#[get("/risks/{id}/probability-suggestion")]
+pub async fn get_probability_suggestion(
     db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    rules: web::Data<ValidationConfig>,
+    config: web::Data<LikelihoodConfig>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    let id = path.into_inner();
+    query: web::Query<ProbabilitySuggestionQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let window_days = query.window_days.unwrap_or(config.window_days);
+    let mut errors = FieldErrors::new();
+    errors.range("window_days", window_days, likelihood::WINDOW_DAYS);
+    errors.into_result()?;
 
-    let result = sqlx::query_as::<_, DbRisk>(
+    let risk_id = path.into_inner();
+    let probability = sqlx::query_scalar::<_, i32>("SELECT probability FROM risks WHERE id = $1")
+        .bind(risk_id)
+        .fetch_optional(db_pool.get_ref())
+        .await?
+        .ok_or_else(risk_not_found)?;
+
+    let since = Utc::now().naive_utc() - Duration::days(window_days.into());
+    let counts = sqlx::query_as::<_, SeverityCount>(
         r#"
-        SELECT * FROM risks WHERE id = $1;
+        SELECT i.severity, COUNT(*) AS count
+        FROM incidents i
+        JOIN incident_risks ir ON ir.incident_id = i.id
+        WHERE ir.risk_id = $1 AND i.created_at >= $2
+        GROUP BY i.severity
+        ORDER BY i.severity
         "#
     )
-    .bind(id)
-    .fetch_optional(db_pool.get_ref())
-    .await;
-
-    match result {
-        Ok(Some(db_risk)) => {
-            let risk = Risk {
-                id: db_risk.id,
-                title: db_risk.title,
-                description: db_risk.description,
-                impact: db_risk.impact,
-                probability: db_risk.probability,
-                status: db_risk.status,
-                created_at: db_risk.created_at,
-                updated_at: db_risk.updated_at,
-                external_id: db_risk.external_id,
-                category: db_risk.category,
-                location: db_risk.location,
-                regulation: db_risk.regulation,
-                control_measure_id: db_risk.control_measure_id,
-                score: Some(db_risk.impact * db_risk.probability),
-            };
-            HttpResponse::Ok().json(risk)
-        }
-        Ok(None) => HttpResponse::NotFound().body("Risque non trouvé"),
-        Err(e) => {
-            eprintln!("Erreur récupération du risque : {:?}", e);
-            HttpResponse::InternalServerError().finish()
-        }
-    }
-}
\ No newline at end of file
+    .bind(risk_id)
+    .bind(since)
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    let suggestion = likelihood::suggest(risk_id, probability, window_days, counts, rules.probability_scale);
+    Ok(HttpResponse::Ok().json(suggestion))
+}
+
+#[get("/risks/{id}")]
+pub async fn get_risk_by_id(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let db_risk = fetch_risk(db_pool.get_ref(), path.into_inner())
+        .await?
+        .ok_or_else(risk_not_found)?;
+
+    let risk = Risk::from(db_risk);
+    Ok(HttpResponse::Ok().insert_header(etag(risk.version)).json(risk))
+}
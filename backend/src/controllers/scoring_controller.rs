@@ -0,0 +1,86 @@
+use actix_web::{get, put, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::audit;
+use crate::auth::{Admin, AuthenticatedUser, Authorized, Read};
+use crate::concurrency::{etag, IfMatch};
+use crate::error::ApiError;
+use crate::models::audit::AuditAction;
+use crate::scoring::{NewRiskLevels, RiskLevelSettings, ScoringService};
+use crate::validation::{Validate, ValidationConfig};
+
+// Méthode par défaut, pondérations et niveaux de risque (seuils et couleurs)
+#[get("/scoring/config")]
+pub async fn get_scoring_config(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    scoring: web::Data<ScoringService>,
+) -> Result<HttpResponse, ApiError> {
+    scoring.sync_levels(&mut *db_pool.acquire().await?).await?;
+    Ok(HttpResponse::Ok().json(scoring.get_ref()))
+}
+
+// Niveaux enregistrés, avec leur version
+#[get("/scoring/levels")]
+pub async fn get_risk_levels(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Admin>,
+    scoring: web::Data<ScoringService>,
+) -> Result<HttpResponse, ApiError> {
+    let mut conn = db_pool.acquire().await?;
+    let settings = scoring.stored_levels(&mut conn).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(settings.version)).json(settings))
+}
+
+async fn save_levels(
+    db_pool: &PgPool,
+    scoring: &ScoringService,
+    data: &NewRiskLevels,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<RiskLevelSettings, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    scoring.stored_levels(&mut tx).await?;
+    let before = sqlx::query_as::<_, RiskLevelSettings>("SELECT * FROM risk_level_settings FOR UPDATE")
+        .fetch_one(&mut *tx)
+        .await?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    let updated = sqlx::query_as::<_, RiskLevelSettings>(
+        r#"
+        UPDATE risk_level_settings
+        SET levels = $1, version = version + 1, updated_at = now()
+        WHERE id = $2
+        RETURNING *
+        "#
+    )
+    .bind(sqlx::types::Json(&data.levels))
+    .bind(before.id)
+    .fetch_one(&mut *tx)
+    .await?;
+    audit::record(&mut *tx, actor, "risk_levels", updated.id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+    scoring.refresh_levels(&mut tx, &data.levels).await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+// Remplace les niveaux et recalcule, dans la même transaction, niveaux et échéances de revue de tous les risques
+#[put("/scoring/levels")]
+pub async fn update_risk_levels(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    scoring: web::Data<ScoringService>,
+    data: web::Json<NewRiskLevels>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let settings = save_levels(db_pool.get_ref(), &scoring, &data, &if_match, &user).await?;
+    scoring.set_levels(&settings);
+    Ok(HttpResponse::Ok().insert_header(etag(settings.version)).json(settings))
+}
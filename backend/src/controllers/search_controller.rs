@@ -0,0 +1,80 @@
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{Authorized, Read};
+use crate::error::ApiError;
+use crate::validation::{FieldErrors, ScaleRange};
+
+const LIMIT: ScaleRange = ScaleRange { min: 1, max: 100 };
+
+#[derive(Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    pub limit: Option<i32>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SearchHit {
+    // "risk" ou "incident"
+    pub kind: String,
+    pub id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub rank: f32,
+    // Extrait du texte, termes trouvés entourés de <mark>...</mark> (contenu non échappé)
+    pub highlight: String,
+}
+
+// Recherche plein texte dans les risques et les incidents, résultats classés par pertinence
+#[get("/search")]
+pub async fn search(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let text = query.q.as_deref().map(str::trim).unwrap_or_default();
+    let limit = query.limit.unwrap_or(20);
+    let mut errors = FieldErrors::new();
+    if text.is_empty() {
+        errors.add("q", "Texte à rechercher requis");
+    }
+    errors.range("limit", limit, LIMIT);
+    errors.into_result()?;
+
+    let hits = sqlx::query_as::<_, SearchHit>(
+        r#"
+        WITH query AS (SELECT websearch_to_tsquery('french', $1) AS tsq)
+        SELECT 'risk' AS kind, r.id, r.title, r.status,
+            ts_rank(r.search_vector, query.tsq) AS rank,
+            ts_headline(
+                'french',
+                concat_ws(' — ', r.title, r.description, r.evaluation_notes, r.additional_notes),
+                query.tsq,
+                'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10'
+            ) AS highlight
+        FROM risks r, query
+        WHERE r.search_vector @@ query.tsq
+        UNION ALL
+        SELECT 'incident' AS kind, i.id, i.title, i.status,
+            ts_rank(i.search_vector, query.tsq) AS rank,
+            ts_headline(
+                'french',
+                concat_ws(' — ', i.title, i.description),
+                query.tsq,
+                'StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10'
+            ) AS highlight
+        FROM incidents i, query
+        WHERE i.search_vector @@ query.tsq
+        ORDER BY rank DESC, title
+        LIMIT $2
+        "#
+    )
+    .bind(text)
+    .bind(i64::from(limit))
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(hits))
+}
@@ -0,0 +1,564 @@
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use chrono::NaiveDate;
+use serde::Serialize;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder, Transaction};
+use uuid::Uuid;
+
+use crate::audit;
+use crate::auth::{Admin, AuthenticatedUser, Authorized, Edit, Read};
+use crate::concurrency::{etag, IfMatch};
+use crate::error::ApiError;
+use crate::listing;
+use crate::models::audit::AuditAction;
+use crate::models::risk::RiskStatus;
+use crate::models::treatment::{ActionItem, ActionStatus, NewActionItem, NewTreatmentPlan, TreatmentPlan, TreatmentPlanDetail};
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+// Actions enrichies du risque traité, du nom de l'assigné et du retard
+const ACTION_SELECT: &str = r#"
+    SELECT a.id, a.plan_id, p.risk_id, a.title, a.description, a.assignee_id, u.username AS assignee_username,
+        a.due_date, a.progress, a.status, a.mandatory, a.completed_at,
+        COALESCE(a.due_date < current_date AND a.status IN ('Open', 'InProgress'), false) AS overdue,
+        a.version, a.created_at, a.updated_at
+    FROM action_items a
+    JOIN treatment_plans p ON p.id = a.plan_id
+    LEFT JOIN users u ON u.id = a.assignee_id
+"#;
+
+// Plans d'un risque (ou le plan d'identifiant `id`) avec leurs actions
+async fn load_plans(
+    conn: &mut PgConnection,
+    risk_id: Option<Uuid>,
+    id: Option<Uuid>,
+) -> Result<Vec<TreatmentPlanDetail>, sqlx::Error> {
+    let plans = sqlx::query_as::<_, TreatmentPlan>(
+        r#"
+        SELECT * FROM treatment_plans
+        WHERE ($1::uuid IS NULL OR risk_id = $1) AND ($2::uuid IS NULL OR id = $2)
+        ORDER BY created_at, id;
+        "#
+    )
+    .bind(risk_id)
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let ids: Vec<Uuid> = plans.iter().map(|p| p.id).collect();
+    let mut actions = sqlx::query_as::<_, ActionItem>(&format!(
+        "{ACTION_SELECT} WHERE a.plan_id = ANY($1) ORDER BY a.due_date NULLS LAST, a.created_at, a.id"
+    ))
+    .bind(&ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(plans
+        .into_iter()
+        .map(|plan| {
+            let (own, rest) = actions.drain(..).partition(|a| a.plan_id == plan.id);
+            actions = rest;
+            TreatmentPlanDetail { plan, actions: own }
+        })
+        .collect())
+}
+
+async fn load_plan(conn: &mut PgConnection, id: Uuid) -> Result<Option<TreatmentPlanDetail>, sqlx::Error> {
+    Ok(load_plans(conn, None, Some(id)).await?.pop())
+}
+
+async fn lock_plan(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Option<TreatmentPlanDetail>, sqlx::Error> {
+    let locked = sqlx::query_scalar::<_, Uuid>("SELECT id FROM treatment_plans WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?;
+    match locked {
+        Some(_) => load_plan(tx, id).await,
+        None => Ok(None),
+    }
+}
+
+async fn fetch_action<'e, E>(executor: E, id: Uuid) -> Result<Option<ActionItem>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, ActionItem>(&format!("{ACTION_SELECT} WHERE a.id = $1"))
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+}
+
+async fn lock_action(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<Option<ActionItem>, sqlx::Error> {
+    sqlx::query_as::<_, ActionItem>(&format!("{ACTION_SELECT} WHERE a.id = $1 FOR UPDATE OF a"))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+}
+
+// Verrouille le risque traité par le plan, comme `change_status` avant le passage en suivi, et renvoie
+// son statut : une action obligatoire ne peut être ouverte pendant que le risque passe en `Monitoring`
+async fn lock_treated_risk(tx: &mut Transaction<'_, Postgres>, plan_id: Uuid) -> Result<Option<RiskStatus>, sqlx::Error> {
+    sqlx::query_scalar::<_, RiskStatus>(
+        "SELECT r.status FROM risks r JOIN treatment_plans p ON p.risk_id = r.id WHERE p.id = $1 FOR UPDATE OF r"
+    )
+    .bind(plan_id)
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+fn risk_in_monitoring() -> ApiError {
+    ApiError::conflict("Risque en suivi : aucune action obligatoire ne peut y être ouverte")
+}
+
+fn plan_not_found() -> ApiError {
+    ApiError::not_found("Plan de traitement non trouvé")
+}
+
+fn action_not_found() -> ApiError {
+    ApiError::not_found("Action non trouvée")
+}
+
+// Action obligatoire encore ouverte, qui bloque le passage du risque en suivi
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct OpenAction {
+    pub id: Uuid,
+    pub title: String,
+    pub status: ActionStatus,
+    pub due_date: Option<NaiveDate>,
+}
+
+pub async fn open_mandatory_actions(conn: &mut PgConnection, risk_id: Uuid) -> Result<Vec<OpenAction>, sqlx::Error> {
+    sqlx::query_as::<_, OpenAction>(
+        r#"
+        SELECT a.id, a.title, a.status, a.due_date
+        FROM action_items a
+        JOIN treatment_plans p ON p.id = a.plan_id
+        WHERE p.risk_id = $1 AND a.mandatory AND a.status IN ('Open', 'InProgress')
+        ORDER BY a.due_date NULLS LAST, a.created_at;
+        "#
+    )
+    .bind(risk_id)
+    .fetch_all(conn)
+    .await
+}
+
+// Plans de traitement d'un risque
+#[get("/risks/{id}/treatment-plans")]
+pub async fn get_treatment_plans(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let mut conn = db_pool.acquire().await?;
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS (SELECT 1 FROM risks WHERE id = $1)")
+        .bind(*id)
+        .fetch_one(&mut *conn)
+        .await?;
+    if !exists {
+        return Err(ApiError::not_found("Risque non trouvé"));
+    }
+
+    let plans = load_plans(&mut conn, Some(*id), None).await?;
+    Ok(HttpResponse::Ok().json(plans))
+}
+
+async fn insert_plan(
+    db_pool: &PgPool,
+    risk_id: Uuid,
+    data: &NewTreatmentPlan,
+    actor: &AuthenticatedUser,
+) -> Result<TreatmentPlanDetail, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let exists = sqlx::query_scalar::<_, Uuid>("SELECT id FROM risks WHERE id = $1 FOR SHARE")
+        .bind(risk_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+    if exists.is_none() {
+        return Err(ApiError::not_found("Risque non trouvé"));
+    }
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO treatment_plans (risk_id, title, strategy, description, owner, due_date)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        "#
+    )
+    .bind(risk_id)
+    .bind(data.title.trim())
+    .bind(data.strategy)
+    .bind(&data.description)
+    .bind(data.owner.as_deref().map(str::trim))
+    .bind(data.due_date)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let plan = load_plan(&mut tx, id).await?.ok_or_else(plan_not_found)?;
+    audit::record(&mut *tx, actor, "treatment_plan", id, AuditAction::Create, None, Some(&plan)).await?;
+
+    tx.commit().await?;
+    Ok(plan)
+}
+
+#[post("/risks/{id}/treatment-plans")]
+pub async fn create_treatment_plan(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    rules: web::Data<ValidationConfig>,
+    id: web::Path<Uuid>,
+    data: web::Json<NewTreatmentPlan>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let plan = insert_plan(db_pool.get_ref(), *id, &data, &user).await?;
+    Ok(HttpResponse::Created().insert_header(etag(plan.plan.version)).json(plan))
+}
+
+#[get("/treatment-plans/{id}")]
+pub async fn get_treatment_plan(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let mut conn = db_pool.acquire().await?;
+    let plan = load_plan(&mut conn, *id).await?.ok_or_else(plan_not_found)?;
+    Ok(HttpResponse::Ok().insert_header(etag(plan.plan.version)).json(plan))
+}
+
+async fn save_plan(
+    db_pool: &PgPool,
+    id: Uuid,
+    data: &NewTreatmentPlan,
+    rules: &ValidationConfig,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<TreatmentPlanDetail, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = lock_plan(&mut tx, id).await?.ok_or_else(plan_not_found)?;
+    if !if_match.matches(before.plan.version) {
+        return Err(ApiError::stale(&before, before.plan.version));
+    }
+    data.validate_update(rules, before.plan.due_date)?;
+
+    sqlx::query(
+        r#"
+        UPDATE treatment_plans
+        SET title = $1, strategy = $2, description = $3, owner = $4, due_date = $5,
+            version = version + 1, updated_at = now()
+        WHERE id = $6
+        "#
+    )
+    .bind(data.title.trim())
+    .bind(data.strategy)
+    .bind(&data.description)
+    .bind(data.owner.as_deref().map(str::trim))
+    .bind(data.due_date)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let updated = load_plan(&mut tx, id).await?.ok_or_else(plan_not_found)?;
+    audit::record(&mut *tx, actor, "treatment_plan", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[put("/treatment-plans/{id}")]
+pub async fn update_treatment_plan(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    id: web::Path<Uuid>,
+    data: web::Json<NewTreatmentPlan>,
+) -> Result<HttpResponse, ApiError> {
+    let plan = save_plan(db_pool.get_ref(), *id, &data, &rules, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(plan.plan.version)).json(plan))
+}
+
+async fn remove_plan(
+    db_pool: &PgPool,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<(), ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = lock_plan(&mut tx, id).await?.ok_or_else(plan_not_found)?;
+    if !if_match.matches(before.plan.version) {
+        return Err(ApiError::stale(&before, before.plan.version));
+    }
+
+    sqlx::query("DELETE FROM treatment_plans WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    audit::record(&mut *tx, actor, "treatment_plan", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[delete("/treatment-plans/{id}")]
+pub async fn delete_treatment_plan(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    remove_plan(db_pool.get_ref(), *id, &if_match, &user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Filtres, tri et pagination de `GET /actions`
+#[derive(serde::Deserialize)]
+pub struct ActionQuery {
+    pub assignee_id: Option<Uuid>,
+    pub risk_id: Option<Uuid>,
+    // Statuts séparés par des virgules
+    pub status: Option<String>,
+    // Échéance au plus tard à cette date (incluse)
+    pub due_to: Option<NaiveDate>,
+    // Uniquement les actions en retard
+    #[serde(default)]
+    pub overdue: bool,
+    pub sort: Option<String>,
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+}
+
+const ACTION_SORT_COLUMNS: &[(&str, &str)] = &[
+    ("due_date", "a.due_date"),
+    ("title", "a.title"),
+    ("status", "a.status"),
+    ("progress", "a.progress"),
+    ("created_at", "a.created_at"),
+    ("updated_at", "a.updated_at"),
+];
+
+fn push_action_filters(builder: &mut QueryBuilder<'_, Postgres>, query: &ActionQuery, statuses: &[ActionStatus]) {
+    builder.push(" WHERE TRUE");
+    if let Some(assignee_id) = query.assignee_id {
+        builder.push(" AND a.assignee_id = ").push_bind(assignee_id);
+    }
+    if let Some(risk_id) = query.risk_id {
+        builder.push(" AND p.risk_id = ").push_bind(risk_id);
+    }
+    if !statuses.is_empty() {
+        let statuses: Vec<String> = statuses.iter().map(ToString::to_string).collect();
+        builder.push(" AND a.status = ANY(").push_bind(statuses).push(")");
+    }
+    if let Some(due_to) = query.due_to {
+        builder.push(" AND a.due_date <= ").push_bind(due_to);
+    }
+    if query.overdue {
+        builder.push(" AND a.due_date < current_date AND a.status IN ('Open', 'InProgress')");
+    }
+}
+
+// Actions de tous les plans, par exemple celles d'un assigné
+#[get("/actions")]
+pub async fn get_all_actions(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    query: web::Query<ActionQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mut errors = FieldErrors::new();
+    let statuses = listing::parse_list::<ActionStatus>("status", query.status.as_deref(), &mut errors);
+    let (limit, offset) = listing::page(query.page, query.per_page, &mut errors);
+    let order_by = listing::order_by(query.sort.as_deref(), "due_date", ACTION_SORT_COLUMNS, "a.id", &mut errors);
+    errors.into_result()?;
+
+    let mut count = QueryBuilder::new(
+        "SELECT COUNT(*) FROM action_items a JOIN treatment_plans p ON p.id = a.plan_id",
+    );
+    push_action_filters(&mut count, &query, &statuses);
+    let total = count.build_query_scalar::<i64>().fetch_one(db_pool.get_ref()).await?;
+
+    let mut select = QueryBuilder::new(ACTION_SELECT);
+    push_action_filters(&mut select, &query, &statuses);
+    select.push(order_by).push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+    let actions = select.build_query_as::<ActionItem>().fetch_all(db_pool.get_ref()).await?;
+
+    Ok(listing::paginated(&actions, total))
+}
+
+async fn insert_action(
+    db_pool: &PgPool,
+    plan_id: Uuid,
+    data: &NewActionItem,
+    actor: &AuthenticatedUser,
+) -> Result<ActionItem, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let opens_mandatory = data.mandatory.unwrap_or(true) && data.status.unwrap_or(ActionStatus::Open).is_open();
+    if opens_mandatory && lock_treated_risk(&mut tx, plan_id).await? == Some(RiskStatus::Monitoring) {
+        return Err(risk_in_monitoring());
+    }
+    lock_plan(&mut tx, plan_id).await?.ok_or_else(plan_not_found)?;
+
+    // Une action terminée est complète à 100 %
+    let id = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO action_items (
+            plan_id, title, description, assignee_id, due_date, progress, status, mandatory, completed_at
+        )
+        VALUES (
+            $1, $2, $3, $4, $5,
+            CASE WHEN $7::text = 'Done' THEN 100 ELSE $6 END,
+            $7, $8,
+            CASE WHEN $7::text = 'Done' THEN now() END
+        )
+        RETURNING id
+        "#
+    )
+    .bind(plan_id)
+    .bind(data.title.trim())
+    .bind(&data.description)
+    .bind(data.assignee_id)
+    .bind(data.due_date)
+    .bind(data.progress.unwrap_or(0))
+    .bind(data.status.unwrap_or(ActionStatus::Open))
+    .bind(data.mandatory.unwrap_or(true))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let action = fetch_action(&mut *tx, id).await?.ok_or_else(action_not_found)?;
+    audit::record(&mut *tx, actor, "action_item", id, AuditAction::Create, None, Some(&action)).await?;
+
+    tx.commit().await?;
+    Ok(action)
+}
+
+#[post("/treatment-plans/{id}/actions")]
+pub async fn create_action(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    rules: web::Data<ValidationConfig>,
+    id: web::Path<Uuid>,
+    data: web::Json<NewActionItem>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let action = insert_action(db_pool.get_ref(), *id, &data, &user).await?;
+    Ok(HttpResponse::Created().insert_header(etag(action.version)).json(action))
+}
+
+#[get("/actions/{id}")]
+pub async fn get_action(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let action = fetch_action(db_pool.get_ref(), *id).await?.ok_or_else(action_not_found)?;
+    Ok(HttpResponse::Ok().insert_header(etag(action.version)).json(action))
+}
+
+async fn save_action(
+    db_pool: &PgPool,
+    id: Uuid,
+    data: &NewActionItem,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<ActionItem, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    // Le risque est verrouillé avant l'action, dans le même ordre que les autres écritures :
+    // une modification peut rouvrir une action obligatoire
+    let plan_id = sqlx::query_scalar::<_, Uuid>("SELECT plan_id FROM action_items WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(action_not_found)?;
+    let risk_status = lock_treated_risk(&mut tx, plan_id).await?;
+
+    let before = lock_action(&mut tx, id).await?.ok_or_else(action_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+    let was_open_mandatory = before.mandatory && before.status.is_open();
+    let opens_mandatory = data.mandatory.unwrap_or(before.mandatory) && data.status.unwrap_or(before.status).is_open();
+    if opens_mandatory && !was_open_mandatory && risk_status == Some(RiskStatus::Monitoring) {
+        return Err(risk_in_monitoring());
+    }
+
+    // Avancement, statut et caractère obligatoire absents : inchangés
+    sqlx::query(
+        r#"
+        UPDATE action_items
+        SET title = $1, description = $2, assignee_id = $3, due_date = $4,
+            status = COALESCE($6, status),
+            progress = CASE WHEN COALESCE($6, status) = 'Done' THEN 100 ELSE COALESCE($5, progress) END,
+            mandatory = COALESCE($7, mandatory),
+            completed_at = CASE WHEN COALESCE($6, status) = 'Done' THEN COALESCE(completed_at, now()) END,
+            version = version + 1, updated_at = now()
+        WHERE id = $8
+        "#
+    )
+    .bind(data.title.trim())
+    .bind(&data.description)
+    .bind(data.assignee_id)
+    .bind(data.due_date)
+    .bind(data.progress)
+    .bind(data.status)
+    .bind(data.mandatory)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let updated = fetch_action(&mut *tx, id).await?.ok_or_else(action_not_found)?;
+    audit::record(&mut *tx, actor, "action_item", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+#[put("/actions/{id}")]
+pub async fn update_action(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Edit>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    id: web::Path<Uuid>,
+    data: web::Json<NewActionItem>,
+) -> Result<HttpResponse, ApiError> {
+    data.validate(&rules)?;
+
+    let action = save_action(db_pool.get_ref(), *id, &data, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(action.version)).json(action))
+}
+
+async fn remove_action(
+    db_pool: &PgPool,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<(), ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = lock_action(&mut tx, id).await?.ok_or_else(action_not_found)?;
+    if !if_match.matches(before.version) {
+        return Err(ApiError::stale(&before, before.version));
+    }
+
+    sqlx::query("DELETE FROM action_items WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    audit::record(&mut *tx, actor, "action_item", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+#[delete("/actions/{id}")]
+pub async fn delete_action(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    id: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    remove_action(db_pool.get_ref(), *id, &if_match, &user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
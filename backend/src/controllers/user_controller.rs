@@ -0,0 +1,147 @@
+use actix_web::{get, patch, post, web, HttpResponse};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::auth::{hash_password, password_problem, Admin, Authorized, ChangeStatus, Edit};
+use crate::error::{ApiError, ErrorCode};
+use crate::models::user::{Role, User, UserSummary};
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+#[derive(Deserialize)]
+pub struct NewUser {
+    pub username: String,
+    pub password: String,
+    pub role: Option<Role>,
+}
+
+impl Validate for NewUser {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors.length("username", &self.username, 1, config.title_max_length);
+        if let Some(problem) = password_problem(self.username.trim(), &self.password) {
+            errors.add("password", problem);
+        }
+        errors.into_result()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct UpdateRolePayload {
+    pub role: Role,
+}
+
+#[get("/users")]
+pub async fn get_all_users(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Admin>,
+) -> Result<HttpResponse, ApiError> {
+    let users = sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY username")
+        .fetch_all(db_pool.get_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(users))
+}
+
+// Utilisateurs pouvant approuver un changement de statut
+#[get("/users/approvers")]
+pub async fn get_approvers(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<ChangeStatus>,
+) -> Result<HttpResponse, ApiError> {
+    let users = sqlx::query_as::<_, UserSummary>(
+        r#"
+        SELECT id, username, role FROM users
+        WHERE role IN ('RiskOwner', 'Admin')
+        ORDER BY username;
+        "#
+    )
+    .fetch_all(db_pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(users))
+}
+
+// Utilisateurs auxquels une action de traitement peut être assignée
+#[get("/users/assignees")]
+pub async fn get_assignees(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Edit>,
+) -> Result<HttpResponse, ApiError> {
+    let users = sqlx::query_as::<_, UserSummary>("SELECT id, username, role FROM users ORDER BY username")
+        .fetch_all(db_pool.get_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(users))
+}
+
+#[post("/users")]
+pub async fn create_user(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Admin>,
+    rules: web::Data<ValidationConfig>,
+    payload: web::Json<NewUser>,
+) -> Result<HttpResponse, ApiError> {
+    payload.validate(&rules)?;
+
+    let password_hash = hash_password(&payload.password).map_err(ApiError::internal)?;
+
+    let result = sqlx::query_as::<_, User>(
+        r#"
+        INSERT INTO users (username, password_hash, role)
+        VALUES ($1, $2, $3)
+        RETURNING *;
+        "#
+    )
+    .bind(payload.username.trim())
+    .bind(&password_hash)
+    .bind(payload.role.unwrap_or(Role::Viewer))
+    .fetch_one(db_pool.get_ref())
+    .await;
+
+    match result {
+        Ok(user) => Ok(HttpResponse::Created().json(user)),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            Err(ApiError::new(ErrorCode::UniqueViolation, "Nom d'utilisateur déjà utilisé"))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[patch("/users/{id}/role")]
+pub async fn update_user_role(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Admin>,
+    path: web::Path<Uuid>,
+    payload: web::Json<UpdateRolePayload>,
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+    let mut tx = db_pool.begin().await?;
+
+    // Comptes Admin verrouillés : deux rétrogradations simultanées ne peuvent retirer le dernier
+    let admins = sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE role = $1 FOR UPDATE")
+        .bind(Role::Admin)
+        .fetch_all(&mut *tx)
+        .await?;
+    if payload.role != Role::Admin && admins == [id] {
+        return Err(ApiError::conflict("Impossible de retirer le rôle Admin au dernier administrateur"));
+    }
+
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        UPDATE users
+        SET role = $1,
+            updated_at = now()
+        WHERE id = $2
+        RETURNING *;
+        "#
+    )
+    .bind(payload.role)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Utilisateur non trouvé"))?;
+
+    tx.commit().await?;
+    Ok(HttpResponse::Ok().json(user))
+}
@@ -0,0 +1,13 @@
+use actix_web::{get, web, HttpResponse};
+
+use crate::auth::{Authorized, Read};
+use crate::validation::ValidationConfig;
+
+// Règles appliquées par l'API, reprises par les formulaires du frontend
+#[get("/validation/rules")]
+pub async fn get_validation_rules(
+    _user: Authorized<Read>,
+    rules: web::Data<ValidationConfig>,
+) -> HttpResponse {
+    HttpResponse::Ok().json(rules.get_ref())
+}
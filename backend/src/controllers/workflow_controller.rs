@@ -0,0 +1,287 @@
+use actix_web::{delete, get, post, put, web, HttpResponse};
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::audit;
+use crate::auth::{Admin, AuthenticatedUser, Authorized, Read};
+use crate::concurrency::{etag, IfMatch};
+use crate::error::ApiError;
+use crate::models::audit::AuditAction;
+use crate::models::workflow::{NewWorkflow, Workflow, WorkflowDefinition, WorkflowTransition};
+use crate::validation::{Validate, ValidationConfig};
+
+// Workflows (tous, ou celui d'identifiant `id`) avec leurs transitions
+async fn load_definitions(
+    conn: &mut PgConnection,
+    id: Option<Uuid>,
+) -> Result<Vec<WorkflowDefinition>, sqlx::Error> {
+    let workflows = sqlx::query_as::<_, Workflow>(
+        r#"
+        SELECT * FROM workflows
+        WHERE $1::uuid IS NULL OR id = $1
+        ORDER BY business_unit NULLS FIRST, name;
+        "#
+    )
+    .bind(id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let ids: Vec<Uuid> = workflows.iter().map(|w| w.id).collect();
+    let mut transitions = sqlx::query_as::<_, WorkflowTransition>(
+        r#"
+        SELECT * FROM workflow_transitions
+        WHERE workflow_id = ANY($1)
+        ORDER BY from_status, to_status;
+        "#
+    )
+    .bind(&ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(workflows
+        .into_iter()
+        .map(|workflow| {
+            let (own, rest) = transitions.drain(..).partition(|t| t.workflow_id == workflow.id);
+            transitions = rest;
+            WorkflowDefinition { workflow, transitions: own }
+        })
+        .collect())
+}
+
+async fn load_definition(conn: &mut PgConnection, id: Uuid) -> Result<Option<WorkflowDefinition>, sqlx::Error> {
+    Ok(load_definitions(conn, Some(id)).await?.pop())
+}
+
+// Verrouille le workflow jusqu'à la fin de la transaction et renvoie sa définition
+async fn lock_definition(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+) -> Result<WorkflowDefinition, ApiError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM workflows WHERE id = $1 FOR UPDATE")
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(workflow_not_found)?;
+    load_definition(tx, id).await?.ok_or_else(workflow_not_found)
+}
+
+fn workflow_not_found() -> ApiError {
+    ApiError::not_found("Workflow non trouvé")
+}
+
+// Un risque sans workflow d'unité métier suit le workflow par défaut : il doit en rester un actif,
+// verrouillé jusqu'à la fin de la transaction pour qu'une écriture concurrente ne le désactive pas
+async fn ensure_active_default(tx: &mut Transaction<'_, Postgres>) -> Result<(), ApiError> {
+    sqlx::query_scalar::<_, Uuid>("SELECT id FROM workflows WHERE is_active AND business_unit IS NULL FOR UPDATE")
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or_else(|| ApiError::conflict("Un workflow par défaut (sans unité métier) doit rester actif"))?;
+    Ok(())
+}
+
+// Enregistre la définition complète d'un workflow ; l'activer désactive l'ancien workflow de la même unité
+async fn apply_definition(
+    tx: &mut Transaction<'_, Postgres>,
+    id: Uuid,
+    data: &NewWorkflow,
+) -> Result<(), ApiError> {
+    if data.is_active {
+        sqlx::query(
+            r#"
+            UPDATE workflows
+            SET is_active = false, version = version + 1, updated_at = now()
+            WHERE is_active AND business_unit IS NOT DISTINCT FROM $1 AND id <> $2;
+            "#
+        )
+        .bind(&data.business_unit)
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE workflows
+        SET name = $1, business_unit = $2, states = $3, is_active = $4, updated_at = now()
+        WHERE id = $5;
+        "#
+    )
+    .bind(data.name.trim())
+    .bind(&data.business_unit)
+    .bind(&data.states)
+    .bind(data.is_active)
+    .bind(id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+            ApiError::conflict("Un autre workflow actif existe déjà pour cette unité métier")
+        }
+        e => e.into(),
+    })?;
+
+    sqlx::query("DELETE FROM workflow_transitions WHERE workflow_id = $1")
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+    for transition in &data.transitions {
+        sqlx::query(
+            r#"
+            INSERT INTO workflow_transitions (workflow_id, from_status, to_status, required_fields)
+            VALUES ($1, $2, $3, $4);
+            "#
+        )
+        .bind(id)
+        .bind(transition.from_status)
+        .bind(transition.to_status)
+        .bind(&transition.required_fields)
+        .execute(&mut **tx)
+        .await?;
+    }
+
+    ensure_active_default(tx).await
+}
+
+async fn insert_workflow(
+    db_pool: &PgPool,
+    data: &NewWorkflow,
+    actor: &AuthenticatedUser,
+) -> Result<WorkflowDefinition, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let id = sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO workflows (name, business_unit, states) VALUES ($1, $2, $3) RETURNING id"
+    )
+    .bind(data.name.trim())
+    .bind(&data.business_unit)
+    .bind(&data.states)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    apply_definition(&mut tx, id, data).await?;
+
+    let created = load_definition(&mut tx, id).await?.ok_or(sqlx::Error::RowNotFound)?;
+    audit::record(&mut *tx, actor, "workflow", id, AuditAction::Create, None, Some(&created)).await?;
+
+    tx.commit().await?;
+    Ok(created)
+}
+
+async fn save_workflow(
+    db_pool: &PgPool,
+    id: Uuid,
+    data: &NewWorkflow,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<WorkflowDefinition, ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = lock_definition(&mut tx, id).await?;
+    if !if_match.matches(before.workflow.version) {
+        return Err(ApiError::stale(&before, before.workflow.version));
+    }
+
+    apply_definition(&mut tx, id, data).await?;
+    sqlx::query("UPDATE workflows SET version = version + 1 WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    let updated = load_definition(&mut tx, id).await?.ok_or(sqlx::Error::RowNotFound)?;
+    audit::record(&mut *tx, actor, "workflow", id, AuditAction::Update, Some(&before), Some(&updated)).await?;
+
+    tx.commit().await?;
+    Ok(updated)
+}
+
+async fn remove_workflow(
+    db_pool: &PgPool,
+    id: Uuid,
+    if_match: &IfMatch,
+    actor: &AuthenticatedUser,
+) -> Result<(), ApiError> {
+    let mut tx = db_pool.begin().await?;
+
+    let before = lock_definition(&mut tx, id).await?;
+    if !if_match.matches(before.workflow.version) {
+        return Err(ApiError::stale(&before, before.workflow.version));
+    }
+    // Un risque doit toujours disposer d'un workflow applicable
+    if before.workflow.is_active {
+        return Err(ApiError::conflict("Impossible de supprimer un workflow actif"));
+    }
+
+    sqlx::query("DELETE FROM workflows WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+    audit::record(&mut *tx, actor, "workflow", id, AuditAction::Delete, Some(&before), None).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+async fn fetch_definition(db_pool: &PgPool, id: Uuid) -> Result<WorkflowDefinition, ApiError> {
+    let mut conn = db_pool.acquire().await?;
+    load_definition(&mut conn, id).await?.ok_or_else(workflow_not_found)
+}
+
+#[get("/workflows")]
+pub async fn get_all_workflows(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+) -> Result<HttpResponse, ApiError> {
+    let mut conn = db_pool.acquire().await?;
+    let workflows = load_definitions(&mut conn, None).await?;
+    Ok(HttpResponse::Ok().json(workflows))
+}
+
+#[get("/workflows/{id}")]
+pub async fn get_workflow_by_id(
+    db_pool: web::Data<PgPool>,
+    _user: Authorized<Read>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let workflow = fetch_definition(db_pool.get_ref(), path.into_inner()).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(workflow.workflow.version)).json(workflow))
+}
+
+#[post("/workflows")]
+pub async fn create_workflow(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    rules: web::Data<ValidationConfig>,
+    payload: web::Json<NewWorkflow>,
+) -> Result<HttpResponse, ApiError> {
+    payload.validate(&rules)?;
+
+    let workflow = insert_workflow(db_pool.get_ref(), &payload, &user).await?;
+    Ok(HttpResponse::Created().insert_header(etag(workflow.workflow.version)).json(workflow))
+}
+
+#[put("/workflows/{id}")]
+pub async fn update_workflow(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    rules: web::Data<ValidationConfig>,
+    path: web::Path<Uuid>,
+    payload: web::Json<NewWorkflow>,
+) -> Result<HttpResponse, ApiError> {
+    payload.validate(&rules)?;
+
+    let workflow = save_workflow(db_pool.get_ref(), path.into_inner(), &payload, &if_match, &user).await?;
+    Ok(HttpResponse::Ok().insert_header(etag(workflow.workflow.version)).json(workflow))
+}
+
+#[delete("/workflows/{id}")]
+pub async fn delete_workflow(
+    db_pool: web::Data<PgPool>,
+    user: Authorized<Admin>,
+    if_match: IfMatch,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    remove_workflow(db_pool.get_ref(), path.into_inner(), &if_match, &user).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
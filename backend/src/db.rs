@@ -1,4 +1,5 @@
 use sqlx::{Pool, Postgres};
+use sqlx::migrate::MigrateError;
 use std::env;
 use dotenvy::dotenv;
 
@@ -7,4 +8,9 @@ pub async fn connect_db() -> Result<Pool<Postgres>, sqlx::Error> {
     let db_url = env::var("DATABASE_URL").expect("DATABASE_URL non trouvé dans .env");
     let pool = Pool::<Postgres>::connect(&db_url).await?;
     Ok(pool)
+}
+
+// Les fichiers de `migrations/` sont embarqués dans le binaire à la compilation
+pub async fn run_migrations(pool: &Pool<Postgres>) -> Result<(), MigrateError> {
+    sqlx::migrate!("./migrations").run(pool).await
 }
\ No newline at end of file
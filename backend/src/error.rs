@@ -0,0 +1,154 @@
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::error::ErrorKind;
+use std::fmt;
+
+use crate::concurrency::etag;
+
+// Codes d'erreur stables, exposés aux clients de l'API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    UniqueViolation,
+    ForeignKeyViolation,
+    InvalidTransition,
+    VersionMismatch,
+    PreconditionRequired,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    ValidationFailed,
+    InternalError,
+}
+
+impl ErrorCode {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ErrorCode::BadRequest | ErrorCode::InvalidTransition => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict | ErrorCode::UniqueViolation => StatusCode::CONFLICT,
+            ErrorCode::VersionMismatch => StatusCode::PRECONDITION_FAILED,
+            ErrorCode::PreconditionRequired => StatusCode::PRECONDITION_REQUIRED,
+            ErrorCode::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ErrorCode::ForeignKeyViolation | ErrorCode::ValidationFailed => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+// Erreur renvoyée par les handlers, sérialisée en { code, message, details }
+#[derive(Debug, Serialize)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub details: Option<Value>,
+    // Version courante de la ressource, renvoyée en ETag avec un 412
+    #[serde(skip)]
+    pub version: Option<i32>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        ApiError { code, message: message.into(), details: None, version: None }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::BadRequest, message)
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Unauthorized, message)
+    }
+
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Forbidden, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Conflict, message)
+    }
+
+    pub fn invalid_transition(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidTransition, message)
+    }
+
+    // La ressource a changé depuis la version connue du client : l'état courant est joint
+    pub fn stale<T: Serialize>(current: &T, version: i32) -> Self {
+        let mut error = Self::new(ErrorCode::VersionMismatch, "La ressource a été modifiée entre-temps")
+            .with_details(json!({ "current": current }));
+        error.version = Some(version);
+        error
+    }
+
+    // Erreur inattendue : détaillée dans les logs, générique pour le client
+    pub fn internal<E: fmt::Debug>(error: E) -> Self {
+        log::error!("Erreur interne : {:?}", error);
+        Self::new(ErrorCode::InternalError, "Erreur interne du serveur")
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} : {}", self.code, self.message)
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.code.status()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        if let Some(version) = self.version {
+            response.insert_header(etag(version));
+        }
+        response.json(self)
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => ApiError::not_found("Ressource introuvable"),
+            sqlx::Error::Database(db_error) => {
+                let details = db_error.constraint().map(|constraint| json!({ "constraint": constraint }));
+                let api_error = match db_error.kind() {
+                    ErrorKind::UniqueViolation => {
+                        ApiError::new(ErrorCode::UniqueViolation, "Une ressource identique existe déjà")
+                    }
+                    ErrorKind::ForeignKeyViolation => {
+                        ApiError::new(ErrorCode::ForeignKeyViolation, "Une ressource référencée est introuvable ou encore utilisée")
+                    }
+                    ErrorKind::NotNullViolation | ErrorKind::CheckViolation => {
+                        ApiError::bad_request("Valeur invalide pour un champ")
+                    }
+                    _ => return ApiError::internal(error),
+                };
+                match details {
+                    Some(details) => api_error.with_details(details),
+                    None => api_error,
+                }
+            }
+            _ => ApiError::internal(error),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+pub mod db;
+pub mod auth;
+pub mod audit;
+pub mod concurrency;
+pub mod error;
+pub mod likelihood;
+pub mod listing;
+pub mod review;
+pub mod scoring;
+pub mod workflow;
+pub mod validation;
+pub mod controllers;
+pub mod models;
+pub mod storage;
+
+use controllers::health_controller::health_check;
+
+use controllers::risk_controller::get_all_risks;
+use controllers::risk_controller::{create_risk, update_risk, delete_risk, update_risk_status, get_risk_history, create_evaluation, get_evaluation, get_evaluations, get_latest_evaluation, get_critical_risks, get_overdue_risks, review_risk, get_risk_by_id, get_probability_suggestion};
+use controllers::incident_handler::{get_all_incidents, create_incident, get_incident_by_id, update_incident, delete_incident, update_incident_status, get_incident_history}; // Importation de delete_incident
+use controllers::attachment_controller::{upload_risk_attachment, get_risk_attachments, upload_incident_attachment, get_incident_attachments, download_attachment, delete_attachment};
+use controllers::auth_controller::{login, current_user};
+use controllers::user_controller::{get_all_users, get_approvers, get_assignees, create_user, update_user_role};
+use controllers::audit_controller::get_audit_log;
+use controllers::workflow_controller::{get_all_workflows, get_workflow_by_id, create_workflow, update_workflow, delete_workflow};
+use controllers::validation_controller::get_validation_rules;
+use controllers::search_controller::search;
+use controllers::scoring_controller::{get_scoring_config, get_risk_levels, update_risk_levels};
+use controllers::treatment_controller::{get_treatment_plans, create_treatment_plan, get_treatment_plan, update_treatment_plan, delete_treatment_plan, get_all_actions, create_action, get_action, update_action, delete_action};
+use controllers::appetite_controller::{get_all_appetites, create_appetite, get_appetite_by_id, update_appetite, delete_appetite, get_out_of_tolerance_risks, get_appetite_breaches};
+use controllers::control_controller::{get_all_controls, create_control, get_control_by_id, update_control, delete_control, get_risk_controls, attach_control, detach_control};
+use auth::require_auth;
+use error::{ApiError, ErrorCode};
+use actix_web::error::JsonPayloadError;
+use actix_web::middleware::from_fn;
+use actix_web::web;
+use serde_json::error::Category;
+
+// Routes de l'API, partagées par le serveur et les tests d'intégration
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    // Erreurs d'extraction (corps JSON, chemin, paramètres) au même format que celles des handlers
+    cfg.app_data(web::JsonConfig::default().error_handler(|err, _| match err {
+        // JSON bien formé mais valeur incompatible (énumération inconnue, champ manquant...) : 422
+        JsonPayloadError::Deserialize(e) if e.classify() == Category::Data => {
+            ApiError::new(ErrorCode::ValidationFailed, format!("Valeur invalide : {}", e)).into()
+        }
+        err => ApiError::bad_request(format!("Corps de requête invalide : {}", err)).into(),
+    }))
+    .app_data(web::PathConfig::default().error_handler(|err, _| {
+        ApiError::not_found(format!("Ressource introuvable : {}", err)).into()
+    }))
+    .app_data(web::QueryConfig::default().error_handler(|err, _| {
+        ApiError::bad_request(format!("Paramètres invalides : {}", err)).into()
+    }));
+
+    cfg.service(health_check)
+        .service(login)
+        // Routes protégées : jeton JWT obligatoire
+        .service(
+            web::scope("")
+                .wrap(from_fn(require_auth))
+                .service(current_user)
+                .service(get_all_users)
+                .service(get_approvers)
+                .service(get_assignees)
+                .service(create_user)
+                .service(update_user_role)
+                .service(get_all_risks)
+                .service(create_risk)
+                .service(update_risk)
+                .service(delete_risk)
+                .service(update_risk_status)
+                .service(get_risk_history)
+                .service(create_evaluation)
+                .service(get_evaluation)
+                .service(get_evaluations)
+                .service(get_latest_evaluation)
+                .service(get_critical_risks)
+                .service(get_overdue_risks)
+                .service(review_risk)
+                .service(get_out_of_tolerance_risks)
+                .service(get_probability_suggestion)
+                .service(create_incident) // Ajout de create_incident
+                .service(get_all_incidents) // Ajout de get_all_incidents
+                .service(get_incident_by_id) // Ajout de get_incident_by_id
+                .service(update_incident) // Ajout de update_incident
+                .service(delete_incident) // Ajout de delete_incident
+                .service(update_incident_status)
+                .service(get_incident_history)
+                .service(get_risk_by_id)
+                .service(upload_risk_attachment)
+                .service(get_risk_attachments)
+                .service(upload_incident_attachment)
+                .service(get_incident_attachments)
+                .service(download_attachment)
+                .service(delete_attachment)
+                .service(get_audit_log)
+                .service(get_all_workflows)
+                .service(get_workflow_by_id)
+                .service(create_workflow)
+                .service(update_workflow)
+                .service(delete_workflow)
+                .service(get_validation_rules)
+                .service(search)
+                .service(get_scoring_config)
+                .service(get_risk_levels)
+                .service(update_risk_levels)
+                .service(get_all_controls)
+                .service(create_control)
+                .service(get_control_by_id)
+                .service(update_control)
+                .service(delete_control)
+                .service(get_risk_controls)
+                .service(attach_control)
+                .service(detach_control)
+                .service(get_treatment_plans)
+                .service(create_treatment_plan)
+                .service(get_treatment_plan)
+                .service(update_treatment_plan)
+                .service(delete_treatment_plan)
+                .service(get_all_actions)
+                .service(create_action)
+                .service(get_action)
+                .service(update_action)
+                .service(delete_action)
+                .service(get_all_appetites)
+                .service(create_appetite)
+                .service(get_appetite_by_id)
+                .service(update_appetite)
+                .service(delete_appetite)
+                .service(get_appetite_breaches)
+        );
+}
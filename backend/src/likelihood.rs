@@ -0,0 +1,137 @@
+use serde::Serialize;
+use std::env;
+use uuid::Uuid;
+
+use crate::models::incident::IncidentSeverity;
+use crate::validation::ScaleRange;
+
+// Bornes (incluses) de la fenêtre d'observation des incidents, en jours
+pub const WINDOW_DAYS: ScaleRange = ScaleRange { min: 1, max: 3650 };
+
+// Fenêtre d'observation appliquée quand `window_days` n'est pas précisé
+#[derive(Clone)]
+pub struct LikelihoodConfig {
+    pub window_days: i32,
+}
+
+impl LikelihoodConfig {
+    pub fn from_env() -> Self {
+        LikelihoodConfig { window_days: window_days(env::var("LIKELIHOOD_WINDOW_DAYS").ok().as_deref()) }
+    }
+}
+
+// Fenêtre configurée, 365 jours sans configuration ; une valeur hors de `WINDOW_DAYS` empêche le démarrage
+// plutôt que de donner une fenêtre vide ou inversée
+fn window_days(value: Option<&str>) -> i32 {
+    let Some(value) = value else { return 365 };
+    value
+        .trim()
+        .parse()
+        .ok()
+        .filter(|days| (WINDOW_DAYS.min..=WINDOW_DAYS.max).contains(days))
+        .unwrap_or_else(|| {
+            panic!(
+                "LIKELIHOOD_WINDOW_DAYS doit être un nombre de jours entre {} et {} (valeur : « {} »)",
+                WINDOW_DAYS.min, WINDOW_DAYS.max, value
+            )
+        })
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SeverityCount {
+    pub severity: IncidentSeverity,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProbabilitySuggestion {
+    pub risk_id: Uuid,
+    pub current_probability: i32,
+    pub suggested_probability: i32,
+    pub window_days: i32,
+    pub incident_count: i64,
+    // Incidents pondérés par leur gravité, ramenés à une année
+    pub annual_frequency: f64,
+    pub incidents_by_severity: Vec<SeverityCount>,
+}
+
+// Un cran au-dessus du minimum dès un incident faible par an (ou équivalent pondéré),
+// puis un cran de plus à chaque doublement de la fréquence
+pub fn suggest_probability(annual_frequency: f64, scale: ScaleRange) -> i32 {
+    if annual_frequency < 1.0 {
+        return scale.min;
+    }
+    let steps = annual_frequency.log2().floor() as i32 + 1;
+    (scale.min + steps).min(scale.max)
+}
+
+pub fn suggest(
+    risk_id: Uuid,
+    current_probability: i32,
+    window_days: i32,
+    incidents_by_severity: Vec<SeverityCount>,
+    scale: ScaleRange,
+) -> ProbabilitySuggestion {
+    let incident_count = incidents_by_severity.iter().map(|c| c.count).sum();
+    let weighted: f64 = incidents_by_severity
+        .iter()
+        .map(|c| c.severity.weight() * c.count as f64)
+        .sum();
+    let annual_frequency = weighted * 365.0 / window_days as f64;
+
+    ProbabilitySuggestion {
+        risk_id,
+        current_probability,
+        suggested_probability: suggest_probability(annual_frequency, scale),
+        window_days,
+        incident_count,
+        annual_frequency,
+        incidents_by_severity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCALE: ScaleRange = ScaleRange { min: 1, max: 5 };
+
+    #[test]
+    fn probability_rises_one_step_per_doubling() {
+        assert_eq!(suggest_probability(0.0, SCALE), 1);
+        assert_eq!(suggest_probability(0.99, SCALE), 1);
+        assert_eq!(suggest_probability(1.0, SCALE), 2);
+        assert_eq!(suggest_probability(1.99, SCALE), 2);
+        assert_eq!(suggest_probability(2.0, SCALE), 3);
+        assert_eq!(suggest_probability(4.0, SCALE), 4);
+        assert_eq!(suggest_probability(8.0, SCALE), 5);
+        // Plafonnée au maximum de l'échelle
+        assert_eq!(suggest_probability(1000.0, SCALE), 5);
+        assert_eq!(suggest_probability(3.0, ScaleRange { min: 0, max: 10 }), 2);
+    }
+
+    #[test]
+    fn window_days_defaults_and_accepts_its_range() {
+        assert_eq!(window_days(None), 365);
+        assert_eq!(window_days(Some(" 90 ")), 90);
+        assert_eq!(window_days(Some("3650")), 3650);
+    }
+
+    #[test]
+    #[should_panic(expected = "LIKELIHOOD_WINDOW_DAYS")]
+    fn zero_window_days_is_rejected() {
+        window_days(Some("0"));
+    }
+
+    #[test]
+    #[should_panic(expected = "LIKELIHOOD_WINDOW_DAYS")]
+    fn negative_window_days_is_rejected() {
+        window_days(Some("-30"));
+    }
+
+    #[test]
+    #[should_panic(expected = "LIKELIHOOD_WINDOW_DAYS")]
+    fn unreadable_window_days_is_rejected() {
+        window_days(Some("un an"));
+    }
+}
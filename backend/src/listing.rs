@@ -0,0 +1,109 @@
+use actix_web::HttpResponse;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::validation::{FieldErrors, ScaleRange};
+
+// En-tête portant le nombre total d'éléments correspondant aux filtres, toutes pages confondues
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+pub const DEFAULT_PER_PAGE: i32 = 50;
+pub const PER_PAGE: ScaleRange = ScaleRange { min: 1, max: 200 };
+
+// Page demandée (`page` à partir de 1) : limite et décalage SQL
+pub fn page(page: Option<i32>, per_page: Option<i32>, errors: &mut FieldErrors) -> (i64, i64) {
+    let page = page.unwrap_or(1);
+    let per_page = per_page.unwrap_or(DEFAULT_PER_PAGE);
+    errors
+        .range("page", page, ScaleRange { min: 1, max: i32::MAX })
+        .range("per_page", per_page, PER_PAGE);
+
+    let per_page = i64::from(per_page.clamp(PER_PAGE.min, PER_PAGE.max));
+    (per_page, (i64::from(page.max(1)) - 1) * per_page)
+}
+
+// Clause ORDER BY pour `sort=colonne` (croissant) ou `sort=-colonne` (décroissant) ;
+// `columns` associe les noms acceptés à leur expression SQL
+pub fn order_by(
+    sort: Option<&str>,
+    default: &str,
+    columns: &[(&str, &str)],
+    id_column: &str,
+    errors: &mut FieldErrors,
+) -> String {
+    let sort = sort.map(str::trim).filter(|s| !s.is_empty()).unwrap_or(default);
+    let (name, direction) = match sort.strip_prefix('-') {
+        Some(name) => (name, "DESC"),
+        None => (sort, "ASC"),
+    };
+
+    match columns.iter().find(|(column, _)| *column == name) {
+        Some((_, expression)) => format!(" ORDER BY {expression} {direction} NULLS LAST, {id_column}"),
+        None => {
+            let allowed: Vec<&str> = columns.iter().map(|(column, _)| *column).collect();
+            errors.add("sort", format!("Tri inconnu, valeurs possibles : {}", allowed.join(", ")));
+            format!(" ORDER BY {id_column}")
+        }
+    }
+}
+
+// Liste de valeurs séparées par des virgules (`status=Identified,Assessed`)
+pub fn parse_list<T: DeserializeOwned>(field: &'static str, value: Option<&str>, errors: &mut FieldErrors) -> Vec<T> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .filter_map(|item| match serde_json::from_value(serde_json::Value::from(item)) {
+            Ok(parsed) => Some(parsed),
+            Err(_) => {
+                errors.add(field, format!("Valeur inconnue : {}", item));
+                None
+            }
+        })
+        .collect()
+}
+
+// Motif ILIKE « contient », caractères spéciaux échappés
+pub fn contains_pattern(text: &str) -> String {
+    let escaped = text.trim().replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+pub fn paginated<T: Serialize>(items: &[T], total: i64) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((TOTAL_COUNT_HEADER, total.to_string()))
+        .json(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[(&str, &str)] = &[("title", "lower(r.title)"), ("score", "r.score")];
+
+    fn order(sort: Option<&str>) -> (String, bool) {
+        let mut errors = FieldErrors::new();
+        let clause = order_by(sort, "-score", COLUMNS, "r.id", &mut errors);
+        (clause, errors.into_result().is_ok())
+    }
+
+    #[test]
+    fn known_columns_sort_both_ways_with_id_tiebreak() {
+        assert_eq!(order(Some("title")), (" ORDER BY lower(r.title) ASC NULLS LAST, r.id".to_string(), true));
+        assert_eq!(order(Some(" -title ")), (" ORDER BY lower(r.title) DESC NULLS LAST, r.id".to_string(), true));
+    }
+
+    #[test]
+    fn missing_sort_uses_the_default() {
+        let expected = (" ORDER BY r.score DESC NULLS LAST, r.id".to_string(), true);
+        assert_eq!(order(None), expected);
+        assert_eq!(order(Some("  ")), expected);
+    }
+
+    #[test]
+    fn unknown_column_is_rejected_without_reaching_sql() {
+        assert_eq!(order(Some("title; DROP TABLE risks")), (" ORDER BY r.id".to_string(), false));
+        assert_eq!(order(Some("-r.score")), (" ORDER BY r.id".to_string(), false));
+    }
+}
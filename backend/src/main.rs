@@ -1,69 +1,119 @@
-mod db;
-mod controllers;
-mod models;
-
-use actix_web::{get, put, App, HttpResponse, HttpServer, Responder};
+use actix_web::middleware::Logger;
+use actix_web::{App, HttpServer};
 use std::env;
 use dotenvy::dotenv;
-use controllers::health_controller::health_check;
-
-use controllers::risk_controller::get_all_risks;
-use controllers::risk_controller::{create_risk, update_risk, delete_risk, update_risk_status, get_risk_history, create_evaluation, get_evaluation, get_critical_risks, get_risk_by_id};
-use controllers::incident_handler::{get_all_incidents, create_incident, get_incident_by_id, update_incident, delete_incident}; // Importation de delete_incident
+use backend::{auth, configure_routes, db};
+use backend::storage::{AttachmentConfig, AttachmentStorage, LocalStorage};
+use std::sync::Arc;
+use backend::auth::JwtConfig;
+use backend::validation::ValidationConfig;
+use backend::likelihood::LikelihoodConfig;
+use backend::listing::TOTAL_COUNT_HEADER;
+use backend::scoring::ScoringService;
+use backend::review::{self, ReviewConfig};
 use actix_web::web;
 use actix_cors::Cors;
 use actix_web::http::header;
 
-#[get("/")]
-async fn hello() -> impl Responder {
-    HttpResponse::Ok().body("SentinelRisk Backend API 🚀")
-}
-
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
+    // Journalisation via `RUST_LOG` (niveau `info` par défaut)
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let migrate_only = env::args().any(|arg| arg == "--migrate-only");
     let port = env::var("PORT").unwrap_or_else(|_| "8080".to_string());
 
-    println!("🚀 Starting server at http://localhost:{port}");
-
     // Connexion DB
     let pool = match db::connect_db().await {
         Ok(p) => {
-            println!("✅ Connexion à PostgreSQL réussie");
+            log::info!("Connexion à PostgreSQL réussie");
             p
         }
         Err(e) => {
-            println!("❌ Erreur de connexion : {:?}", e);
-            return Ok(());
+            log::error!("Erreur de connexion : {:?}", e);
+            return Err(std::io::Error::other(e));
         }
     };
 
+    // Application des migrations embarquées
+    if let Err(e) = db::run_migrations(&pool).await {
+        log::error!("Erreur lors des migrations : {:?}", e);
+        return Err(std::io::Error::other(e));
+    }
+    log::info!("Migrations appliquées");
+
+    if migrate_only {
+        return Ok(());
+    }
+
+    if let Err(e) = auth::bootstrap_user(&pool).await {
+        log::error!("Erreur création de l'utilisateur initial : {:?}", e);
+        return Err(std::io::Error::other(e.to_string()));
+    }
+
+    // Stockage des pièces jointes
+    let attachment_config = AttachmentConfig::from_env();
+    let storage: Arc<dyn AttachmentStorage> = Arc::new(LocalStorage::new(&attachment_config.dir)?);
+    let storage = web::Data::from(storage);
+
+    let jwt_config = JwtConfig::from_env();
+    let validation_config = ValidationConfig::from_env();
+    let likelihood_config = LikelihoodConfig::from_env();
+    let scoring_service = ScoringService::from_env();
+
+    // Niveaux enregistrés en base (ceux de la configuration au premier démarrage)
+    if let Err(e) = scoring_service.load_levels(&pool).await {
+        log::error!("Erreur lors du chargement des niveaux de risque : {:?}", e);
+        return Err(std::io::Error::other(e));
+    }
+
+    // Niveaux des risques existants alignés sur les seuils enregistrés
+    let refreshed = match pool.acquire().await {
+        Ok(mut conn) => scoring_service.refresh_levels(&mut conn, &scoring_service.levels()).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = refreshed {
+        log::error!("Erreur lors du recalcul des niveaux de risque : {:?}", e);
+        return Err(std::io::Error::other(e));
+    }
+
+    // Signalement périodique des revues de risque en retard
+    review::spawn_overdue_check(pool.clone(), ReviewConfig::from_env());
+
+    // Origines autorisées (séparées par des virgules)
+    let allowed_origins: Vec<String> = env::var("CORS_ALLOWED_ORIGINS")
+        .unwrap_or_else(|_| "http://localhost:8080,http://127.0.0.1:8080".to_string())
+        .split(',')
+        .map(|origin| origin.trim().to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect();
+
+    log::info!("Starting server at http://localhost:{port}");
+
     HttpServer::new(move || {
+        let cors = allowed_origins
+            .iter()
+            .fold(Cors::default(), |cors, origin| cors.allowed_origin(origin))
+            .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE"])
+            .allowed_headers(vec![header::CONTENT_TYPE, header::AUTHORIZATION, header::IF_MATCH])
+            .expose_headers(vec![
+                header::CONTENT_DISPOSITION,
+                header::ETAG,
+                header::HeaderName::from_static(TOTAL_COUNT_HEADER),
+            ])
+            .max_age(3600);
+
         App::new()
-            .wrap(
-                Cors::default()
-                    .allow_any_origin()
-                    .allowed_methods(vec!["GET", "POST", "PUT", "DELETE"])
-                    .allowed_headers(vec![header::CONTENT_TYPE])
-                    .max_age(3600),
-            )
+            .wrap(cors)
+            .wrap(Logger::default())
             .app_data(web::Data::new(pool.clone()))
-            .service(health_check)
-            .service(get_all_risks)
-            .service(create_risk)
-            .service(update_risk)
-            .service(delete_risk)
-            .service(update_risk_status)
-            .service(get_risk_history)
-            .service(create_evaluation)
-            .service(get_evaluation)
-            .service(get_critical_risks)
-            .service(create_incident) // Ajout de create_incident
-            .service(get_all_incidents) // Ajout de get_all_incidents
-            .service(get_incident_by_id) // Ajout de get_incident_by_id
-            .service(update_incident) // Ajout de update_incident
-            .service(delete_incident) // Ajout de delete_incident
-            .service(get_risk_by_id)
+            .app_data(storage.clone())
+            .app_data(web::Data::new(attachment_config.clone()))
+            .app_data(web::Data::new(jwt_config.clone()))
+            .app_data(web::Data::new(validation_config.clone()))
+            .app_data(web::Data::new(likelihood_config.clone()))
+            .app_data(web::Data::new(scoring_service.clone()))
+            .configure(configure_routes)
     })
     .bind(("127.0.0.1", port.parse::<u16>().unwrap()))?
     .run()
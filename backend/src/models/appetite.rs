@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::scoring::ScoringMethod;
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+// Seuils d'une catégorie, sur le score inhérent : l'appétence est le niveau recherché,
+// la tolérance le niveau maximal acceptable
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RiskAppetite {
+    pub id: Uuid,
+    pub category: String,
+    // Seuils en impact × probabilité
+    pub appetite: i32,
+    pub tolerance: i32,
+    // Seuils des scores AMDEC et pondérés, d'échelles différentes ; absents, ces risques ne sont pas comparés
+    pub fmea_appetite: Option<i32>,
+    pub fmea_tolerance: Option<i32>,
+    pub weighted_appetite: Option<i32>,
+    pub weighted_tolerance: Option<i32>,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewRiskAppetite {
+    pub category: String,
+    pub appetite: i32,
+    pub tolerance: i32,
+    // Appétence et tolérance d'une méthode : renseignées ensemble ou pas du tout
+    pub fmea_appetite: Option<i32>,
+    pub fmea_tolerance: Option<i32>,
+    pub weighted_appetite: Option<i32>,
+    pub weighted_tolerance: Option<i32>,
+}
+
+impl Validate for NewRiskAppetite {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors.length("category", &self.category, 1, config.title_max_length);
+
+        for ((appetite_field, appetite), (tolerance_field, tolerance)) in [
+            (("appetite", Some(self.appetite)), ("tolerance", Some(self.tolerance))),
+            (("fmea_appetite", self.fmea_appetite), ("fmea_tolerance", self.fmea_tolerance)),
+            (("weighted_appetite", self.weighted_appetite), ("weighted_tolerance", self.weighted_tolerance)),
+        ] {
+            match (appetite, tolerance) {
+                (Some(appetite), Some(tolerance)) => {
+                    if appetite < 0 {
+                        errors.add(appetite_field, "Doit être positif ou nul");
+                    }
+                    if tolerance < appetite {
+                        errors.add(tolerance_field, "Doit être supérieure ou égale à l'appétence");
+                    }
+                }
+                (Some(_), None) => {
+                    errors.add(tolerance_field, "À renseigner avec l'appétence correspondante");
+                }
+                (None, Some(_)) => {
+                    errors.add(appetite_field, "À renseigner avec la tolérance correspondante");
+                }
+                (None, None) => {}
+            }
+        }
+        errors.into_result()
+    }
+}
+
+// Écriture du risque à l'origine d'un dépassement de tolérance
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum BreachTrigger {
+    Create,
+    Update,
+    Evaluation,
+}
+
+// Dépassement constaté, avec la catégorie, le score et les seuils du moment (ceux de la méthode du score)
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AppetiteBreach {
+    pub id: Uuid,
+    pub risk_id: Uuid,
+    pub risk_title: String,
+    pub category: String,
+    pub scoring_method: ScoringMethod,
+    pub score: i32,
+    pub appetite: i32,
+    pub tolerance: i32,
+    pub trigger: BreachTrigger,
+    pub triggered_by: Option<Uuid>,
+    pub triggered_by_username: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Risque dont le score dépasse actuellement la tolérance de sa catégorie
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ToleranceExcess {
+    pub risk_id: Uuid,
+    pub title: String,
+    pub status: String,
+    pub category: String,
+    pub scoring_method: ScoringMethod,
+    pub score: i32,
+    pub level: Option<String>,
+    // Seuils de la catégorie pour la méthode du score
+    pub appetite: i32,
+    pub tolerance: i32,
+    // Écart au-dessus de la tolérance, sur l'échelle de la méthode
+    pub excess: i32,
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy)]
+#[sqlx(type_name = "TEXT")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub action: AuditAction,
+    pub actor_id: Option<Uuid>,
+    pub actor_username: String,
+    // { "champ": { "before": ..., "after": ... } }
+    pub changes: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use std::fmt;
+
+use crate::error::ApiError;
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum ControlType {
+    Preventive,
+    Detective,
+    Corrective,
+    Directive,
+}
+
+impl ControlType {
+    pub const ALL: [ControlType; 4] = [
+        ControlType::Preventive,
+        ControlType::Detective,
+        ControlType::Corrective,
+        ControlType::Directive,
+    ];
+
+    // Les contrôles préventifs et directifs réduisent la probabilité, les autres l'impact
+    pub fn reduces_probability(&self) -> bool {
+        matches!(self, ControlType::Preventive | ControlType::Directive)
+    }
+}
+
+impl fmt::Display for ControlType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            ControlType::Preventive => "Preventive",
+            ControlType::Detective => "Detective",
+            ControlType::Corrective => "Corrective",
+            ControlType::Directive => "Directive",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum ControlFrequency {
+    Continuous,
+    Daily,
+    Weekly,
+    Monthly,
+    Quarterly,
+    Annually,
+    AdHoc,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum Effectiveness {
+    Effective,
+    PartiallyEffective,
+    Ineffective,
+    NotTested,
+}
+
+impl Effectiveness {
+    // Part de l'efficacité retenue : un contrôle non testé ne compte pas
+    pub fn factor(&self) -> f64 {
+        match self {
+            Effectiveness::Effective => 1.0,
+            Effectiveness::PartiallyEffective => 0.5,
+            Effectiveness::Ineffective | Effectiveness::NotTested => 0.0,
+        }
+    }
+}
+
+impl fmt::Display for Effectiveness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Effectiveness::Effective => "Effective",
+            Effectiveness::PartiallyEffective => "PartiallyEffective",
+            Effectiveness::Ineffective => "Ineffective",
+            Effectiveness::NotTested => "NotTested",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Control {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub control_type: ControlType,
+    pub owner: Option<String>,
+    pub frequency: Option<ControlFrequency>,
+    // Efficacité de conception (le contrôle couvre-t-il le risque ?) et de fonctionnement (est-il appliqué ?)
+    pub design_effectiveness: Effectiveness,
+    pub operating_effectiveness: Effectiveness,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    // Risques couverts (`risk_controls`)
+    pub risk_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewControl {
+    pub name: String,
+    pub description: Option<String>,
+    pub control_type: ControlType,
+    pub owner: Option<String>,
+    pub frequency: Option<ControlFrequency>,
+    // Absentes : non testé
+    pub design_effectiveness: Option<Effectiveness>,
+    pub operating_effectiveness: Option<Effectiveness>,
+    // Absent en modification : liens inchangés
+    pub risk_ids: Option<Vec<Uuid>>,
+}
+
+impl Validate for NewControl {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors.length("name", &self.name, config.title_min_length, config.title_max_length);
+        errors.into_result()
+    }
+}
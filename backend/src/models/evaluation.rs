@@ -3,14 +3,25 @@ use sqlx::FromRow;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::scoring::ScoreBasis;
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct RiskEvaluation {
     pub id: Uuid,
     pub risk_id: Uuid,
+    // Risque évalué : inhérent, résiduel ou cible
+    pub basis: ScoreBasis,
     pub severity: i32,
     pub likelihood: i32,
     pub detectability: i32,
+    // Produit gravité × vraisemblance × détectabilité
     pub score: i32,
+    // Score canonique du risque (pour la même base) juste après l'évaluation, selon sa méthode de notation
+    pub risk_score: Option<i32>,
+    pub notes: Option<String>,
+    pub evaluated_by: Option<Uuid>,
+    // Nom conservé si le compte est supprimé par la suite
+    pub evaluated_by_username: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -1,14 +1,126 @@
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use uuid::Uuid;
+use std::fmt;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum IncidentSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl IncidentSeverity {
+    pub const ALL: [IncidentSeverity; 4] = [
+        IncidentSeverity::Low,
+        IncidentSeverity::Medium,
+        IncidentSeverity::High,
+        IncidentSeverity::Critical,
+    ];
+
+    // Poids d'un incident dans le calcul de la probabilité suggérée d'un risque
+    pub fn weight(&self) -> f64 {
+        match self {
+            IncidentSeverity::Low => 1.0,
+            IncidentSeverity::Medium => 2.0,
+            IncidentSeverity::High => 3.0,
+            IncidentSeverity::Critical => 4.0,
+        }
+    }
+}
+
+impl fmt::Display for IncidentSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            IncidentSeverity::Low => "Low",
+            IncidentSeverity::Medium => "Medium",
+            IncidentSeverity::High => "High",
+            IncidentSeverity::Critical => "Critical",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum IncidentStatus {
+    New,
+    Triaged,
+    Investigating,
+    Contained,
+    Resolved,
+    Closed,
+}
+
+impl IncidentStatus {
+    pub const ALL: [IncidentStatus; 6] = [
+        IncidentStatus::New,
+        IncidentStatus::Triaged,
+        IncidentStatus::Investigating,
+        IncidentStatus::Contained,
+        IncidentStatus::Resolved,
+        IncidentStatus::Closed,
+    ];
+
+    // Cycle de vie : New → Triaged → Investigating → Contained → Resolved → Closed,
+    // un incident résolu pouvant être rouvert pour investigation
+    pub fn next_statuses(&self) -> &'static [IncidentStatus] {
+        match self {
+            IncidentStatus::New => &[IncidentStatus::Triaged],
+            IncidentStatus::Triaged => &[IncidentStatus::Investigating],
+            IncidentStatus::Investigating => &[IncidentStatus::Contained],
+            IncidentStatus::Contained => &[IncidentStatus::Resolved],
+            IncidentStatus::Resolved => &[IncidentStatus::Closed],
+            IncidentStatus::Closed => &[],
+        }
+    }
+
+    pub fn can_transition_to(&self, next: IncidentStatus) -> bool {
+        self.next_statuses().contains(&next)
+    }
+}
+
+impl fmt::Display for IncidentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            IncidentStatus::New => "New",
+            IncidentStatus::Triaged => "Triaged",
+            IncidentStatus::Investigating => "Investigating",
+            IncidentStatus::Contained => "Contained",
+            IncidentStatus::Resolved => "Resolved",
+            IncidentStatus::Closed => "Closed",
+        };
+        write!(f, "{}", text)
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Incident {
     pub id: Uuid,
     pub title: String,
     pub description: Option<String>,
-    pub severity: String,         // Exemple : "Faible", "Moyenne", "Critique"
-    pub status: String,           // Exemple : "Nouveau", "En cours", "Résolu"
+    pub severity: IncidentSeverity,
+    pub status: IncidentStatus,
+    // Risque principal, toujours présent parmi `risk_ids`
     pub related_risk_id: Option<Uuid>,
     pub created_at: NaiveDateTime,
+    pub version: i32,
+    // Tous les risques liés à l'incident (`incident_risks`)
+    pub risk_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct IncidentStatusHistory {
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub old_status: String,
+    pub new_status: String,
+    pub comment: Option<String>,
+    pub changed_by: Option<Uuid>,
+    pub changed_by_username: Option<String>,
+    pub changed_at: DateTime<Utc>,
 }
@@ -1,4 +1,11 @@
 pub mod risk;
 pub mod risk_history;
 pub mod evaluation;
-pub mod incident;
\ No newline at end of file
+pub mod incident;
+pub mod attachment;
+pub mod user;
+pub mod audit;
+pub mod workflow;
+pub mod control;
+pub mod treatment;
+pub mod appetite;
@@ -2,9 +2,14 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use sqlx::postgres::{PgHasArrayType, PgTypeInfo};
 use std::fmt;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy)]
+use crate::error::ApiError;
+use crate::scoring::ScoringMethod;
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq, Hash)]
 #[sqlx(type_name = "TEXT")]
 #[serde(rename_all = "PascalCase")]
 pub enum RiskStatus {
@@ -34,6 +39,27 @@ impl fmt::Display for RiskStatus {
     }
 }
 
+impl RiskStatus {
+    // Statut de tout nouveau risque
+    pub const INITIAL: RiskStatus = RiskStatus::Identified;
+
+    // Accepter ou transférer un risque doit toujours être justifié, quel que soit le workflow
+    pub fn requires_justification(&self) -> bool {
+        matches!(self, RiskStatus::Accepted | RiskStatus::Transferred)
+    }
+}
+
+// Les statuts sont stockés en TEXT : une liste de statuts est donc un TEXT[]
+impl PgHasArrayType for RiskStatus {
+    fn array_type_info() -> PgTypeInfo {
+        <String as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <String as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
 #[derive(Debug, FromRow)]
 pub struct DbRisk {
     pub id: Uuid,
@@ -44,11 +70,40 @@ pub struct DbRisk {
     pub status: RiskStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub scoring_method: ScoringMethod,
+    pub score: Option<i32>,
+    pub level: Option<String>,
+    pub residual_impact: Option<i32>,
+    pub residual_probability: Option<i32>,
+    pub residual_score: Option<i32>,
+    pub residual_level: Option<String>,
+    pub target_impact: Option<i32>,
+    pub target_probability: Option<i32>,
+    pub target_score: Option<i32>,
+    pub target_level: Option<String>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+    pub next_review_at: Option<DateTime<Utc>>,
+    pub review_overdue: bool,
     pub external_id: Option<String>,
     pub category: Option<String>,
     pub location: Option<String>,
     pub regulation: Option<String>,
     pub control_measure_id: Option<String>,
+    pub risk_map: Option<String>,
+    pub threat_map: Option<String>,
+    pub technology: Option<String>,
+    pub team: Option<String>,
+    pub business_unit: Option<String>,
+    pub owner: Option<String>,
+    pub owner_delegate: Option<String>,
+    pub source: Option<String>,
+    pub evaluation_method: Option<String>,
+    pub evaluation_notes: Option<String>,
+    pub additional_notes: Option<String>,
+    pub tags: Vec<String>,
+    pub assets: Vec<String>,
+    pub stakeholders: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -61,15 +116,94 @@ pub struct Risk {
     pub status: RiskStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub version: i32,
+    pub scoring_method: ScoringMethod,
+    // Score canonique, calculé par `ScoringService` selon `scoring_method`
+    pub score: Option<i32>,
+    // Niveau atteint par le score, selon les seuils de `ScoringService`
+    pub level: Option<String>,
+    // Risque résiduel, après mesures de maîtrise ; `impact`, `probability` et `score` portent sur le risque inhérent
+    pub residual_impact: Option<i32>,
+    pub residual_probability: Option<i32>,
+    pub residual_score: Option<i32>,
+    pub residual_level: Option<String>,
+    // Risque cible visé par le traitement
+    pub target_impact: Option<i32>,
+    pub target_probability: Option<i32>,
+    pub target_score: Option<i32>,
+    pub target_level: Option<String>,
+    // Revue périodique : fréquence selon le niveau, retard signalé par une tâche de fond
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+    pub next_review_at: Option<DateTime<Utc>>,
+    pub review_overdue: bool,
     pub external_id: Option<String>,
     pub category: Option<String>,
     pub location: Option<String>,
     pub regulation: Option<String>,
     pub control_measure_id: Option<String>,
+    pub risk_map: Option<String>,
+    pub threat_map: Option<String>,
+    pub technology: Option<String>,
+    pub team: Option<String>,
+    pub business_unit: Option<String>,
+    pub owner: Option<String>,
+    pub owner_delegate: Option<String>,
+    pub source: Option<String>,
+    pub evaluation_method: Option<String>,
+    pub evaluation_notes: Option<String>,
+    pub additional_notes: Option<String>,
+    pub tags: Vec<String>,
+    pub assets: Vec<String>,
+    pub stakeholders: Vec<String>,
+}
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub score: Option<i32>, // <-- Calculé manuellement
-
+impl From<DbRisk> for Risk {
+    fn from(r: DbRisk) -> Self {
+        Risk {
+            id: r.id,
+            title: r.title,
+            description: r.description,
+            impact: r.impact,
+            probability: r.probability,
+            status: r.status,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            version: r.version,
+            scoring_method: r.scoring_method,
+            score: r.score,
+            level: r.level,
+            residual_impact: r.residual_impact,
+            residual_probability: r.residual_probability,
+            residual_score: r.residual_score,
+            residual_level: r.residual_level,
+            target_impact: r.target_impact,
+            target_probability: r.target_probability,
+            target_score: r.target_score,
+            target_level: r.target_level,
+            last_reviewed_at: r.last_reviewed_at,
+            next_review_at: r.next_review_at,
+            review_overdue: r.review_overdue,
+            external_id: r.external_id,
+            category: r.category,
+            location: r.location,
+            regulation: r.regulation,
+            control_measure_id: r.control_measure_id,
+            risk_map: r.risk_map,
+            threat_map: r.threat_map,
+            technology: r.technology,
+            team: r.team,
+            business_unit: r.business_unit,
+            owner: r.owner,
+            owner_delegate: r.owner_delegate,
+            source: r.source,
+            evaluation_method: r.evaluation_method,
+            evaluation_notes: r.evaluation_notes,
+            additional_notes: r.additional_notes,
+            tags: r.tags,
+            assets: r.assets,
+            stakeholders: r.stakeholders,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -87,6 +221,7 @@ pub struct NewRisk {
     pub assets: Option<Vec<String>>,
     pub technology: Option<String>,
     pub team: Option<String>,
+    pub business_unit: Option<String>,
     pub stakeholders: Option<Vec<String>>,
     pub owner: Option<String>,
     pub owner_delegate: Option<String>,
@@ -94,8 +229,46 @@ pub struct NewRisk {
     pub evaluation_method: Option<String>,
     pub impact: i32,
     pub probability: i32,
+    // Risque résiduel et cible : impact et probabilité renseignés ensemble ou pas du tout
+    pub residual_impact: Option<i32>,
+    pub residual_probability: Option<i32>,
+    pub target_impact: Option<i32>,
+    pub target_probability: Option<i32>,
     pub evaluation_notes: Option<String>,
     pub additional_notes: Option<String>,
-    pub file_attachment: Option<String>,
+    // Création : seul le statut initial est admis, la suite passe par le workflow ; ignoré en modification
     pub status: Option<RiskStatus>,
+    // Absente : méthode par défaut à la création, méthode inchangée en modification
+    pub scoring_method: Option<ScoringMethod>,
+}
+
+impl Validate for NewRisk {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors
+            .length("title", &self.title, config.title_min_length, config.title_max_length)
+            .range("impact", self.impact, config.impact_scale)
+            .range("probability", self.probability, config.probability_scale);
+
+        for ((impact_field, impact), (probability_field, probability)) in [
+            (("residual_impact", self.residual_impact), ("residual_probability", self.residual_probability)),
+            (("target_impact", self.target_impact), ("target_probability", self.target_probability)),
+        ] {
+            match (impact, probability) {
+                (Some(impact), Some(probability)) => {
+                    errors
+                        .range(impact_field, impact, config.impact_scale)
+                        .range(probability_field, probability, config.probability_scale);
+                }
+                (Some(_), None) => {
+                    errors.add(probability_field, "À renseigner avec l'impact correspondant");
+                }
+                (None, Some(_)) => {
+                    errors.add(impact_field, "À renseigner avec la probabilité correspondante");
+                }
+                (None, None) => {}
+            }
+        }
+        errors.into_result()
+    }
 }
\ No newline at end of file
@@ -9,5 +9,11 @@ pub struct RiskStatusHistory {
     pub risk_id: Uuid,
     pub old_status: String,
     pub new_status: String,
+    pub justification: Option<String>,
+    pub changed_by: Option<Uuid>,
+    // Noms conservés si le compte est supprimé par la suite
+    pub changed_by_username: Option<String>,
+    pub approved_by: Option<Uuid>,
+    pub approved_by_username: Option<String>,
     pub changed_at: DateTime<Utc>,
 }
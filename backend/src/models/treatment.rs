@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+use uuid::Uuid;
+use std::fmt;
+
+use crate::error::ApiError;
+use crate::validation::{FieldErrors, ScaleRange, Validate, ValidationConfig};
+
+// Stratégie de traitement d'un risque (ISO 31000)
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum TreatmentStrategy {
+    Mitigate,
+    Avoid,
+    Transfer,
+    Accept,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum ActionStatus {
+    Open,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl ActionStatus {
+    // Action encore à réaliser
+    pub fn is_open(&self) -> bool {
+        matches!(self, ActionStatus::Open | ActionStatus::InProgress)
+    }
+
+    pub const ALL: [ActionStatus; 4] = [
+        ActionStatus::Open,
+        ActionStatus::InProgress,
+        ActionStatus::Done,
+        ActionStatus::Cancelled,
+    ];
+}
+
+impl fmt::Display for ActionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            ActionStatus::Open => "Open",
+            ActionStatus::InProgress => "InProgress",
+            ActionStatus::Done => "Done",
+            ActionStatus::Cancelled => "Cancelled",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// Avancement d'une action, en pourcentage
+pub const PROGRESS_RANGE: ScaleRange = ScaleRange { min: 0, max: 100 };
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TreatmentPlan {
+    pub id: Uuid,
+    pub risk_id: Uuid,
+    pub title: String,
+    pub strategy: TreatmentStrategy,
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ActionItem {
+    pub id: Uuid,
+    pub plan_id: Uuid,
+    // Risque traité par le plan de l'action
+    pub risk_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_id: Option<Uuid>,
+    pub assignee_username: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub progress: i32,
+    pub status: ActionStatus,
+    pub mandatory: bool,
+    pub completed_at: Option<DateTime<Utc>>,
+    // Échéance dépassée alors que l'action est encore ouverte
+    pub overdue: bool,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Plan de traitement et ses actions, tel qu'exposé par l'API
+#[derive(Debug, Serialize)]
+pub struct TreatmentPlanDetail {
+    #[serde(flatten)]
+    pub plan: TreatmentPlan,
+    pub actions: Vec<ActionItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewTreatmentPlan {
+    pub title: String,
+    pub strategy: TreatmentStrategy,
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub due_date: Option<NaiveDate>,
+}
+
+impl NewTreatmentPlan {
+    // Contrôles d'une modification : l'échéance n'est contrôlée que si elle change, pour qu'un plan
+    // en retard reste modifiable sans être replanifié
+    pub fn validate_update(&self, config: &ValidationConfig, stored_due_date: Option<NaiveDate>) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors.length("title", &self.title, config.title_min_length, config.title_max_length);
+        if self.owner.as_deref().is_none_or(|owner| owner.trim().is_empty()) {
+            errors.add("owner", "Le responsable du plan est obligatoire");
+        }
+        if self.due_date != stored_due_date && self.due_date.is_some_and(|due_date| due_date < Utc::now().date_naive()) {
+            errors.add("due_date", "L'échéance ne peut pas être dans le passé");
+        }
+        errors.into_result()
+    }
+}
+
+impl Validate for NewTreatmentPlan {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        self.validate_update(config, None)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewActionItem {
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_id: Option<Uuid>,
+    pub due_date: Option<NaiveDate>,
+    // Absents à la création : 0 %, `Open`, obligatoire ; en modification : inchangés
+    pub progress: Option<i32>,
+    pub status: Option<ActionStatus>,
+    pub mandatory: Option<bool>,
+}
+
+impl Validate for NewActionItem {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors.length("title", &self.title, config.title_min_length, config.title_max_length);
+        if let Some(progress) = self.progress {
+            errors.range("progress", progress, PROGRESS_RANGE);
+        }
+        errors.into_result()
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+// Rôles ordonnés du moins au plus privilégié
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum Role {
+    Viewer,
+    Analyst,
+    RiskOwner,
+    Admin,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Role::Viewer => "Viewer",
+            Role::Analyst => "Analyst",
+            Role::RiskOwner => "RiskOwner",
+            Role::Admin => "Admin",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct User {
+    pub id: Uuid,
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub role: Role,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Identité publique d'un utilisateur (ex. liste des approbateurs)
+#[derive(Debug, Serialize, FromRow)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub role: Role,
+}
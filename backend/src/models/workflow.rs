@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use crate::models::risk::RiskStatus;
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct Workflow {
+    pub id: Uuid,
+    pub name: String,
+    pub business_unit: Option<String>,
+    pub states: Vec<RiskStatus>,
+    pub is_active: bool,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct WorkflowTransition {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    pub from_status: RiskStatus,
+    pub to_status: RiskStatus,
+    // Champs à renseigner pour effectuer la transition (ex. "justification")
+    pub required_fields: Vec<String>,
+}
+
+// Workflow et ses transitions, tel qu'exposé par l'API d'administration
+#[derive(Debug, Serialize)]
+pub struct WorkflowDefinition {
+    #[serde(flatten)]
+    pub workflow: Workflow,
+    pub transitions: Vec<WorkflowTransition>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewTransition {
+    pub from_status: RiskStatus,
+    pub to_status: RiskStatus,
+    #[serde(default)]
+    pub required_fields: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NewWorkflow {
+    pub name: String,
+    pub business_unit: Option<String>,
+    pub states: Vec<RiskStatus>,
+    pub transitions: Vec<NewTransition>,
+    #[serde(default)]
+    pub is_active: bool,
+}
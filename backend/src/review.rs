@@ -0,0 +1,58 @@
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::scoring::REVIEW_EXEMPT_STATUSES;
+
+// Période de la tâche de fond qui signale les revues en retard
+#[derive(Clone)]
+pub struct ReviewConfig {
+    pub check_interval: Duration,
+}
+
+impl ReviewConfig {
+    pub fn from_env() -> Self {
+        let seconds = env::var("REVIEW_CHECK_INTERVAL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|seconds| *seconds > 0)
+            .unwrap_or(3600);
+        ReviewConfig { check_interval: Duration::from_secs(seconds) }
+    }
+}
+
+// Aligne `review_overdue` sur l'échéance de revue, en changeant la version des risques concernés ; renvoie le nombre de risques nouvellement en retard
+pub async fn flag_overdue_reviews(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let flagged = sqlx::query_scalar::<_, bool>(&format!(
+        r#"
+        WITH due AS (
+            SELECT id, COALESCE(next_review_at < now() AND status NOT IN {REVIEW_EXEMPT_STATUSES}, false) AS overdue
+            FROM risks
+        )
+        UPDATE risks r
+        SET review_overdue = due.overdue, version = r.version + 1, updated_at = now()
+        FROM due
+        WHERE r.id = due.id AND r.review_overdue <> due.overdue
+        RETURNING r.review_overdue
+        "#
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(flagged.into_iter().filter(|overdue| *overdue).count() as u64)
+}
+
+// Tâche de fond : vérifie les échéances au démarrage, puis à chaque période
+pub fn spawn_overdue_check(pool: PgPool, config: ReviewConfig) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.check_interval);
+        loop {
+            interval.tick().await;
+            match flag_overdue_reviews(&pool).await {
+                Ok(0) => {}
+                Ok(count) => log::info!("{} risque(s) en retard de revue", count),
+                Err(e) => log::error!("Erreur lors de la vérification des revues : {:?}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,745 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize, Serializer};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use std::env;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::control::{ControlType, Effectiveness};
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+// Méthode de calcul du score d'un risque, enregistrée sur chaque risque
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum ScoringMethod {
+    // impact × probabilité
+    ImpactProbability,
+    // AMDEC : gravité × vraisemblance × détectabilité de la dernière évaluation
+    Fmea,
+    // Somme pondérée de l'impact et de la probabilité
+    Weighted,
+}
+
+impl ScoringMethod {
+    pub const ALL: [ScoringMethod; 3] = [
+        ScoringMethod::ImpactProbability,
+        ScoringMethod::Fmea,
+        ScoringMethod::Weighted,
+    ];
+}
+
+impl fmt::Display for ScoringMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            ScoringMethod::ImpactProbability => "ImpactProbability",
+            ScoringMethod::Fmea => "Fmea",
+            ScoringMethod::Weighted => "Weighted",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// Risque noté (ISO 31000) : inhérent (avant mesures), résiduel (après mesures) ou cible
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "TEXT")]
+#[serde(rename_all = "PascalCase")]
+pub enum ScoreBasis {
+    Inherent,
+    Residual,
+    Target,
+}
+
+impl ScoreBasis {
+    pub const ALL: [ScoreBasis; 3] = [ScoreBasis::Inherent, ScoreBasis::Residual, ScoreBasis::Target];
+
+    // Préfixe des colonnes de `risks` : `impact`, `residual_impact`, `target_impact`...
+    pub fn column_prefix(self) -> &'static str {
+        match self {
+            ScoreBasis::Inherent => "",
+            ScoreBasis::Residual => "residual_",
+            ScoreBasis::Target => "target_",
+        }
+    }
+}
+
+impl fmt::Display for ScoreBasis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            ScoreBasis::Inherent => "Inherent",
+            ScoreBasis::Residual => "Residual",
+            ScoreBasis::Target => "Target",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// Données d'un risque utilisées par les méthodes de notation, pour une base donnée
+#[derive(Debug, sqlx::FromRow)]
+pub struct ScoreInputs {
+    // Absents tant que le risque résiduel ou cible n'est pas renseigné
+    pub impact: Option<i32>,
+    pub probability: Option<i32>,
+    pub scoring_method: ScoringMethod,
+    // Dernière évaluation AMDEC de la même base, si le risque en a une
+    pub severity: Option<i32>,
+    pub likelihood: Option<i32>,
+    pub detectability: Option<i32>,
+}
+
+// Niveau de risque : s'applique à partir de son seuil et jusqu'au seuil du niveau suivant ;
+// les scores des méthodes n'ayant pas la même échelle, chaque méthode a ses propres seuils
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RiskLevel {
+    pub name: String,
+    // Seuil en impact × probabilité
+    pub min_score: i32,
+    pub fmea_min_score: i32,
+    pub weighted_min_score: i32,
+    // Couleur d'affichage (CSS)
+    pub color: String,
+    // Délai entre deux revues d'un risque de ce niveau, en jours
+    pub review_days: i32,
+}
+
+impl RiskLevel {
+    fn new(name: &str, min_score: i32, color: &str, review_days: i32) -> Self {
+        RiskLevel {
+            name: name.to_string(),
+            min_score,
+            fmea_min_score: default_threshold(ScoringMethod::Fmea, name).unwrap_or(min_score),
+            weighted_min_score: default_threshold(ScoringMethod::Weighted, name).unwrap_or(min_score),
+            color: color.to_string(),
+            review_days,
+        }
+    }
+
+    // Seuil du niveau pour les scores calculés avec `method`
+    pub fn threshold(&self, method: ScoringMethod) -> i32 {
+        match method {
+            ScoringMethod::ImpactProbability => self.min_score,
+            ScoringMethod::Fmea => self.fmea_min_score,
+            ScoringMethod::Weighted => self.weighted_min_score,
+        }
+    }
+
+    fn set_threshold(&mut self, method: ScoringMethod, min_score: i32) {
+        match method {
+            ScoringMethod::ImpactProbability => self.min_score = min_score,
+            ScoringMethod::Fmea => self.fmea_min_score = min_score,
+            ScoringMethod::Weighted => self.weighted_min_score = min_score,
+        }
+    }
+
+    // Format `Nom:seuil:couleur[:jours]`, par exemple `Critical:15:#c62828:30`
+    fn parse(entry: &str, default_review_days: i32) -> Option<Self> {
+        let mut parts = entry.trim().splitn(4, ':');
+        let name = parts.next()?.trim();
+        let min_score = parts.next()?.trim().parse().ok()?;
+        let color = parts.next()?.trim();
+        let review_days = match parts.next() {
+            Some(days) => days.trim().parse().ok().filter(|days| *days > 0)?,
+            None => default_review_days,
+        };
+        (!name.is_empty() && !color.is_empty()).then(|| RiskLevel::new(name, min_score, color, review_days))
+    }
+}
+
+// Seuils adaptés au score impact × probabilité sur des échelles de 1 à 5
+fn default_levels() -> Vec<RiskLevel> {
+    vec![
+        RiskLevel::new("Low", 1, "#2e7d32", 365),
+        RiskLevel::new("Medium", 5, "#f9a825", 180),
+        RiskLevel::new("High", 10, "#ef6c00", 90),
+        RiskLevel::new("Critical", 15, "#c62828", 30),
+    ]
+}
+
+// Seuils par défaut des niveaux standards pour les autres méthodes : AMDEC (gravité × vraisemblance
+// × détectabilité sur des échelles de 1 à 10) et somme pondérée (impact + probabilité, poids unitaires)
+fn default_threshold(method: ScoringMethod, name: &str) -> Option<i32> {
+    let thresholds = match method {
+        ScoringMethod::ImpactProbability => return None,
+        ScoringMethod::Fmea => [1, 50, 100, 200],
+        ScoringMethod::Weighted => [1, 4, 6, 8],
+    };
+    let index = ["Low", "Medium", "High", "Critical"].iter().position(|level| level.eq_ignore_ascii_case(name))?;
+    Some(thresholds[index])
+}
+
+// Variable des seuils propres à une méthode, au format `Nom:seuil` ; `None` pour impact × probabilité,
+// dont les seuils sont ceux de `RISK_LEVELS`
+fn thresholds_var(method: ScoringMethod) -> Option<&'static str> {
+    match method {
+        ScoringMethod::ImpactProbability => None,
+        ScoringMethod::Fmea => Some("RISK_LEVELS_FMEA"),
+        ScoringMethod::Weighted => Some("RISK_LEVELS_WEIGHTED"),
+    }
+}
+
+// Applique les seuils `Nom:seuil` d'une méthode aux niveaux (triés) ; rien n'est appliqué si la liste
+// est mal formée, nomme un niveau inconnu ou ne donne pas des seuils croissants dans l'ordre des niveaux
+fn apply_thresholds(levels: &mut [RiskLevel], method: ScoringMethod, value: &str) -> bool {
+    let parsed = value
+        .split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (name, min_score) = entry.split_once(':')?;
+            let level = levels.iter().position(|level| level.name.eq_ignore_ascii_case(name.trim()))?;
+            Some((level, min_score.trim().parse::<i32>().ok()?))
+        })
+        .collect::<Option<Vec<_>>>();
+    let Some(parsed) = parsed else { return false };
+
+    let mut thresholds: Vec<i32> = levels.iter().map(|level| level.threshold(method)).collect();
+    for (level, min_score) in parsed {
+        thresholds[level] = min_score;
+    }
+    if !thresholds.is_sorted() {
+        return false;
+    }
+    for (level, min_score) in levels.iter_mut().zip(thresholds) {
+        level.set_threshold(method, min_score);
+    }
+    true
+}
+
+// Niveaux lus depuis `RISK_LEVELS` (entrées séparées par des virgules), triés par seuil croissant ;
+// une liste vide ou mal formée laisse les niveaux par défaut. Les seuils AMDEC et pondérés se
+// règlent avec `RISK_LEVELS_FMEA` et `RISK_LEVELS_WEIGHTED`
+fn levels_from_env(default_review_days: i32) -> Vec<RiskLevel> {
+    let parsed = env::var("RISK_LEVELS").ok().and_then(|value| {
+        value
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| RiskLevel::parse(entry, default_review_days))
+            .collect::<Option<Vec<_>>>()
+    });
+
+    let mut levels = match parsed {
+        Some(levels) if !levels.is_empty() => levels,
+        _ => default_levels(),
+    };
+    levels.sort_by_key(|level| level.min_score);
+
+    for method in ScoringMethod::ALL {
+        let Some(var) = thresholds_var(method) else { continue };
+        // Niveaux personnalisés dont les seuils par défaut ne se suivent plus : ceux d'impact × probabilité
+        if !levels.iter().map(|level| level.threshold(method)).is_sorted() {
+            for level in levels.iter_mut() {
+                level.set_threshold(method, level.min_score);
+            }
+        }
+        if let Ok(value) = env::var(var) {
+            apply_thresholds(&mut levels, method, &value);
+        }
+    }
+    levels
+}
+
+// Niveaux enregistrés en base (ligne unique), modifiables par un administrateur
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RiskLevelSettings {
+    pub id: Uuid,
+    pub levels: Json<Vec<RiskLevel>>,
+    pub version: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Corps de `PUT /scoring/levels` : liste complète des niveaux, du plus faible au plus élevé
+#[derive(Debug, Deserialize)]
+pub struct NewRiskLevels {
+    pub levels: Vec<RiskLevel>,
+}
+
+impl Validate for NewRiskLevels {
+    fn validate(&self, _config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        if self.levels.is_empty() {
+            errors.add("levels", "Au moins un niveau est requis");
+        }
+        for (index, level) in self.levels.iter().enumerate() {
+            if level.name.trim().is_empty() || level.color.trim().is_empty() {
+                errors.add("levels", format!("Niveau {} : nom et couleur obligatoires", index + 1));
+            }
+            if self.levels[..index].iter().any(|other| other.name.trim().eq_ignore_ascii_case(level.name.trim())) {
+                errors.add("levels", format!("Niveau {} : nom déjà utilisé", index + 1));
+            }
+            if level.review_days <= 0 {
+                errors.add("levels", format!("Niveau {} : délai de revue strictement positif attendu", index + 1));
+            }
+        }
+        // Seuils strictement croissants dans l'ordre des niveaux, pour chaque méthode
+        for method in ScoringMethod::ALL {
+            let thresholds: Vec<i32> = self.levels.iter().map(|level| level.threshold(method)).collect();
+            if thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+                errors.add("levels", format!("Seuils {} : strictement croissants attendus", method));
+            }
+        }
+        errors.into_result()
+    }
+}
+
+// Statuts des risques qui ne font plus l'objet de revues périodiques (liste SQL)
+pub const REVIEW_EXEMPT_STATUSES: &str = "('Closed', 'Rejected')";
+
+// Réduction apportée sur son axe par un contrôle efficace en conception comme en fonctionnement
+pub const CONTROL_MAX_REDUCTION: f64 = 0.5;
+
+// Contrôle lié à un risque, tel que pris en compte dans le risque résiduel
+#[derive(Debug, sqlx::FromRow)]
+pub struct ControlEffect {
+    pub control_type: ControlType,
+    pub design_effectiveness: Effectiveness,
+    pub operating_effectiveness: Effectiveness,
+}
+
+impl ControlEffect {
+    pub fn strength(&self) -> f64 {
+        self.design_effectiveness.factor() * self.operating_effectiveness.factor()
+    }
+}
+
+// Impact et probabilité résiduels : chaque contrôle réduit son axe de `CONTROL_MAX_REDUCTION × efficacité`,
+// les réductions se cumulant de façon multiplicative, sans descendre sous 1
+pub fn mitigate(impact: i32, probability: i32, controls: &[ControlEffect]) -> (i32, i32) {
+    let remaining = |on_probability: bool| -> f64 {
+        controls
+            .iter()
+            .filter(|c| c.control_type.reduces_probability() == on_probability)
+            .map(|c| 1.0 - CONTROL_MAX_REDUCTION * c.strength())
+            .product()
+    };
+    let apply = |value: i32, factor: f64| ((f64::from(value) * factor).round() as i32).clamp(value.min(1), value);
+    (apply(impact, remaining(false)), apply(probability, remaining(true)))
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Weights {
+    pub impact: f64,
+    pub probability: f64,
+}
+
+// Niveaux en vigueur et version de `risk_level_settings` dont ils proviennent (0 : configuration)
+#[derive(Debug)]
+struct LevelCache {
+    version: i32,
+    levels: Vec<RiskLevel>,
+}
+
+// Niveaux en vigueur, partagés par toutes les copies du service (un par worker)
+fn serialize_levels<S: Serializer>(cache: &Arc<RwLock<LevelCache>>, serializer: S) -> Result<S::Ok, S::Error> {
+    cache.read().unwrap_or_else(|e| e.into_inner()).levels.serialize(serializer)
+}
+
+// Service de notation : seul endroit où le score canonique d'un risque est calculé
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoringService {
+    // Méthode appliquée aux risques créés sans `scoring_method`
+    pub default_method: ScoringMethod,
+    pub weights: Weights,
+    // Niveaux triés par seuil croissant, pour chaque méthode ; enregistrés en base, voir `sync_levels`
+    #[serde(serialize_with = "serialize_levels")]
+    levels: Arc<RwLock<LevelCache>>,
+    // Délai entre deux revues d'un risque sans niveau, en jours
+    pub default_review_days: i32,
+}
+
+impl ScoringService {
+    pub fn from_env() -> Self {
+        let weight = |name: &str| env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(1.0);
+        let default_method = env::var("SCORING_DEFAULT_METHOD")
+            .ok()
+            .and_then(|v| serde_json::from_value(serde_json::Value::from(v)).ok())
+            .unwrap_or(ScoringMethod::ImpactProbability);
+
+        let default_review_days = env::var("REVIEW_DEFAULT_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|days| *days > 0)
+            .unwrap_or(365);
+
+        ScoringService {
+            default_method,
+            weights: Weights {
+                impact: weight("SCORING_WEIGHT_IMPACT"),
+                probability: weight("SCORING_WEIGHT_PROBABILITY"),
+            },
+            levels: Arc::new(RwLock::new(LevelCache { version: 0, levels: levels_from_env(default_review_days) })),
+            default_review_days,
+        }
+    }
+
+    // Copie des niveaux en vigueur
+    pub fn levels(&self) -> Vec<RiskLevel> {
+        self.levels.read().unwrap_or_else(|e| e.into_inner()).levels.clone()
+    }
+
+    pub fn set_levels(&self, settings: &RiskLevelSettings) {
+        *self.levels.write().unwrap_or_else(|e| e.into_inner()) =
+            LevelCache { version: settings.version, levels: settings.levels.0.clone() };
+    }
+
+    // Niveaux enregistrés en base ; au premier démarrage, ceux de la configuration (`RISK_LEVELS`...) y sont enregistrés
+    pub async fn stored_levels(&self, conn: &mut PgConnection) -> Result<RiskLevelSettings, sqlx::Error> {
+        sqlx::query("INSERT INTO risk_level_settings (levels) VALUES ($1) ON CONFLICT DO NOTHING")
+            .bind(Json(self.levels()))
+            .execute(&mut *conn)
+            .await?;
+        sqlx::query_as::<_, RiskLevelSettings>("SELECT * FROM risk_level_settings")
+            .fetch_one(conn)
+            .await
+    }
+
+    // Remplace les niveaux de la configuration par ceux enregistrés en base, à appeler au démarrage
+    pub async fn load_levels(&self, pool: &PgPool) -> Result<(), sqlx::Error> {
+        let settings = self.stored_levels(&mut *pool.acquire().await?).await?;
+        self.set_levels(&settings);
+        Ok(())
+    }
+
+    // Recharge les niveaux s'ils ont été modifiés depuis leur chargement, y compris par une autre instance ;
+    // à appeler avant de les utiliser
+    pub async fn sync_levels(&self, conn: &mut PgConnection) -> Result<(), sqlx::Error> {
+        let version = self.levels.read().unwrap_or_else(|e| e.into_inner()).version;
+        let changed = sqlx::query_as::<_, RiskLevelSettings>("SELECT * FROM risk_level_settings WHERE version <> $1")
+            .bind(version)
+            .fetch_optional(conn)
+            .await?;
+        if let Some(settings) = changed {
+            self.set_levels(&settings);
+        }
+        Ok(())
+    }
+
+    // Niveau le plus élevé dont le seuil (pour la méthode du score) est atteint ;
+    // aucun sous le premier seuil ou sans score
+    pub fn level(&self, method: ScoringMethod, score: Option<i32>) -> Option<RiskLevel> {
+        let score = score?;
+        self.levels().into_iter().rev().find(|level| level.threshold(method) <= score)
+    }
+
+    pub fn level_named(&self, name: &str) -> Option<RiskLevel> {
+        self.levels().into_iter().find(|level| level.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    // Délai de revue d'un risque selon son niveau (inhérent)
+    pub fn review_days(&self, level: Option<&str>) -> i32 {
+        level
+            .and_then(|name| self.level_named(name))
+            .map_or(self.default_review_days, |level| level.review_days)
+    }
+
+    // Niveau le plus élevé, seuil par défaut de `/risks/critical`
+    pub fn critical_level(&self) -> Option<RiskLevel> {
+        self.levels().pop()
+    }
+
+    // `None` quand la méthode manque de données (AMDEC sans évaluation, risque résiduel non renseigné)
+    pub fn score(&self, inputs: &ScoreInputs) -> Option<i32> {
+        match inputs.scoring_method {
+            ScoringMethod::ImpactProbability => Some(inputs.impact? * inputs.probability?),
+            ScoringMethod::Fmea => Some(inputs.severity? * inputs.likelihood? * inputs.detectability?),
+            ScoringMethod::Weighted => {
+                let weighted = self.weights.impact * f64::from(inputs.impact?)
+                    + self.weights.probability * f64::from(inputs.probability?);
+                Some(weighted.round() as i32)
+            }
+        }
+    }
+
+    // Recalcule et enregistre les scores inhérent, résiduel et cible du risque,
+    // à appeler après toute modification de ses données
+    pub async fn refresh(&self, tx: &mut Transaction<'_, Postgres>, risk_id: Uuid) -> Result<(), sqlx::Error> {
+        for basis in ScoreBasis::ALL {
+            self.refresh_basis(tx, risk_id, basis).await?;
+        }
+        Ok(())
+    }
+
+    // Recalcule et enregistre le score et le niveau du risque pour une seule base
+    pub async fn refresh_basis(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        risk_id: Uuid,
+        basis: ScoreBasis,
+    ) -> Result<Option<i32>, sqlx::Error> {
+        self.sync_levels(tx).await?;
+        let prefix = basis.column_prefix();
+        let mut inputs = sqlx::query_as::<_, ScoreInputs>(&format!(
+            r#"
+            SELECT r.{prefix}impact AS impact, r.{prefix}probability AS probability, r.scoring_method,
+                e.severity, e.likelihood, e.detectability
+            FROM risks r
+            LEFT JOIN LATERAL (
+                SELECT severity, likelihood, detectability FROM risk_evaluation
+                WHERE risk_id = r.id AND basis = $2
+                ORDER BY created_at DESC
+                LIMIT 1
+            ) e ON TRUE
+            WHERE r.id = $1
+            "#
+        ))
+        .bind(risk_id)
+        .bind(basis)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        // Résiduel non saisi : déduit du risque inhérent et des contrôles liés
+        if basis == ScoreBasis::Residual
+            && inputs.impact.is_none()
+            && inputs.probability.is_none()
+            && let Some((impact, probability)) = self.residual_from_controls(tx, risk_id).await?
+        {
+            inputs.impact = Some(impact);
+            inputs.probability = Some(probability);
+        }
+
+        let score = self.score(&inputs);
+        let level = self.level(inputs.scoring_method, score).map(|level| level.name);
+        let level = level.as_deref();
+        sqlx::query(&format!("UPDATE risks SET {prefix}score = $1, {prefix}level = $2 WHERE id = $3"))
+            .bind(score)
+            .bind(level)
+            .bind(risk_id)
+            .execute(&mut **tx)
+            .await?;
+
+        // La fréquence de revue suit le niveau inhérent
+        if basis == ScoreBasis::Inherent {
+            self.schedule_review(tx, risk_id, level).await?;
+        }
+        Ok(score)
+    }
+
+    // Prochaine revue : dernière revue (à défaut, création du risque) plus le délai du niveau
+    pub async fn schedule_review(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        risk_id: Uuid,
+        level: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        self.sync_levels(tx).await?;
+        sqlx::query(&format!(
+            r#"
+            UPDATE risks r
+            SET next_review_at = s.next_review_at,
+                review_overdue = s.next_review_at < now() AND r.status NOT IN {REVIEW_EXEMPT_STATUSES}
+            FROM (
+                SELECT id, COALESCE(last_reviewed_at, created_at) + make_interval(days => $1) AS next_review_at
+                FROM risks WHERE id = $2
+            ) s
+            WHERE r.id = s.id
+            "#
+        ))
+        .bind(self.review_days(level))
+        .bind(risk_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    // Impact et probabilité résiduels déduits des contrôles liés ; `None` sans contrôle
+    async fn residual_from_controls(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        risk_id: Uuid,
+    ) -> Result<Option<(i32, i32)>, sqlx::Error> {
+        let controls = sqlx::query_as::<_, ControlEffect>(
+            r#"
+            SELECT c.control_type, c.design_effectiveness, c.operating_effectiveness
+            FROM controls c
+            JOIN risk_controls rc ON rc.control_id = c.id
+            WHERE rc.risk_id = $1
+            "#
+        )
+        .bind(risk_id)
+        .fetch_all(&mut **tx)
+        .await?;
+        if controls.is_empty() {
+            return Ok(None);
+        }
+
+        let (impact, probability) = sqlx::query_as::<_, (i32, i32)>("SELECT impact, probability FROM risks WHERE id = $1")
+            .bind(risk_id)
+            .fetch_one(&mut **tx)
+            .await?;
+        Ok(Some(mitigate(impact, probability, &controls)))
+    }
+
+    // Recalcule les niveaux et échéances de revue de tous les risques selon `levels`, à appeler au démarrage
+    // et dans la transaction qui modifie les niveaux ; un risque dont le niveau ou l'échéance change passe à une nouvelle version
+    pub async fn refresh_levels(&self, conn: &mut PgConnection, levels: &[RiskLevel]) -> Result<(), sqlx::Error> {
+        let names: Vec<&str> = levels.iter().map(|level| level.name.as_str()).collect();
+        let thresholds = |method| levels.iter().map(|level| level.threshold(method)).collect::<Vec<i32>>();
+
+        for basis in ScoreBasis::ALL {
+            let prefix = basis.column_prefix();
+            sqlx::query(&format!(
+                r#"
+                UPDATE risks r
+                SET {prefix}level = n.level, version = r.version + 1, updated_at = now()
+                FROM (
+                    SELECT s.id, (
+                        SELECT l.name
+                        FROM unnest($1::text[], $2::int[], $3::int[], $4::int[]) AS l(name, impact_probability, fmea, weighted)
+                        CROSS JOIN LATERAL (
+                            SELECT CASE s.scoring_method WHEN 'Fmea' THEN l.fmea WHEN 'Weighted' THEN l.weighted
+                                ELSE l.impact_probability END AS min_score
+                        ) t
+                        WHERE t.min_score <= s.{prefix}score
+                        ORDER BY t.min_score DESC
+                        LIMIT 1
+                    ) AS level
+                    FROM risks s
+                ) n
+                WHERE n.id = r.id AND r.{prefix}level IS DISTINCT FROM n.level
+                "#
+            ))
+            .bind(&names)
+            .bind(thresholds(ScoringMethod::ImpactProbability))
+            .bind(thresholds(ScoringMethod::Fmea))
+            .bind(thresholds(ScoringMethod::Weighted))
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        // Échéances de revue alignées sur les fréquences configurées, avec le retard qui en découle
+        let review_days: Vec<i32> = levels.iter().map(|level| level.review_days).collect();
+        sqlx::query(&format!(
+            r#"
+            UPDATE risks r
+            SET next_review_at = n.next_review_at,
+                review_overdue = n.next_review_at < now() AND r.status NOT IN {REVIEW_EXEMPT_STATUSES},
+                version = r.version + 1,
+                updated_at = now()
+            FROM (
+                SELECT s.id, COALESCE(s.last_reviewed_at, s.created_at) + make_interval(days => COALESCE(
+                    (SELECT l.days FROM unnest($1::text[], $2::int[]) AS l(name, days) WHERE l.name = s.level),
+                    $3
+                )) AS next_review_at
+                FROM risks s
+            ) n
+            WHERE n.id = r.id AND r.next_review_at IS DISTINCT FROM n.next_review_at
+            "#
+        ))
+        .bind(&names)
+        .bind(&review_days)
+        .bind(self.default_review_days)
+        .execute(conn)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service() -> ScoringService {
+        ScoringService {
+            default_method: ScoringMethod::ImpactProbability,
+            weights: Weights { impact: 1.0, probability: 1.0 },
+            levels: Arc::new(RwLock::new(LevelCache { version: 0, levels: default_levels() })),
+            default_review_days: 365,
+        }
+    }
+
+    fn inputs(scoring_method: ScoringMethod, impact: i32, probability: i32, evaluation: [i32; 3]) -> ScoreInputs {
+        let [severity, likelihood, detectability] = evaluation;
+        ScoreInputs {
+            impact: Some(impact),
+            probability: Some(probability),
+            scoring_method,
+            severity: Some(severity),
+            likelihood: Some(likelihood),
+            detectability: Some(detectability),
+        }
+    }
+
+    fn level_of(service: &ScoringService, inputs: &ScoreInputs) -> Option<String> {
+        service.level(inputs.scoring_method, service.score(inputs)).map(|level| level.name)
+    }
+
+    #[test]
+    fn impact_probability_levels() {
+        let service = service();
+        let level = |impact, probability| level_of(&service, &inputs(ScoringMethod::ImpactProbability, impact, probability, [1; 3]));
+        assert_eq!(level(1, 4), Some("Low".to_string()));
+        assert_eq!(level(1, 5), Some("Medium".to_string()));
+        assert_eq!(level(2, 5), Some("High".to_string()));
+        assert_eq!(level(4, 4), Some("Critical".to_string()));
+    }
+
+    #[test]
+    fn fmea_levels_use_their_own_scale() {
+        let service = service();
+        let level = |evaluation| level_of(&service, &inputs(ScoringMethod::Fmea, 5, 5, evaluation));
+        // 18 est « Critical » en impact × probabilité, pas en AMDEC
+        assert_eq!(level([3, 3, 2]), Some("Low".to_string()));
+        assert_eq!(level([5, 5, 2]), Some("Medium".to_string()));
+        assert_eq!(level([5, 5, 5]), Some("High".to_string()));
+        assert_eq!(level([8, 7, 4]), Some("Critical".to_string()));
+    }
+
+    #[test]
+    fn weighted_levels_use_their_own_scale() {
+        let service = service();
+        let level = |impact, probability| level_of(&service, &inputs(ScoringMethod::Weighted, impact, probability, [10; 3]));
+        assert_eq!(level(1, 2), Some("Low".to_string()));
+        assert_eq!(level(2, 2), Some("Medium".to_string()));
+        assert_eq!(level(3, 3), Some("High".to_string()));
+        // 4 + 4 comme 4 × 4 : niveau le plus élevé
+        assert_eq!(level(4, 4), Some("Critical".to_string()));
+        assert_eq!(service.level(ScoringMethod::Weighted, None), None);
+    }
+
+    #[test]
+    fn level_entries_parse_with_optional_review_days() {
+        assert_eq!(RiskLevel::parse(" Critical : 15 : #c62828 : 30 ", 365), Some(RiskLevel::new("Critical", 15, "#c62828", 30)));
+        assert_eq!(RiskLevel::parse("Low:1:#2e7d32", 365).map(|level| level.review_days), Some(365));
+        // Couleur ou nom manquant, seuil non numérique, délai nul : entrée rejetée
+        assert_eq!(RiskLevel::parse("Low:1", 365), None);
+        assert_eq!(RiskLevel::parse(":1:#2e7d32", 365), None);
+        assert_eq!(RiskLevel::parse("Low:un:#2e7d32", 365), None);
+        assert_eq!(RiskLevel::parse("Low:1:#2e7d32:0", 365), None);
+    }
+
+    fn control(control_type: ControlType, design: Effectiveness, operating: Effectiveness) -> ControlEffect {
+        ControlEffect { control_type, design_effectiveness: design, operating_effectiveness: operating }
+    }
+
+    #[test]
+    fn controls_reduce_their_own_axis() {
+        use ControlType::*;
+        use Effectiveness::*;
+
+        assert_eq!(mitigate(4, 4, &[]), (4, 4));
+        assert_eq!(mitigate(4, 4, &[control(Preventive, Effective, Effective)]), (4, 2));
+        assert_eq!(mitigate(4, 4, &[control(Detective, Effective, Effective)]), (2, 4));
+        // Efficacité partielle : 1 - 0,5 × 0,5, arrondi
+        assert_eq!(mitigate(4, 4, &[control(Corrective, Effective, PartiallyEffective)]), (3, 4));
+        // Réductions multiplicatives : 4 × 0,5 × 0,5
+        let both = [control(Preventive, Effective, Effective), control(Directive, Effective, Effective)];
+        assert_eq!(mitigate(4, 4, &both), (4, 1));
+        // Contrôle non testé sans effet, jamais sous 1
+        assert_eq!(mitigate(1, 3, &[control(Preventive, Effective, NotTested)]), (1, 3));
+        assert_eq!(mitigate(1, 1, &both), (1, 1));
+    }
+
+    #[test]
+    fn method_thresholds_override_defaults_when_well_formed() {
+        let mut levels = default_levels();
+        assert!(apply_thresholds(&mut levels, ScoringMethod::Fmea, "high:120, Critical:250"));
+        let fmea: Vec<i32> = levels.iter().map(|level| level.threshold(ScoringMethod::Fmea)).collect();
+        assert_eq!(fmea, [1, 50, 120, 250]);
+
+        // Niveau inconnu ou seuils décroissants : configuration ignorée
+        assert!(!apply_thresholds(&mut levels, ScoringMethod::Fmea, "Severe:300"));
+        assert!(!apply_thresholds(&mut levels, ScoringMethod::Fmea, "Medium:500"));
+        assert_eq!(levels[1].threshold(ScoringMethod::Fmea), 50);
+    }
+}
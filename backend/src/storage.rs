@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+
+// Backend de stockage des pièces jointes (interchangeable : disque local, S3...)
+#[async_trait]
+pub trait AttachmentStorage: Send + Sync {
+    async fn put(&self, key: &str, data: &[u8]) -> io::Result<()>;
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>>;
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
+
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> io::Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+        Ok(LocalStorage { root })
+    }
+
+    fn path(&self, key: &str) -> io::Result<PathBuf> {
+        // Les clés sont générées par l'API, on refuse tout ce qui pourrait sortir du répertoire
+        if key.is_empty() || key.contains(['/', '\\']) || key.starts_with('.') {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "clé de stockage invalide"));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl AttachmentStorage for LocalStorage {
+    async fn put(&self, key: &str, data: &[u8]) -> io::Result<()> {
+        tokio::fs::write(self.path(key)?, data).await
+    }
+
+    async fn get(&self, key: &str) -> io::Result<Vec<u8>> {
+        tokio::fs::read(self.path(key)?).await
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            other => other,
+        }
+    }
+}
+
+const DEFAULT_MAX_SIZE: usize = 10 * 1024 * 1024;
+
+const DEFAULT_ALLOWED_TYPES: &[&str] = &[
+    "application/pdf",
+    "image/png",
+    "image/jpeg",
+    "text/plain",
+    "text/csv",
+    "application/zip",
+    "application/msword",
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.ms-excel",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+];
+
+const ZIP_SIGNATURES: &[&[u8]] = &[b"PK\x03\x04", b"PK\x05\x06"];
+const OLE_SIGNATURES: &[&[u8]] = &[b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1"];
+
+// Type connu : extensions admises et signatures de début de fichier (`None` : texte UTF-8)
+struct KnownType {
+    mime: &'static str,
+    extensions: &'static [&'static str],
+    signatures: Option<&'static [&'static [u8]]>,
+}
+
+const KNOWN_TYPES: &[KnownType] = &[
+    KnownType { mime: "application/pdf", extensions: &["pdf"], signatures: Some(&[b"%PDF-"]) },
+    KnownType { mime: "image/png", extensions: &["png"], signatures: Some(&[b"\x89PNG\r\n\x1A\n"]) },
+    KnownType { mime: "image/jpeg", extensions: &["jpg", "jpeg"], signatures: Some(&[b"\xFF\xD8\xFF"]) },
+    KnownType { mime: "text/plain", extensions: &["txt", "log", "md"], signatures: None },
+    KnownType { mime: "text/csv", extensions: &["csv"], signatures: None },
+    KnownType { mime: "application/zip", extensions: &["zip"], signatures: Some(ZIP_SIGNATURES) },
+    KnownType { mime: "application/msword", extensions: &["doc"], signatures: Some(OLE_SIGNATURES) },
+    KnownType {
+        mime: "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        extensions: &["docx"],
+        signatures: Some(ZIP_SIGNATURES),
+    },
+    KnownType { mime: "application/vnd.ms-excel", extensions: &["xls"], signatures: Some(OLE_SIGNATURES) },
+    KnownType {
+        mime: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        extensions: &["xlsx"],
+        signatures: Some(ZIP_SIGNATURES),
+    },
+];
+
+// Le type déclaré par le client doit correspondre à l'extension et au contenu du fichier ;
+// un type autorisé par configuration mais absent de `KNOWN_TYPES` n'est pas vérifié
+pub fn content_matches(content_type: &str, filename: &str, data: &[u8]) -> bool {
+    let Some(known) = KNOWN_TYPES.iter().find(|known| known.mime == content_type) else {
+        return true;
+    };
+    let extension = filename.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase());
+    if !extension.is_some_and(|ext| known.extensions.contains(&ext.as_str())) {
+        return false;
+    }
+    match known.signatures {
+        Some(signatures) => signatures.iter().any(|signature| data.starts_with(signature)),
+        None => !data.contains(&0) && std::str::from_utf8(data).is_ok(),
+    }
+}
+
+#[derive(Clone)]
+pub struct AttachmentConfig {
+    pub dir: String,
+    pub max_size: usize,
+    pub allowed_types: Vec<String>,
+}
+
+impl AttachmentConfig {
+    pub fn from_env() -> Self {
+        let dir = env::var("ATTACHMENTS_DIR").unwrap_or_else(|_| "./uploads".to_string());
+        let max_size = env::var("ATTACHMENTS_MAX_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_MAX_SIZE);
+        let allowed_types = env::var("ATTACHMENTS_ALLOWED_TYPES")
+            .map(|v| v.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect())
+            .unwrap_or_else(|_| DEFAULT_ALLOWED_TYPES.iter().map(|t| t.to_string()).collect());
+
+        AttachmentConfig { dir, max_size, allowed_types }
+    }
+
+    pub fn is_allowed(&self, content_type: &str) -> bool {
+        self.allowed_types.iter().any(|t| t == content_type)
+    }
+}
@@ -0,0 +1,117 @@
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::env;
+
+use crate::error::{ApiError, ErrorCode};
+use crate::models::incident::{IncidentSeverity, IncidentStatus};
+use crate::scoring::ScoringMethod;
+
+// Bornes (incluses) d'une échelle de notation
+#[derive(Clone, Copy, Serialize)]
+pub struct ScaleRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+impl ScaleRange {
+    // Une échelle mal configurée refuserait toutes les saisies : le serveur ne démarre pas
+    fn from_env(prefix: &str, default: ScaleRange) -> Self {
+        let read = |suffix: &str, fallback: i32| {
+            let name = format!("{prefix}_{suffix}");
+            match env::var(&name) {
+                Ok(value) => value
+                    .trim()
+                    .parse()
+                    .unwrap_or_else(|_| panic!("{name} doit être un nombre entier (valeur : « {value} »)")),
+                Err(_) => fallback,
+            }
+        };
+        let range = ScaleRange { min: read("MIN", default.min), max: read("MAX", default.max) };
+        if range.min > range.max {
+            panic!("{prefix}_MIN ({}) doit être inférieur ou égal à {prefix}_MAX ({})", range.min, range.max);
+        }
+        range
+    }
+}
+
+// Règles de validation des saisies, exposées aux clients via `GET /validation/rules`
+#[derive(Clone, Serialize)]
+pub struct ValidationConfig {
+    // Impact et probabilité d'un risque, chacun sur sa propre échelle
+    pub impact_scale: ScaleRange,
+    pub probability_scale: ScaleRange,
+    // Gravité, vraisemblance et détectabilité d'une évaluation
+    pub evaluation_scale: ScaleRange,
+    pub title_min_length: usize,
+    pub title_max_length: usize,
+    pub incident_severities: Vec<IncidentSeverity>,
+    pub incident_statuses: Vec<IncidentStatus>,
+    pub scoring_methods: Vec<ScoringMethod>,
+}
+
+impl ValidationConfig {
+    pub fn from_env() -> Self {
+        let title_length = |name: &str, fallback: usize| {
+            env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(fallback)
+        };
+        // Échelle commune aux deux axes, sauf surcharge par IMPACT_SCALE_* / PROBABILITY_SCALE_*
+        let risk_scale = ScaleRange::from_env("RISK_SCALE", ScaleRange { min: 1, max: 5 });
+
+        ValidationConfig {
+            impact_scale: ScaleRange::from_env("IMPACT_SCALE", risk_scale),
+            probability_scale: ScaleRange::from_env("PROBABILITY_SCALE", risk_scale),
+            evaluation_scale: ScaleRange::from_env("EVALUATION_SCALE", ScaleRange { min: 1, max: 10 }),
+            title_min_length: title_length("TITLE_MIN_LENGTH", 3),
+            title_max_length: title_length("TITLE_MAX_LENGTH", 200),
+            incident_severities: IncidentSeverity::ALL.to_vec(),
+            incident_statuses: IncidentStatus::ALL.to_vec(),
+            scoring_methods: ScoringMethod::ALL.to_vec(),
+        }
+    }
+}
+
+// Saisie contrôlée avant écriture en base
+pub trait Validate {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError>;
+}
+
+// Erreurs accumulées champ par champ, renvoyées ensemble en 422
+#[derive(Default)]
+pub struct FieldErrors(BTreeMap<&'static str, Vec<String>>);
+
+impl FieldErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, field: &'static str, message: impl Into<String>) -> &mut Self {
+        self.0.entry(field).or_default().push(message.into());
+        self
+    }
+
+    pub fn length(&mut self, field: &'static str, value: &str, min: usize, max: usize) -> &mut Self {
+        let length = value.trim().chars().count();
+        if length < min {
+            self.add(field, format!("Au moins {} caractères requis", min));
+        } else if length > max {
+            self.add(field, format!("Au plus {} caractères autorisés", max));
+        }
+        self
+    }
+
+    pub fn range(&mut self, field: &'static str, value: i32, scale: ScaleRange) -> &mut Self {
+        if value < scale.min || value > scale.max {
+            self.add(field, format!("Doit être compris entre {} et {}", scale.min, scale.max));
+        }
+        self
+    }
+
+    pub fn into_result(self) -> Result<(), ApiError> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        Err(ApiError::new(ErrorCode::ValidationFailed, "Certains champs sont invalides")
+            .with_details(json!({ "fields": self.0 })))
+    }
+}
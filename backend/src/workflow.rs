@@ -0,0 +1,123 @@
+use serde_json::Value;
+use sqlx::Postgres;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::risk::{Risk, RiskStatus};
+use crate::models::workflow::{NewWorkflow, Workflow, WorkflowTransition};
+use crate::validation::{FieldErrors, Validate, ValidationConfig};
+
+// Champs qu'une transition peut exiger : la justification du changement ou un champ du risque
+pub const REQUIRED_FIELD_NAMES: &[&str] = &[
+    "justification",
+    "description",
+    "category",
+    "business_unit",
+    "team",
+    "owner",
+    "owner_delegate",
+    "regulation",
+    "control_measure_id",
+    "evaluation_method",
+    "evaluation_notes",
+    "additional_notes",
+    "assets",
+    "stakeholders",
+];
+
+// Workflow actif applicable : celui de l'unité métier du risque, à défaut le workflow par défaut
+pub async fn active_workflow<'e, E>(executor: E, business_unit: Option<&str>) -> Result<Option<Workflow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, Workflow>(
+        r#"
+        SELECT * FROM workflows
+        WHERE is_active AND (business_unit = $1 OR business_unit IS NULL)
+        ORDER BY business_unit IS NULL
+        LIMIT 1;
+        "#
+    )
+    .bind(business_unit)
+    .fetch_optional(executor)
+    .await
+}
+
+pub async fn find_transition<'e, E>(
+    executor: E,
+    workflow_id: Uuid,
+    from: RiskStatus,
+    to: RiskStatus,
+) -> Result<Option<WorkflowTransition>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = Postgres>,
+{
+    sqlx::query_as::<_, WorkflowTransition>(
+        r#"
+        SELECT * FROM workflow_transitions
+        WHERE workflow_id = $1 AND from_status = $2 AND to_status = $3;
+        "#
+    )
+    .bind(workflow_id)
+    .bind(from)
+    .bind(to)
+    .fetch_optional(executor)
+    .await
+}
+
+fn is_filled(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::String(text)) => !text.trim().is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(_) => true,
+    }
+}
+
+// Champs exigés par la transition mais non renseignés (sur le risque ou dans la demande)
+pub fn missing_fields(transition: &WorkflowTransition, risk: &Risk, justification: Option<&str>) -> Vec<String> {
+    let mut values = match serde_json::to_value(risk) {
+        Ok(Value::Object(map)) => map,
+        _ => Default::default(),
+    };
+    values.insert("justification".to_string(), justification.into());
+
+    transition
+        .required_fields
+        .iter()
+        .filter(|field| !is_filled(values.get(field.as_str())))
+        .cloned()
+        .collect()
+}
+
+// Cohérence d'une définition de workflow avant enregistrement
+impl Validate for NewWorkflow {
+    fn validate(&self, config: &ValidationConfig) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::new();
+        errors.length("name", &self.name, 1, config.title_max_length);
+        if self.states.is_empty() {
+            errors.add("states", "Le workflow doit comporter au moins un état");
+        }
+
+        let mut seen = HashSet::new();
+        for transition in &self.transitions {
+            let (from, to) = (transition.from_status, transition.to_status);
+            if !self.states.contains(&from) || !self.states.contains(&to) {
+                errors.add("transitions", format!("Transition {} → {} : état absent du workflow", from, to));
+            } else if from == to {
+                errors.add("transitions", format!("Transition {} → {} : les états doivent être différents", from, to));
+            } else if !seen.insert((from, to)) {
+                errors.add("transitions", format!("Transition {} → {} définie plusieurs fois", from, to));
+            }
+            for field in transition
+                .required_fields
+                .iter()
+                .filter(|field| !REQUIRED_FIELD_NAMES.contains(&field.as_str()))
+            {
+                errors.add("transitions", format!("Transition {} → {} : champ requis inconnu « {} »", from, to, field));
+            }
+        }
+        errors.into_result()
+    }
+}
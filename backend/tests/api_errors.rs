@@ -0,0 +1,81 @@
+// Tests d'intégration du format d'erreur { code, message, details }
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn errors_use_stable_codes() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Admin).await;
+    let app = init_app(&pool).await;
+
+    // Sans jeton : 401
+    let response = test::call_service(&app, test::TestRequest::get().uri("/risks").to_request()).await;
+    assert_eq!(response.status(), 401);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "unauthorized");
+
+    // Ressource inconnue : 404
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", Uuid::new_v4())).insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 404);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "not_found");
+    assert!(body["message"].is_string());
+
+    // Corps JSON invalide : 400
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload("{ \"title\": ");
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "bad_request");
+
+    // Nom d'utilisateur déjà pris : 409
+    let request = test::TestRequest::post()
+        .uri("/users")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "username": user.username, "password": "mot-de-passe-solide" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 409);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "unique_violation");
+
+    // Risque lié inexistant : 422
+    let request = test::TestRequest::post()
+        .uri("/incidents")
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "title": "Test incident",
+            "severity": "Low",
+            "related_risk_id": Uuid::new_v4(),
+        }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "foreign_key_violation");
+    assert_eq!(body["details"]["constraint"], "incidents_related_risk_id_fkey");
+
+    // Transition absente du workflow : 400
+    let risk_id = create_risk(&pool, "Test erreurs", "Identified").await;
+    let request = test::TestRequest::patch()
+        .uri(&format!("/risks/{}/status", risk_id))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(json!({ "status": "Closed" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "invalid_transition");
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,120 @@
+// Tests d'intégration des pièces jointes : envoi, téléchargement, limites et journal d'audit
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool, ATTACHMENT_MAX_SIZE};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+const BOUNDARY: &str = "sentinelrisk-test-boundary";
+
+// Corps multipart d'un unique champ `file`
+fn upload_request(uri: &str, token: &str, filename: &str, content_type: &str, data: &[u8]) -> test::TestRequest {
+    let mut body = format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\nContent-Type: {content_type}\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(data);
+    body.extend_from_slice(format!("\r\n--{BOUNDARY}--\r\n").as_bytes());
+
+    test::TestRequest::post()
+        .uri(uri)
+        .insert_header(bearer(token))
+        .insert_header(("Content-Type", format!("multipart/form-data; boundary={BOUNDARY}")))
+        .set_payload(body)
+}
+
+#[actix_web::test]
+async fn uploads_round_trip_with_their_checksum_and_are_audited() {
+    let pool = test_pool().await;
+    let (admin, token) = create_user(&pool, Role::Admin).await;
+    let risk_id = create_risk(&pool, "Test pièces jointes", "Identified").await;
+    let app = init_app(&pool).await;
+    let data = b"%PDF-1.7\nrapport d'audit";
+
+    let uri = format!("/risks/{}/attachments", risk_id);
+    let response = test::call_service(&app, upload_request(&uri, &token, "rapport.pdf", "application/pdf", data).to_request()).await;
+    assert_eq!(response.status(), 201);
+    let attachment: Value = test::read_body_json(response).await;
+    let checksum = hex::encode(Sha256::digest(data));
+    assert_eq!(attachment["sha256"], checksum.as_str());
+    assert_eq!(attachment["size_bytes"], data.len());
+    assert_eq!(attachment["content_type"], "application/pdf");
+    assert!(attachment.get("storage_key").is_none());
+
+    let request = test::TestRequest::get().uri(&uri).insert_header(bearer(&token));
+    let listed: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+
+    let attachment_id = attachment["id"].as_str().unwrap();
+    let request = test::TestRequest::get()
+        .uri(&format!("/attachments/{}/download", attachment_id))
+        .insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("Content-Type").unwrap(), "application/pdf");
+    assert_eq!(response.headers().get("X-Checksum-Sha256").unwrap(), checksum.as_str());
+    assert!(response.headers().get("Content-Disposition").unwrap().to_str().unwrap().contains("rapport.pdf"));
+    assert_eq!(test::read_body(response).await.as_ref(), data);
+
+    let request = test::TestRequest::delete()
+        .uri(&format!("/attachments/{}", attachment_id))
+        .insert_header(bearer(&token));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 204);
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/audit?entity=attachment&id={}", attachment_id))
+        .insert_header(bearer(&token));
+    let entries: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let actions: Vec<&str> = entries.as_array().unwrap().iter().map(|e| e["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, vec!["Create", "Delete"]);
+    assert_eq!(entries[0]["changes"]["sha256"]["after"], checksum.as_str());
+    assert_eq!(entries[0]["changes"]["risk_id"]["after"], risk_id.to_string());
+    assert_eq!(entries[1]["changes"]["filename"]["before"], "rapport.pdf");
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &admin).await;
+}
+
+#[actix_web::test]
+async fn oversized_or_mistyped_uploads_are_rejected() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let risk_id = create_risk(&pool, "Test pièces jointes refusées", "Identified").await;
+    let app = init_app(&pool).await;
+    let uri = format!("/risks/{}/attachments", risk_id);
+
+    let oversized = vec![b'a'; ATTACHMENT_MAX_SIZE + 1];
+    let response = test::call_service(&app, upload_request(&uri, &token, "notes.txt", "text/plain", &oversized).to_request()).await;
+    assert_eq!(response.status(), 413);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "payload_too_large");
+    assert_eq!(body["details"]["max_size"], ATTACHMENT_MAX_SIZE);
+
+    let rejected: [(&str, &str, &[u8]); 4] = [
+        ("script.sh", "application/x-sh", b"#!/bin/sh\n"),
+        // Exécutable présenté comme un PDF : extension correcte, signature absente
+        ("rapport.pdf", "application/pdf", b"MZ\x90\x00"),
+        // Bonne signature, mauvaise extension
+        ("rapport.exe", "application/pdf", b"%PDF-1.7\n"),
+        ("notes.txt", "text/plain", b"\x00\x01\x02binaire"),
+    ];
+    for (filename, content_type, data) in rejected {
+        let response = test::call_service(&app, upload_request(&uri, &token, filename, content_type, data).to_request()).await;
+        assert_eq!(response.status(), 415, "{} ({})", filename, content_type);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "unsupported_media_type");
+    }
+
+    let count = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM attachments WHERE risk_id = $1")
+        .bind(risk_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
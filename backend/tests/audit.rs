@@ -0,0 +1,59 @@
+// Tests d'intégration du journal d'audit (`GET /audit`)
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn risk_updates_record_before_and_after_of_changed_fields() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let body = json!({ "title": "Test audit", "impact": 2, "probability": 2, "owner": "Alice", "tags": ["rgpd"] });
+    let request = test::TestRequest::post().uri("/risks").insert_header(bearer(&token)).set_json(&body);
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let created: Value = test::read_body_json(response).await;
+    let risk_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+
+    let request = test::TestRequest::put()
+        .uri(&format!("/risks/{}", risk_id))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(json!({ "title": "Test audit modifié", "impact": 2, "probability": 2, "owner": "Bob", "tags": ["rgpd", "cloud"] }));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 200);
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/audit?entity=risk&id={}", risk_id))
+        .insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let entries: Value = test::read_body_json(response).await;
+    let entries = entries.as_array().unwrap();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0]["action"], "Create");
+    assert_eq!(entries[0]["changes"]["title"], json!({ "before": null, "after": "Test audit" }));
+
+    let update = &entries[1];
+    assert_eq!(update["action"], "Update");
+    assert_eq!(update["entity_type"], "risk");
+    assert_eq!(update["actor_id"], json!(user.id));
+    assert_eq!(update["actor_username"], user.username.as_str());
+    let changes = update["changes"].as_object().unwrap();
+    assert_eq!(changes["title"], json!({ "before": "Test audit", "after": "Test audit modifié" }));
+    assert_eq!(changes["owner"], json!({ "before": "Alice", "after": "Bob" }));
+    assert_eq!(changes["tags"], json!({ "before": ["rgpd"], "after": ["cloud", "rgpd"] }));
+    // Seuls les champs modifiés figurent, sans les champs techniques
+    assert!(!changes.contains_key("impact"));
+    assert!(!changes.contains_key("version"));
+    assert!(!changes.contains_key("updated_at"));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
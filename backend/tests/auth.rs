@@ -0,0 +1,114 @@
+// Tests d'intégration de l'authentification : connexion, jetons refusés, routes protégées
+
+mod common;
+
+use actix_web::test;
+use backend::auth::{hash_password, Claims};
+use backend::models::user::{Role, User};
+use chrono::{Duration, Utc};
+use common::{bearer, delete_user, init_app, test_pool};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
+
+const PASSWORD: &str = "mot-de-passe-solide";
+
+async fn create_account(pool: &PgPool) -> User {
+    sqlx::query_as::<_, User>("INSERT INTO users (username, password_hash, role) VALUES ($1, $2, $3) RETURNING *")
+        .bind(format!("test-{}", Uuid::new_v4()))
+        .bind(hash_password(PASSWORD).unwrap())
+        .bind(Role::Analyst)
+        .fetch_one(pool)
+        .await
+        .expect("création utilisateur")
+}
+
+// Jeton signé avec `secret`, émis il y a deux heures et valable `validity`
+fn forge_token(user: &User, secret: &str, validity: Duration) -> String {
+    let issued_at = Utc::now() - Duration::hours(2);
+    let claims = Claims {
+        sub: user.id,
+        username: user.username.clone(),
+        role: user.role,
+        iat: issued_at.timestamp(),
+        exp: (issued_at + validity).timestamp(),
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_bytes())).unwrap()
+}
+
+#[actix_web::test]
+async fn login_issues_a_token_for_valid_credentials_only() {
+    let pool = test_pool().await;
+    let user = create_account(&pool).await;
+    let app = init_app(&pool).await;
+
+    let login = |username: &str, password: &str| {
+        test::TestRequest::post()
+            .uri("/auth/login")
+            .set_json(json!({ "username": username, "password": password }))
+            .to_request()
+    };
+
+    let response = test::call_service(&app, login(&user.username, "mauvais-mot-de-passe")).await;
+    assert_eq!(response.status(), 401);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "unauthorized");
+    let response = test::call_service(&app, login("compte-inexistant", PASSWORD)).await;
+    assert_eq!(response.status(), 401);
+
+    let response = test::call_service(&app, login(&user.username, PASSWORD)).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["user"]["username"], user.username.as_str());
+    assert!(body["user"].get("password_hash").is_none());
+    assert!(body["expires_at"].is_string());
+
+    // Le jeton obtenu ouvre les routes protégées
+    let token = body["token"].as_str().unwrap();
+    let request = test::TestRequest::get().uri("/auth/me").insert_header(bearer(token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let me: Value = test::read_body_json(response).await;
+    assert_eq!(me["id"], json!(user.id));
+
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn protected_routes_reject_missing_invalid_and_expired_tokens() {
+    let pool = test_pool().await;
+    let user = create_account(&pool).await;
+    let app = init_app(&pool).await;
+    let secret = env::var("JWT_SECRET").unwrap();
+
+    // La route de santé reste publique
+    let response = test::call_service(&app, test::TestRequest::get().uri("/").to_request()).await;
+    assert_eq!(response.status(), 200);
+
+    for uri in ["/auth/me", "/risks", "/incidents", "/audit"] {
+        let response = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        assert_eq!(response.status(), 401, "{} sans jeton", uri);
+    }
+
+    let valid = forge_token(&user, &secret, Duration::hours(8));
+    let rejected = [
+        ("jeton illisible", bearer("pas-un-jwt")),
+        ("schéma autre que Bearer", ("Authorization", format!("Basic {}", valid))),
+        ("signature d'une autre clé", bearer(&forge_token(&user, "une-autre-cle-de-signature", Duration::hours(8)))),
+        ("jeton expiré", bearer(&forge_token(&user, &secret, Duration::hours(1)))),
+    ];
+    for (case, header) in rejected {
+        let request = test::TestRequest::get().uri("/risks").insert_header(header);
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 401, "{}", case);
+        let body: Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "unauthorized");
+    }
+
+    let request = test::TestRequest::get().uri("/risks").insert_header(bearer(&valid));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 200);
+
+    delete_user(&pool, &user).await;
+}
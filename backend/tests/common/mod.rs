@@ -0,0 +1,107 @@
+// Outils partagés par les tests d'intégration : nécessitent une base PostgreSQL
+// accessible via DATABASE_URL, faute de quoi ils échouent
+
+// Chaque fichier de tests n'utilise qu'une partie de ces outils
+#![allow(dead_code)]
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceResponse};
+use actix_web::{test, web, App, Error};
+use actix_http::Request;
+use backend::auth::JwtConfig;
+use backend::models::user::{Role, User};
+use backend::storage::{AttachmentConfig, AttachmentStorage, LocalStorage};
+use backend::validation::ValidationConfig;
+use backend::likelihood::LikelihoodConfig;
+use backend::scoring::ScoringService;
+use backend::{configure_routes, db};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::PgPool;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+// Base de test, migrations appliquées ; un test sans base échoue au lieu de passer sans rien vérifier
+pub async fn test_pool() -> PgPool {
+    dotenvy::dotenv().ok();
+    let url = env::var("DATABASE_URL").expect("DATABASE_URL requis pour les tests d'intégration");
+    let pool = PgPoolOptions::new()
+        .max_connections(10)
+        .acquire_timeout(Duration::from_secs(3))
+        .connect(&url)
+        .await
+        .unwrap_or_else(|e| panic!("Base de données de test injoignable : {}", e));
+    db::run_migrations(&pool).await.expect("migrations");
+    pool
+}
+
+// Utilisateur de test et jeton JWT associé
+pub async fn create_user(pool: &PgPool, role: Role) -> (User, String) {
+    let user = sqlx::query_as::<_, User>(
+        "INSERT INTO users (username, password_hash, role) VALUES ($1, '', $2) RETURNING *"
+    )
+    .bind(format!("test-{}", Uuid::new_v4()))
+    .bind(role)
+    .fetch_one(pool)
+    .await
+    .expect("création utilisateur");
+    let (token, _) = JwtConfig::from_env().issue(&user).expect("jeton");
+    (user, token)
+}
+
+pub async fn delete_user(pool: &PgPool, user: &User) {
+    sqlx::query("DELETE FROM users WHERE id = $1").bind(user.id).execute(pool).await.unwrap();
+}
+
+pub async fn create_risk(pool: &PgPool, title: &str, status: &str) -> Uuid {
+    sqlx::query_scalar::<_, Uuid>(
+        "INSERT INTO risks (title, impact, probability, status) VALUES ($1, 1, 1, $2) RETURNING id"
+    )
+    .bind(title)
+    .bind(status)
+    .fetch_one(pool)
+    .await
+    .expect("création risque")
+}
+
+pub async fn delete_risk(pool: &PgPool, risk_id: Uuid) {
+    sqlx::query("DELETE FROM risks WHERE id = $1").bind(risk_id).execute(pool).await.unwrap();
+}
+
+pub async fn delete_incident(pool: &PgPool, incident_id: Uuid) {
+    sqlx::query("DELETE FROM incidents WHERE id = $1").bind(incident_id).execute(pool).await.unwrap();
+}
+
+// Taille maximale des pièces jointes dans les tests, pour éprouver la limite sans gros fichiers
+pub const ATTACHMENT_MAX_SIZE: usize = 1024;
+
+pub async fn init_app(pool: &PgPool) -> impl Service<Request, Response = ServiceResponse<impl MessageBody>, Error = Error> {
+    // Niveaux enregistrés en base, comme au démarrage du serveur
+    let scoring = ScoringService::from_env();
+    scoring.load_levels(pool).await.expect("niveaux de risque");
+    let dir = env::temp_dir().join("sentinelrisk-tests");
+    let storage: Arc<dyn AttachmentStorage> = Arc::new(LocalStorage::new(&dir).expect("stockage"));
+    let attachment_config = AttachmentConfig {
+        dir: dir.to_string_lossy().into_owned(),
+        max_size: ATTACHMENT_MAX_SIZE,
+        ..AttachmentConfig::from_env()
+    };
+
+    test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .app_data(web::Data::from(storage))
+            .app_data(web::Data::new(attachment_config))
+            .app_data(web::Data::new(JwtConfig::from_env()))
+            .app_data(web::Data::new(ValidationConfig::from_env()))
+            .app_data(web::Data::new(LikelihoodConfig::from_env()))
+            .app_data(web::Data::new(scoring))
+            .configure(configure_routes),
+    )
+    .await
+}
+
+pub fn bearer(token: &str) -> (&'static str, String) {
+    ("Authorization", format!("Bearer {}", token))
+}
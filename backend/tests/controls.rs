@@ -0,0 +1,103 @@
+// Tests d'intégration de la bibliothèque de contrôles et du risque résiduel qui en découle
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn linked_controls_drive_the_residual_score() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Risque couvert par un contrôle", "impact": 4, "probability": 4 }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+    assert_eq!((&risk["score"], &risk["residual_score"]), (&json!(16), &Value::Null));
+
+    // Chaque changement du résiduel donne une nouvelle version du risque
+    let residual = |risk: Value| (risk["residual_score"].clone(), risk["residual_level"].clone(), risk["version"].clone());
+    let fetch_risk = || test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&token));
+
+    // Contrôle préventif pleinement efficace : probabilité divisée par deux
+    let request = test::TestRequest::post()
+        .uri("/controls")
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "name": "Revue trimestrielle des habilitations",
+            "control_type": "Preventive",
+            "frequency": "Quarterly",
+            "design_effectiveness": "Effective",
+            "operating_effectiveness": "Effective",
+            "risk_ids": [risk_id]
+        }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let control: Value = test::read_body_json(response).await;
+    let control_id: Uuid = serde_json::from_value(control["id"].clone()).unwrap();
+    assert_eq!(control["risk_ids"], json!([risk_id]));
+
+    let risk: Value = test::read_body_json(test::call_service(&app, fetch_risk().to_request()).await).await;
+    assert_eq!(residual(risk), (json!(8), json!("Medium"), json!(2)));
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/{}/controls", risk_id))
+        .insert_header(bearer(&token));
+    let controls: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(controls.len(), 1);
+    assert_eq!(controls[0]["id"], json!(control_id));
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/controls?control_type=Preventive&risk_id={}", risk_id))
+        .insert_header(bearer(&token));
+    let listed: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert!(listed.iter().any(|c| c["id"] == json!(control_id)));
+
+    // Efficacité de fonctionnement dégradée : réduction de moitié moindre
+    let request = test::TestRequest::put()
+        .uri(&format!("/controls/{}", control_id))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(json!({
+            "name": "Revue trimestrielle des habilitations",
+            "control_type": "Preventive",
+            "design_effectiveness": "Effective",
+            "operating_effectiveness": "PartiallyEffective"
+        }));
+    let updated: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!((&updated["version"], &updated["risk_ids"]), (&json!(2), &json!([risk_id])));
+
+    let risk: Value = test::read_body_json(test::call_service(&app, fetch_risk().to_request()).await).await;
+    assert_eq!(residual(risk), (json!(12), json!("High"), json!(3)));
+
+    // Sans contrôle ni saisie manuelle, plus de risque résiduel
+    let request = test::TestRequest::delete()
+        .uri(&format!("/risks/{}/controls/{}", risk_id, control_id))
+        .insert_header(bearer(&token));
+    let detached: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(detached["risk_ids"], json!([]));
+
+    let risk: Value = test::read_body_json(test::call_service(&app, fetch_risk().to_request()).await).await;
+    assert_eq!(residual(risk), (Value::Null, Value::Null, json!(4)));
+
+    // Trois recalculs du résiduel, chacun audité sur le risque
+    let audited = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM audit_log WHERE entity_type = 'risk' AND entity_id = $1 AND action = 'Update'",
+    )
+    .bind(risk_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(audited, 3);
+
+    sqlx::query("DELETE FROM controls WHERE id = $1").bind(control_id).execute(&pool).await.unwrap();
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
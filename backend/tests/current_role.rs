@@ -0,0 +1,44 @@
+// Tests d'intégration : les permissions suivent le rôle courant du compte, pas celui du jeton
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn role_changes_and_deletions_apply_to_existing_tokens() {
+    let pool = test_pool().await;
+    let (admin, admin_token) = create_user(&pool, Role::Admin).await;
+    let (analyst, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let create = || {
+        test::TestRequest::post()
+            .uri("/risks")
+            .insert_header(bearer(&token))
+            .set_json(json!({ "title": "Risque avant rétrogradation", "impact": 2, "probability": 2 }))
+    };
+    let response = test::call_service(&app, create().to_request()).await;
+    assert_eq!(response.status(), 200);
+    let risk: Value = test::read_body_json(response).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+
+    // Rétrogradé en lecteur : le jeton encore valide n'autorise plus l'écriture
+    let request = test::TestRequest::patch()
+        .uri(&format!("/users/{}/role", analyst.id))
+        .insert_header(bearer(&admin_token))
+        .set_json(json!({ "role": "Viewer" }));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 200);
+    assert_eq!(test::call_service(&app, create().to_request()).await.status(), 403);
+
+    // Compte supprimé : le jeton est refusé
+    delete_user(&pool, &analyst).await;
+    let request = test::TestRequest::get().uri("/risks").insert_header(bearer(&token));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 401);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &admin).await;
+}
@@ -0,0 +1,84 @@
+// Tests d'intégration de l'historique des évaluations
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn evaluations_form_a_time_series() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    let risk_id = create_risk(&pool, "Risque réévalué", "Identified").await;
+    sqlx::query("UPDATE risks SET scoring_method = 'Fmea' WHERE id = $1")
+        .bind(risk_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut etags = Vec::new();
+    for (severity, notes) in [(2, "Première revue"), (5, "Dégradation constatée")] {
+        let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&token));
+        let response = test::call_service(&app, request.to_request()).await;
+        etags.push(response.headers().get("ETag").unwrap().to_str().unwrap().to_string());
+
+        let request = test::TestRequest::post()
+            .uri(&format!("/risks/{}/evaluation", risk_id))
+            .insert_header(bearer(&token))
+            .set_json(json!({ "severity": severity, "likelihood": 3, "detectability": 2, "notes": notes }));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 200);
+    }
+
+    // Chaque évaluation recalcule le score AMDEC : nouvelle version du risque, entrée d'audit
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    etags.push(response.headers().get("ETag").unwrap().to_str().unwrap().to_string());
+    assert_ne!(etags[0], etags[1]);
+    assert_ne!(etags[1], etags[2]);
+
+    let request = test::TestRequest::put()
+        .uri(&format!("/risks/{}", risk_id))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", etags[1].as_str()))
+        .set_json(json!({ "title": "Risque réévalué", "impact": 2, "probability": 2 }));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 412);
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/audit?entity=risk&id={}", risk_id))
+        .insert_header(bearer(&token));
+    let entries: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let last = entries.last().unwrap();
+    assert_eq!(last["action"], "Update");
+    assert_eq!(last["changes"]["score"], json!({ "before": 12, "after": 30 }));
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/{}/evaluations", risk_id))
+        .insert_header(bearer(&token));
+    let evaluations: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let scores: Vec<&Value> = evaluations.iter().map(|e| &e["risk_score"]).collect();
+    assert_eq!(scores, vec![&json!(12), &json!(30)]);
+    assert_eq!(evaluations[0]["notes"], "Première revue");
+    assert_eq!(evaluations[1]["evaluated_by_username"], json!(user.username));
+
+    // Deux évaluations : la dernière est renvoyée par les deux routes
+    for uri in [format!("/risks/{}/evaluations/latest", risk_id), format!("/risks/{}/evaluation", risk_id)] {
+        let request = test::TestRequest::get().uri(&uri).insert_header(bearer(&token));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 200);
+        let latest: Value = test::read_body_json(response).await;
+        assert_eq!(latest["id"], evaluations[1]["id"]);
+    }
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/{}/evaluations", Uuid::new_v4()))
+        .insert_header(bearer(&token));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 404);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,84 @@
+// Tests d'intégration du cycle de vie des incidents
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_incident, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn incident_follows_lifecycle() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/incidents")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Test incident", "severity": "High" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let created: Value = test::read_body_json(response).await;
+    let id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+    let uri = format!("/incidents/{}/status", id);
+
+    // Étape sautée : New → Contained
+    let request = test::TestRequest::patch()
+        .uri(&uri)
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(json!({ "status": "Contained" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "invalid_transition");
+    assert_eq!(body["details"]["allowed"], json!(["Triaged"]));
+
+    for (version, status) in [(1, "Triaged"), (2, "Investigating"), (3, "Contained"), (4, "Resolved"), (5, "Closed")] {
+        let request = test::TestRequest::patch()
+            .uri(&uri)
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+            .set_json(json!({ "status": status, "comment": "Étape suivante" }));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert!(response.status().is_success(), "{} : {}", status, response.status());
+        let incident: Value = test::read_body_json(response).await;
+        assert_eq!(incident["status"], status);
+
+        // Pas de retour en arrière : un incident résolu ne peut qu'être clos
+        if status == "Resolved" {
+            let request = test::TestRequest::patch()
+                .uri(&uri)
+                .insert_header(bearer(&token))
+                .insert_header(("If-Match", format!("\"{}\"", version + 1)))
+                .set_json(json!({ "status": "Investigating" }));
+            let response = test::call_service(&app, request.to_request()).await;
+            assert_eq!(response.status(), 400);
+            let body: Value = test::read_body_json(response).await;
+            assert_eq!(body["details"]["allowed"], json!(["Closed"]));
+        }
+    }
+
+    let request = test::TestRequest::get().uri(&format!("/incidents/{}/history", id)).insert_header(bearer(&token));
+    let history: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let transitions: Vec<(&str, &str)> = history
+        .iter()
+        .map(|h| (h["old_status"].as_str().unwrap(), h["new_status"].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        transitions,
+        vec![
+            ("New", "Triaged"),
+            ("Triaged", "Investigating"),
+            ("Investigating", "Contained"),
+            ("Contained", "Resolved"),
+            ("Resolved", "Closed"),
+        ]
+    );
+    assert_eq!(history[0]["changed_by_username"], user.username);
+
+    delete_incident(&pool, id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,116 @@
+// Tests d'intégration des liens incidents ↔ risques et de la probabilité suggérée
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_incident, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn linked_incidents_drive_probability_suggestion() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    let risk_a = create_risk(&pool, "Risque A", "Identified").await;
+    let risk_b = create_risk(&pool, "Risque B", "Identified").await;
+
+    let mut incidents = Vec::new();
+    for payload in [
+        json!({ "title": "Incident partagé", "severity": "Medium", "related_risk_id": risk_a, "risk_ids": [risk_b] }),
+        json!({ "title": "Incident critique", "severity": "Critical", "related_risk_id": risk_a }),
+    ] {
+        let request = test::TestRequest::post().uri("/incidents").insert_header(bearer(&token)).set_json(payload);
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 201);
+        let created: Value = test::read_body_json(response).await;
+        incidents.push(serde_json::from_value::<Uuid>(created["id"].clone()).unwrap());
+    }
+
+    let request = test::TestRequest::get().uri(&format!("/incidents/{}", incidents[0])).insert_header(bearer(&token));
+    let incident: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let mut risk_ids: Vec<Uuid> = serde_json::from_value(incident["risk_ids"].clone()).unwrap();
+    let mut expected = vec![risk_a, risk_b];
+    risk_ids.sort();
+    expected.sort();
+    assert_eq!(risk_ids, expected);
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/incidents?related_risk_id={}", risk_b))
+        .insert_header(bearer(&token));
+    let linked: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(linked.len(), 1);
+
+    // A : 1 moyen + 1 critique = 6 par an ; B : 1 moyen = 2 par an (échelle 1 à 5)
+    for (risk_id, expected) in [(risk_a, 4), (risk_b, 3)] {
+        let request = test::TestRequest::get()
+            .uri(&format!("/risks/{}/probability-suggestion?window_days=365", risk_id))
+            .insert_header(bearer(&token));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 200);
+        let suggestion: Value = test::read_body_json(response).await;
+        assert_eq!(suggestion["current_probability"], 1);
+        assert_eq!(suggestion["suggested_probability"], expected);
+    }
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/{}/probability-suggestion?window_days=0", risk_a))
+        .insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+
+    for id in incidents {
+        delete_incident(&pool, id).await;
+    }
+    delete_risk(&pool, risk_a).await;
+    delete_risk(&pool, risk_b).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn changing_the_primary_risk_replaces_its_link() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    let risk_a = create_risk(&pool, "Risque principal initial", "Identified").await;
+    let risk_b = create_risk(&pool, "Risque lié", "Identified").await;
+    let risk_c = create_risk(&pool, "Nouveau risque principal", "Identified").await;
+
+    let request = test::TestRequest::post().uri("/incidents").insert_header(bearer(&token)).set_json(json!({
+        "title": "Incident requalifié", "severity": "Low", "related_risk_id": risk_a, "risk_ids": [risk_b]
+    }));
+    let created: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let incident_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+
+    let linked_risks = |incident: &Value| {
+        let mut risk_ids: Vec<Uuid> = serde_json::from_value(incident["risk_ids"].clone()).unwrap();
+        risk_ids.sort();
+        risk_ids
+    };
+    let sorted = |mut risk_ids: Vec<Uuid>| {
+        risk_ids.sort();
+        risk_ids
+    };
+
+    // Nouveau risque principal sans `risk_ids` : l'ancien est délié, les autres liens restent ;
+    // la même requête rejouée ne change plus rien
+    for (version, related_risk_id) in [(1, risk_c), (2, risk_c)] {
+        let request = test::TestRequest::put()
+            .uri(&format!("/incidents/{}", incident_id))
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+            .set_json(json!({ "title": "Incident requalifié", "severity": "Low", "related_risk_id": related_risk_id }));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 200);
+        let updated: Value = test::read_body_json(response).await;
+        assert_eq!(updated["related_risk_id"], json!(risk_c));
+        assert_eq!(linked_risks(&updated), sorted(vec![risk_b, risk_c]));
+    }
+
+    delete_incident(&pool, incident_id).await;
+    for risk_id in [risk_a, risk_b, risk_c] {
+        delete_risk(&pool, risk_id).await;
+    }
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,65 @@
+// Tests d'intégration de la pagination, des filtres et du tri des listes
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn risks_are_filtered_sorted_and_paginated() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    // Catégorie propre au test pour isoler ses risques
+    let category = format!("listing-{}", Uuid::new_v4());
+
+    let mut ids = Vec::new();
+    for (title, impact) in [("Risque faible", 1), ("Risque fort", 5), ("Risque moyen", 3)] {
+        let request = test::TestRequest::post()
+            .uri("/risks")
+            .insert_header(bearer(&token))
+            .set_json(json!({ "title": title, "impact": impact, "probability": 2, "category": category }));
+        let created: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        ids.push(serde_json::from_value::<Uuid>(created["id"].clone()).unwrap());
+    }
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks?category={}&sort=-score&per_page=2", category))
+        .insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get("x-total-count").unwrap(), "3");
+    let page: Vec<Value> = test::read_body_json(response).await;
+    let titles: Vec<&str> = page.iter().map(|r| r["title"].as_str().unwrap()).collect();
+    assert_eq!(titles, vec!["Risque fort", "Risque moyen"]);
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks?category={}&sort=-score&per_page=2&page=2", category))
+        .insert_header(bearer(&token));
+    let page: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0]["title"], "Risque faible");
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks?category={}&score_min=6&q=moyen", category))
+        .insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.headers().get("x-total-count").unwrap(), "1");
+
+    let request = test::TestRequest::get()
+        .uri("/risks?sort=password&status=Unknown")
+        .insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["sort"].is_array());
+    assert!(body["details"]["fields"]["status"].is_array());
+
+    for id in ids {
+        delete_risk(&pool, id).await;
+    }
+    delete_user(&pool, &user).await;
+}
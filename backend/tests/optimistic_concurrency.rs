@@ -0,0 +1,94 @@
+// Tests d'intégration du contrôle de concurrence optimiste (ETag / If-Match)
+
+mod common;
+
+use actix_web::http::header::ETAG;
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+
+fn risk_body(title: &str) -> Value {
+    json!({ "title": title, "impact": 2, "probability": 3 })
+}
+
+#[actix_web::test]
+async fn risk_updates_require_current_version() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Admin).await;
+    let risk_id = create_risk(&pool, "Test ETag", "Identified").await;
+    let app = init_app(&pool).await;
+    let uri = format!("/risks/{}", risk_id);
+
+    let response = test::call_service(&app, test::TestRequest::get().uri(&uri).insert_header(bearer(&token)).to_request()).await;
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"1\"");
+
+    // Sans If-Match : 428
+    let request = test::TestRequest::put().uri(&uri).insert_header(bearer(&token)).set_json(risk_body("Sans version"));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 428);
+
+    let request = test::TestRequest::put()
+        .uri(&uri)
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(risk_body("Première modification"));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert!(response.status().is_success());
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"2\"");
+
+    // Deuxième modification basée sur la version 1 : conflit, l'état courant est renvoyé
+    let request = test::TestRequest::put()
+        .uri(&uri)
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(risk_body("Modification concurrente"));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 412);
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"2\"");
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "version_mismatch");
+    assert_eq!(body["details"]["current"]["title"], "Première modification");
+    assert_eq!(body["details"]["current"]["version"], 2);
+
+    let request = test::TestRequest::delete().uri(&uri).insert_header(bearer(&token)).insert_header(("If-Match", "\"1\""));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 412);
+
+    let request = test::TestRequest::delete().uri(&uri).insert_header(bearer(&token)).insert_header(("If-Match", "\"2\""));
+    assert!(test::call_service(&app, request.to_request()).await.status().is_success());
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn if_match_accepts_star_and_tag_lists() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Admin).await;
+    let risk_id = create_risk(&pool, "Test If-Match", "Identified").await;
+    let app = init_app(&pool).await;
+    let uri = format!("/risks/{}", risk_id);
+    let put = |if_match: &str, title: &str| {
+        test::TestRequest::put()
+            .uri(&uri)
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", if_match.to_string()))
+            .set_json(risk_body(title))
+            .to_request()
+    };
+
+    // `*` : toute version existante convient
+    let response = test::call_service(&app, put("*", "Avec étoile")).await;
+    assert!(response.status().is_success());
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"2\"");
+
+    // Liste : il suffit qu'une des étiquettes corresponde
+    let response = test::call_service(&app, put("\"1\", \"2\"", "Avec liste")).await;
+    assert!(response.status().is_success());
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"3\"");
+
+    assert_eq!(test::call_service(&app, put("\"1\", \"2\"", "Liste périmée")).await.status(), 412);
+    assert_eq!(test::call_service(&app, put(" ", "En-tête vide")).await.status(), 412);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,68 @@
+// Tests d'intégration des scores inhérent, résiduel et cible
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn risks_expose_inherent_residual_and_target_scores() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    // Un seul membre de la paire résiduelle : refusé
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Risque incomplet", "impact": 5, "probability": 4, "residual_impact": 2 }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["residual_probability"].is_array());
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "title": "Risque maîtrisé",
+            "impact": 5, "probability": 4,
+            "residual_impact": 2, "residual_probability": 3,
+            "target_impact": 1, "target_probability": 2
+        }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+    assert_eq!((&risk["score"], &risk["level"]), (&json!(20), &json!("Critical")));
+    assert_eq!((&risk["residual_score"], &risk["residual_level"]), (&json!(6), &json!("Medium")));
+    assert_eq!((&risk["target_score"], &risk["target_level"]), (&json!(2), &json!("Low")));
+
+    let listed = |critical: &[Value]| critical.iter().find(|r| r["id"] == json!(risk_id)).cloned();
+    let request = test::TestRequest::get().uri("/risks/critical").insert_header(bearer(&token));
+    let critical: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(listed(&critical).expect("risque inhérent critique")["basis"], "Inherent");
+
+    let request = test::TestRequest::get().uri("/risks/critical?basis=Residual").insert_header(bearer(&token));
+    let critical: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert!(listed(&critical).is_none());
+
+    let request = test::TestRequest::get()
+        .uri("/risks/critical?basis=Residual&threshold=Medium")
+        .insert_header(bearer(&token));
+    let critical: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let entry = listed(&critical).expect("risque résiduel moyen");
+    assert_eq!((&entry["basis"], &entry["score"]), (&json!("Residual"), &json!(6)));
+
+    // Une évaluation du risque résiduel ne touche pas au score inhérent
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/evaluation", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "basis": "Residual", "severity": 2, "likelihood": 2, "detectability": 2 }));
+    let evaluation: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!((&evaluation["basis"], &evaluation["risk_score"]), (&json!("Residual"), &json!(6)));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
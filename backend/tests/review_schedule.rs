@@ -0,0 +1,124 @@
+// Tests d'intégration de la revue périodique et de la détection des revues en retard
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use backend::review::flag_overdue_reviews;
+use backend::scoring::ScoringService;
+use chrono::{DateTime, Duration, Utc};
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn overdue_reviews_are_flagged_until_the_risk_is_reviewed() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    // Niveau Critical : revue tous les 30 jours
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Risque à revoir régulièrement", "impact": 4, "probability": 4 }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+    let created_at: DateTime<Utc> = serde_json::from_value(risk["created_at"].clone()).unwrap();
+    let next_review_at: DateTime<Utc> = serde_json::from_value(risk["next_review_at"].clone()).unwrap();
+    assert_eq!(risk["level"], json!("Critical"));
+    assert_eq!(next_review_at - created_at, Duration::days(30));
+    assert_eq!(risk["review_overdue"], json!(false));
+
+    let overdue_ids = || async {
+        let request = test::TestRequest::get().uri("/risks/overdue").insert_header(bearer(&token));
+        let risks: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        risks.into_iter().map(|r| r["id"].clone()).collect::<Vec<_>>()
+    };
+    assert!(!overdue_ids().await.contains(&json!(risk_id)));
+
+    // Échéance dépassée : la tâche de fond signale le retard
+    sqlx::query("UPDATE risks SET next_review_at = now() - interval '1 day' WHERE id = $1")
+        .bind(risk_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert!(flag_overdue_reviews(&pool).await.unwrap() >= 1);
+    assert!(overdue_ids().await.contains(&json!(risk_id)));
+
+    // Le signalement change la version du risque, et donc son ETag
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    let etag = response.headers().get("ETag").and_then(|v| v.to_str().ok()).map(str::to_string);
+    let flagged: Value = test::read_body_json(response).await;
+    assert_eq!(flagged["review_overdue"], json!(true));
+    assert_eq!(flagged["version"], json!(risk["version"].as_i64().unwrap() + 1));
+    assert_eq!(etag, Some(format!("\"{}\"", flagged["version"])));
+
+    let request = test::TestRequest::get()
+        .uri("/risks?review_overdue=true&per_page=100")
+        .insert_header(bearer(&token));
+    let listed: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert!(listed.iter().any(|r| r["id"] == json!(risk_id)));
+
+    // La revue reporte l'échéance et lève le retard
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/review", risk_id))
+        .insert_header(bearer(&token));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 428);
+    let review = |version: &Value| {
+        test::TestRequest::post()
+            .uri(&format!("/risks/{}/review", risk_id))
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+    };
+    assert_eq!(test::call_service(&app, review(&risk["version"]).to_request()).await.status(), 412);
+    let response = test::call_service(&app, review(&flagged["version"]).to_request()).await;
+    assert_eq!(response.status(), 200);
+    let etag = response.headers().get("ETag").and_then(|v| v.to_str().ok()).map(str::to_string);
+    let reviewed: Value = test::read_body_json(response).await;
+    assert_eq!(reviewed["version"], json!(flagged["version"].as_i64().unwrap() + 1));
+    assert_eq!(etag, Some(format!("\"{}\"", reviewed["version"])));
+    let last_reviewed_at: DateTime<Utc> = serde_json::from_value(reviewed["last_reviewed_at"].clone()).unwrap();
+    let next_review_at: DateTime<Utc> = serde_json::from_value(reviewed["next_review_at"].clone()).unwrap();
+    assert_eq!(next_review_at - last_reviewed_at, Duration::days(30));
+    assert_eq!(reviewed["review_overdue"], json!(false));
+    assert!(!overdue_ids().await.contains(&json!(risk_id)));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn realigned_review_dates_clear_the_overdue_flag() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Risque à échéance réalignée", "impact": 4, "probability": 4 }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+
+    // Échéance périmée, signalée en retard
+    sqlx::query("UPDATE risks SET next_review_at = now() - interval '1 day', review_overdue = true WHERE id = $1")
+        .bind(risk_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    // Le recalcul des niveaux réaligne l'échéance sur la fréquence du niveau et lève le retard
+    let scoring = ScoringService::from_env();
+    scoring.load_levels(&pool).await.unwrap();
+    scoring.refresh_levels(&mut pool.acquire().await.unwrap(), &scoring.levels()).await.unwrap();
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&token));
+    let refreshed: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(refreshed["next_review_at"], risk["next_review_at"]);
+    assert_eq!(refreshed["review_overdue"], json!(false));
+    assert_eq!(refreshed["version"], json!(risk["version"].as_i64().unwrap() + 1));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,217 @@
+// Tests d'intégration de l'appétence par catégorie et des dépassements de tolérance
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn crossing_the_category_tolerance_records_a_breach() {
+    let pool = test_pool().await;
+    let (admin, admin_token) = create_user(&pool, Role::Admin).await;
+    let (analyst, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    let category = format!("Fournisseurs {}", Uuid::new_v4());
+
+    // Seuils réservés aux administrateurs, tolérance au moins égale à l'appétence
+    let appetite = json!({ "category": category, "appetite": 6, "tolerance": 12 });
+    let request = test::TestRequest::post().uri("/risk-appetites").insert_header(bearer(&token)).set_json(&appetite);
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 403);
+    let request = test::TestRequest::post()
+        .uri("/risk-appetites")
+        .insert_header(bearer(&admin_token))
+        .set_json(json!({ "category": category, "appetite": 12, "tolerance": 6 }));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 422);
+    let request = test::TestRequest::post().uri("/risk-appetites").insert_header(bearer(&admin_token)).set_json(&appetite);
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let appetite: Value = test::read_body_json(response).await;
+    let appetite_id: Uuid = serde_json::from_value(appetite["id"].clone()).unwrap();
+
+    let request = test::TestRequest::post()
+        .uri("/risk-appetites")
+        .insert_header(bearer(&admin_token))
+        .set_json(json!({ "category": category.to_uppercase(), "appetite": 1, "tolerance": 2 }));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 409);
+
+    // Risque dans la tolérance : aucun dépassement
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Défaillance d'un sous-traitant", "impact": 2, "probability": 2, "category": category }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+
+    let breaches = || async {
+        let request = test::TestRequest::get()
+            .uri(&format!("/appetite-breaches?risk_id={}", risk_id))
+            .insert_header(bearer(&token));
+        let breaches: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        breaches
+    };
+    assert!(breaches().await.is_empty());
+
+    // Score 16 au-delà de la tolérance 12 : un dépassement, puis aucun tant que le risque reste au-delà
+    let mut version = risk["version"].clone();
+    for (impact, probability) in [(4, 4), (5, 4)] {
+        let request = test::TestRequest::put()
+            .uri(&format!("/risks/{}", risk_id))
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+            .set_json(json!({
+                "title": "Défaillance d'un sous-traitant",
+                "impact": impact,
+                "probability": probability,
+                "category": category
+            }));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 200);
+        let risk: Value = test::read_body_json(response).await;
+        version = risk["version"].clone();
+    }
+
+    let recorded = breaches().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(
+        (&recorded[0]["trigger"], &recorded[0]["score"], &recorded[0]["tolerance"]),
+        (&json!("Update"), &json!(16), &json!(12))
+    );
+    assert_eq!(recorded[0]["triggered_by_username"], json!(analyst.username));
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/out-of-tolerance?category={}", category.replace(' ', "%20")))
+        .insert_header(bearer(&token));
+    let report: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(report.len(), 1);
+    assert_eq!((&report[0]["risk_id"], &report[0]["excess"]), (&json!(risk_id), &json!(8)));
+
+    // Création directement au-delà de la tolérance
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Fuite de données chez un prestataire", "impact": 5, "probability": 5, "category": category }));
+    let created: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let created_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+    let request = test::TestRequest::get()
+        .uri(&format!("/appetite-breaches?risk_id={}", created_id))
+        .insert_header(bearer(&token));
+    let recorded: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0]["trigger"], json!("Create"));
+
+    let request = test::TestRequest::delete()
+        .uri(&format!("/risk-appetites/{}", appetite_id))
+        .insert_header(bearer(&admin_token))
+        .insert_header(("If-Match", "\"1\""));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 204);
+
+    delete_risk(&pool, risk_id).await;
+    delete_risk(&pool, created_id).await;
+    delete_user(&pool, &analyst).await;
+    delete_user(&pool, &admin).await;
+}
+
+#[actix_web::test]
+async fn fmea_risks_are_compared_with_their_own_thresholds() {
+    let pool = test_pool().await;
+    let (admin, admin_token) = create_user(&pool, Role::Admin).await;
+    let (analyst, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    let category = format!("Production {}", Uuid::new_v4());
+
+    // Seuils en impact × probabilité seulement : les risques AMDEC ne sont pas comparés
+    let request = test::TestRequest::post()
+        .uri("/risk-appetites")
+        .insert_header(bearer(&admin_token))
+        .set_json(json!({ "category": category, "appetite": 6, "tolerance": 12 }));
+    let appetite: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let appetite_id = appetite["id"].as_str().unwrap().to_string();
+    assert_eq!(appetite["fmea_tolerance"], Value::Null);
+
+    let mut ids = Vec::new();
+    for (title, scoring_method) in [("Arrêt de la ligne", "Fmea"), ("Panne du convoyeur", "ImpactProbability")] {
+        let request = test::TestRequest::post()
+            .uri("/risks")
+            .insert_header(bearer(&token))
+            .set_json(json!({
+                "title": title, "impact": 4, "probability": 4, "category": category, "scoring_method": scoring_method
+            }));
+        let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        ids.push(serde_json::from_value::<Uuid>(risk["id"].clone()).unwrap());
+    }
+    let (fmea_id, matrix_id) = (ids[0], ids[1]);
+
+    let evaluate = |evaluation: [i32; 3]| {
+        let [severity, likelihood, detectability] = evaluation;
+        test::TestRequest::post()
+            .uri(&format!("/risks/{}/evaluation", fmea_id))
+            .insert_header(bearer(&token))
+            .set_json(json!({ "severity": severity, "likelihood": likelihood, "detectability": detectability }))
+    };
+    let breaches = || async {
+        let request = test::TestRequest::get()
+            .uri(&format!("/appetite-breaches?risk_id={}", fmea_id))
+            .insert_header(bearer(&token));
+        let breaches: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        breaches
+    };
+
+    // 5 × 5 × 2 = 50, bien au-delà de 12 mais sur une autre échelle : aucun dépassement
+    assert_eq!(test::call_service(&app, evaluate([5, 5, 2]).to_request()).await.status(), 200);
+    assert!(breaches().await.is_empty());
+
+    let update = |body: Value, version: i32| {
+        test::TestRequest::put()
+            .uri(&format!("/risk-appetites/{}", appetite_id))
+            .insert_header(bearer(&admin_token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+            .set_json(body)
+    };
+    let response = test::call_service(
+        &app,
+        update(json!({ "category": category, "appetite": 6, "tolerance": 12, "fmea_appetite": 100 }), 1).to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["fmea_tolerance"].is_array());
+
+    let body = json!({ "category": category, "appetite": 6, "tolerance": 12, "fmea_appetite": 100, "fmea_tolerance": 200 });
+    assert_eq!(test::call_service(&app, update(body, 1).to_request()).await.status(), 200);
+
+    // 100 reste sous la tolérance AMDEC de 200, 10 × 5 × 5 = 250 la dépasse
+    assert_eq!(test::call_service(&app, evaluate([5, 5, 4]).to_request()).await.status(), 200);
+    assert!(breaches().await.is_empty());
+    assert_eq!(test::call_service(&app, evaluate([10, 5, 5]).to_request()).await.status(), 200);
+    let recorded = breaches().await;
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(
+        (&recorded[0]["trigger"], &recorded[0]["scoring_method"], &recorded[0]["score"], &recorded[0]["tolerance"]),
+        (&json!("Evaluation"), &json!("Fmea"), &json!(250), &json!(200))
+    );
+
+    // Chaque risque face aux seuils de sa méthode, classés par écart relatif : 4 / 12 avant 50 / 200
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/out-of-tolerance?category={}", category.replace(' ', "%20")))
+        .insert_header(bearer(&token));
+    let report: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let rows: Vec<(&Value, &Value, &Value)> = report.iter().map(|r| (&r["risk_id"], &r["tolerance"], &r["excess"])).collect();
+    assert_eq!(
+        rows,
+        vec![(&json!(matrix_id), &json!(12), &json!(4)), (&json!(fmea_id), &json!(200), &json!(50))]
+    );
+
+    for id in ids {
+        delete_risk(&pool, id).await;
+    }
+    sqlx::query("DELETE FROM risk_appetites WHERE id = $1")
+        .bind(Uuid::parse_str(&appetite_id).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    delete_user(&pool, &analyst).await;
+    delete_user(&pool, &admin).await;
+}
@@ -0,0 +1,75 @@
+// Tests d'intégration des champs descriptifs d'un risque : tags, actifs, parties prenantes
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn linked_lists_and_descriptive_fields_round_trip() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "title": "Test champs descriptifs",
+            "impact": 3,
+            "probability": 2,
+            "tags": ["rgpd", " cloud ", "", "rgpd"],
+            "assets": ["CRM", "Entrepôt de données"],
+            "stakeholders": ["DPO"],
+            "owner": "Alice",
+            "owner_delegate": "Bob",
+            "business_unit": "Ventes",
+            "threat_map": "Fuite",
+        }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let created: Value = test::read_body_json(response).await;
+    let risk_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+    let uri = format!("/risks/{}", risk_id);
+
+    // Valeurs vides écartées, doublons fusionnés, listes triées
+    let request = test::TestRequest::get().uri(&uri).insert_header(bearer(&token));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(risk["tags"], json!(["cloud", "rgpd"]));
+    assert_eq!(risk["assets"], json!(["CRM", "Entrepôt de données"]));
+    assert_eq!(risk["stakeholders"], json!(["DPO"]));
+    assert_eq!(risk["owner"], "Alice");
+    assert_eq!(risk["owner_delegate"], "Bob");
+    assert_eq!(risk["business_unit"], "Ventes");
+    assert_eq!(risk["threat_map"], "Fuite");
+
+    // Les listes envoyées remplacent les précédentes ; absentes, elles sont vidées
+    let request = test::TestRequest::put()
+        .uri(&uri)
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", format!("\"{}\"", risk["version"])))
+        .set_json(json!({
+            "title": "Test champs descriptifs",
+            "impact": 3,
+            "probability": 2,
+            "tags": ["cloud", "sous-traitance"],
+            "stakeholders": [],
+            "owner": "Carole",
+        }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+
+    let request = test::TestRequest::get().uri(&uri).insert_header(bearer(&token));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(risk["tags"], json!(["cloud", "sous-traitance"]));
+    assert_eq!(risk["assets"], json!([]));
+    assert_eq!(risk["stakeholders"], json!([]));
+    assert_eq!(risk["owner"], "Carole");
+    assert_eq!(risk["owner_delegate"], Value::Null);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,101 @@
+// Tests d'intégration des niveaux de risque enregistrés en base et modifiables par un administrateur ;
+// un seul test dans ce fichier : la modification recalcule les niveaux de tous les risques et s'applique
+// à toutes les instances du serveur
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+
+#[actix_web::test]
+async fn admins_update_levels_and_risks_follow() {
+    let pool = test_pool().await;
+    let (admin, admin_token) = create_user(&pool, Role::Admin).await;
+    let (analyst, analyst_token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    // Seconde instance du serveur, démarrée avant la modification des niveaux
+    let other = init_app(&pool).await;
+
+    let request = test::TestRequest::get().uri("/scoring/levels").insert_header(bearer(&analyst_token));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 403);
+
+    let request = test::TestRequest::get().uri("/scoring/levels").insert_header(bearer(&admin_token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let etag = response.headers().get("ETag").unwrap().to_str().unwrap().to_string();
+    let stored: Value = test::read_body_json(response).await;
+    let original = stored["levels"].clone();
+
+    // 2 × 3 = 6 : « Medium » avec les seuils par défaut (Low 1, Medium 5, High 10, Critical 15)
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&analyst_token))
+        .set_json(json!({ "title": "Risque reclassé", "impact": 2, "probability": 3 }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id = risk["id"].as_str().unwrap().to_string();
+    assert_eq!(risk["level"], "Medium");
+
+    let mut raised = original.clone();
+    raised[1]["min_score"] = json!(7);
+    let put = |levels: &Value, if_match: Option<&str>| {
+        let mut request = test::TestRequest::put()
+            .uri("/scoring/levels")
+            .insert_header(bearer(&admin_token))
+            .set_json(json!({ "levels": levels }));
+        if let Some(if_match) = if_match {
+            request = request.insert_header(("If-Match", if_match.to_string()));
+        }
+        request
+    };
+
+    assert_eq!(test::call_service(&app, put(&raised, None).to_request()).await.status(), 428);
+
+    // Seuils non croissants : refusés, rien n'est modifié
+    let mut unordered = original.clone();
+    unordered[2]["fmea_min_score"] = json!(10);
+    let response = test::call_service(&app, put(&unordered, Some(&etag)).to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["levels"].is_array());
+
+    let response = test::call_service(&app, put(&raised, Some(&etag)).to_request()).await;
+    assert_eq!(response.status(), 200);
+    let raised_etag = response.headers().get("ETag").unwrap().to_str().unwrap().to_string();
+    assert_ne!(raised_etag, etag);
+
+    // Niveau recalculé, nouvelle version du risque, configuration exposée à jour
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&analyst_token));
+    let reclassified: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(reclassified["level"], "Low");
+    assert!(reclassified["version"].as_i64() > risk["version"].as_i64());
+    let request = test::TestRequest::get().uri("/scoring/config").insert_header(bearer(&analyst_token));
+    let config: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(config["levels"][1]["min_score"], 7);
+
+    // L'autre instance applique les niveaux enregistrés, sans redémarrage
+    let request = test::TestRequest::get().uri("/scoring/config").insert_header(bearer(&analyst_token));
+    let config: Value = test::read_body_json(test::call_service(&other, request.to_request()).await).await;
+    assert_eq!(config["levels"][1]["min_score"], 7);
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&analyst_token))
+        .set_json(json!({ "title": "Risque noté par une autre instance", "impact": 2, "probability": 3 }));
+    let other_risk: Value = test::read_body_json(test::call_service(&other, request.to_request()).await).await;
+    assert_eq!(other_risk["level"], "Low");
+
+    assert_eq!(test::call_service(&app, put(&original, Some(&etag)).to_request()).await.status(), 412);
+
+    // Seuils d'origine rétablis
+    let response = test::call_service(&app, put(&original, Some(&raised_etag)).to_request()).await;
+    assert_eq!(response.status(), 200);
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&analyst_token));
+    let restored: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(restored["level"], "Medium");
+
+    delete_risk(&pool, risk_id.parse().unwrap()).await;
+    delete_risk(&pool, other_risk["id"].as_str().unwrap().parse().unwrap()).await;
+    delete_user(&pool, &analyst).await;
+    delete_user(&pool, &admin).await;
+}
@@ -0,0 +1,61 @@
+// Tests d'intégration des niveaux de risque et du seuil de `/risks/critical`
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn risks_carry_their_level_and_critical_list_honours_threshold() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let mut ids = Vec::new();
+    for (title, impact, probability) in [("Risque majeur", 4, 4), ("Risque modéré", 2, 3)] {
+        let request = test::TestRequest::post()
+            .uri("/risks")
+            .insert_header(bearer(&token))
+            .set_json(json!({ "title": title, "impact": impact, "probability": probability }));
+        let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        ids.push(serde_json::from_value::<Uuid>(risk["id"].clone()).unwrap());
+    }
+    let (major, moderate) = (ids[0], ids[1]);
+
+    // Seuils par défaut : Low 1, Medium 5, High 10, Critical 15
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", major)).insert_header(bearer(&token));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(risk["level"], "Critical");
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", moderate)).insert_header(bearer(&token));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(risk["level"], "Medium");
+
+    let listed = |critical: &[Value], id: Uuid| critical.iter().any(|r| r["id"] == json!(id));
+    for (uri, expect_moderate) in [
+        ("/risks/critical", false),
+        ("/risks/critical?threshold=medium", true),
+        ("/risks/critical?threshold=7", false),
+    ] {
+        let request = test::TestRequest::get().uri(uri).insert_header(bearer(&token));
+        let critical: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+        assert!(listed(&critical, major), "{uri}");
+        assert_eq!(listed(&critical, moderate), expect_moderate, "{uri}");
+    }
+
+    let request = test::TestRequest::get().uri("/risks/critical?threshold=Extreme").insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+
+    let request = test::TestRequest::get().uri("/scoring/config").insert_header(bearer(&token));
+    let config: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let names: Vec<&str> = config["levels"].as_array().unwrap().iter().map(|l| l["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["Low", "Medium", "High", "Critical"]);
+
+    for id in ids {
+        delete_risk(&pool, id).await;
+    }
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,66 @@
+// Tests d'intégration du score canonique des risques
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn every_endpoint_returns_the_score_of_the_risk_method() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Risque AMDEC", "impact": 4, "probability": 3, "scoring_method": "Fmea" }));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(risk["id"].clone()).unwrap();
+    // Pas encore d'évaluation : pas de score
+    assert_eq!(risk["scoring_method"], "Fmea");
+    assert_eq!(risk["score"], Value::Null);
+
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/evaluation", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "severity": 2, "likelihood": 3, "detectability": 4 }));
+    assert!(test::call_service(&app, request.to_request()).await.status().is_success());
+
+    let request = test::TestRequest::get().uri(&format!("/risks/{}", risk_id)).insert_header(bearer(&token));
+    let risk: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(risk["score"], 24);
+
+    // Seuils AMDEC : 24 n'est que `Low`, loin du seuil `Critical` (200)
+    assert_eq!(risk["level"], "Low");
+    let critical = |threshold: &str| {
+        test::TestRequest::get().uri(&format!("/risks/critical{}", threshold)).insert_header(bearer(&token)).to_request()
+    };
+    let listed: Vec<Value> = test::read_body_json(test::call_service(&app, critical("")).await).await;
+    assert!(listed.iter().all(|r| r["id"] != json!(risk_id)));
+
+    // Un score explicite vaut pour toutes les méthodes
+    let listed: Vec<Value> = test::read_body_json(test::call_service(&app, critical("?threshold=20")).await).await;
+    let listed = listed.iter().find(|r| r["id"] == json!(risk_id)).expect("risque au-delà du score");
+    assert_eq!(listed["score"], 24);
+
+    // Passage en somme pondérée (poids 1 par défaut) : 4 + 3
+    let request = test::TestRequest::put()
+        .uri(&format!("/risks/{}", risk_id))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", format!("\"{}\"", risk["version"])))
+        .set_json(json!({ "title": "Risque AMDEC", "impact": 4, "probability": 3, "scoring_method": "Weighted" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let risk: Value = test::read_body_json(response).await;
+    assert_eq!((&risk["score"], &risk["level"]), (&json!(7), &json!("High")));
+
+    let listed: Vec<Value> = test::read_body_json(test::call_service(&app, critical("?threshold=High")).await).await;
+    assert!(listed.iter().any(|r| r["id"] == json!(risk_id)));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,49 @@
+// Tests d'intégration de la recherche plein texte
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_incident, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn search_returns_ranked_highlighted_hits_of_both_kinds() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    // Terme propre au test pour ne trouver que ses données
+    let term = format!("zq{}", &Uuid::new_v4().simple().to_string()[..8]);
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": format!("Fuite {}", term), "impact": 3, "probability": 2 }));
+    let created: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let risk_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+
+    let request = test::TestRequest::post()
+        .uri("/incidents")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Incident réseau", "description": format!("Trace {} relevée", term), "severity": "Low" }));
+    let created: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let incident_id: Uuid = serde_json::from_value(created["id"].clone()).unwrap();
+
+    let request = test::TestRequest::get().uri(&format!("/search?q={}", term)).insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let hits: Vec<Value> = test::read_body_json(response).await;
+    let kinds: Vec<&str> = hits.iter().map(|h| h["kind"].as_str().unwrap()).collect();
+    // Terme dans le titre du risque : mieux classé que dans la description de l'incident
+    assert_eq!(kinds, vec!["risk", "incident"]);
+    assert!(hits[0]["highlight"].as_str().unwrap().contains(&format!("<mark>{}</mark>", term)));
+
+    let request = test::TestRequest::get().uri("/search?q=").insert_header(bearer(&token));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+
+    delete_incident(&pool, incident_id).await;
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,188 @@
+// Tests d'intégration des changements de statut
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool};
+use futures_util::future::join_all;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn history(pool: &PgPool, risk_id: Uuid) -> Vec<(String, String, Option<String>)> {
+    sqlx::query_as::<_, (String, String, Option<String>)>(
+        "SELECT old_status, new_status, changed_by_username FROM risk_status_history WHERE risk_id = $1 ORDER BY changed_at"
+    )
+    .bind(risk_id)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+async fn current_status(pool: &PgPool, risk_id: Uuid) -> String {
+    sqlx::query_scalar::<_, String>("SELECT status FROM risks WHERE id = $1")
+        .bind(risk_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+fn status_request(token: &str, risk_id: Uuid, version: i32, body: Value) -> test::TestRequest {
+    test::TestRequest::patch()
+        .uri(&format!("/risks/{}/status", risk_id))
+        .insert_header(bearer(token))
+        .insert_header(("If-Match", format!("\"{}\"", version)))
+        .set_json(body)
+}
+
+#[actix_web::test]
+async fn concurrent_identical_transitions_apply_once() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Test transitions", "Identified").await;
+    let app = init_app(&pool).await;
+
+    let responses = join_all((0..5).map(|_| {
+        test::call_service(&app, status_request(&token, risk_id, 1, json!({ "status": "Assessed" })).to_request())
+    }))
+    .await;
+
+    // Les requêtes servies après la première voient une version périmée
+    let succeeded = responses.iter().filter(|r| r.status().is_success()).count();
+    let stale = responses.iter().filter(|r| r.status() == 412).count();
+    assert_eq!(succeeded, 1);
+    assert_eq!(stale, 4);
+    assert_eq!(history(&pool, risk_id).await.len(), 1);
+    assert_eq!(current_status(&pool, risk_id).await, "Assessed");
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn concurrent_conflicting_transitions_keep_history_consistent() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Test transitions", "Monitoring").await;
+    let app = init_app(&pool).await;
+
+    let bodies = [
+        json!({ "status": "Accepted", "justification": "Coût de traitement disproportionné" }),
+        json!({ "status": "Rejected" }),
+        json!({ "status": "Transferred", "justification": "Couvert par l'assurance" }),
+    ];
+    let responses = join_all(
+        bodies
+            .into_iter()
+            .map(|body| test::call_service(&app, status_request(&token, risk_id, 1, body).to_request())),
+    )
+    .await;
+
+    assert_eq!(responses.iter().filter(|r| r.status().is_success()).count(), 1);
+
+    let history = history(&pool, risk_id).await;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].0, "Monitoring");
+    assert_eq!(history[0].1, current_status(&pool, risk_id).await);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn sequential_transitions_are_all_recorded() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Test transitions", "Identified").await;
+    let app = init_app(&pool).await;
+
+    for (version, status) in [(1, "Assessed"), (2, "InTreatment")] {
+        let request = status_request(&token, risk_id, version, json!({ "status": status }));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert!(response.status().is_success());
+    }
+
+    let history = history(&pool, risk_id).await;
+    assert_eq!(
+        history,
+        vec![
+            ("Identified".to_string(), "Assessed".to_string(), Some(user.username.clone())),
+            ("Assessed".to_string(), "InTreatment".to_string(), Some(user.username.clone())),
+        ]
+    );
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn rejected_transition_leaves_risk_untouched() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Test transitions", "Identified").await;
+    let app = init_app(&pool).await;
+
+    let response =
+        test::call_service(&app, status_request(&token, risk_id, 1, json!({ "status": "Closed" })).to_request()).await;
+    assert_eq!(response.status(), 400);
+
+    let response = test::call_service(
+        &app,
+        status_request(&token, risk_id, 1, json!({ "status": "Assessed", "approver_id": user.id })).to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+
+    // Justification exigée par le statut visé : même erreur que pour un champ exigé par le workflow
+    let response = test::call_service(
+        &app,
+        status_request(&token, risk_id, 1, json!({ "status": "Accepted", "justification": "  " })).to_request(),
+    )
+    .await;
+    assert_eq!(response.status(), 400);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "invalid_transition");
+    assert_eq!(body["details"]["missing_fields"], json!(["justification"]));
+
+    assert!(history(&pool, risk_id).await.is_empty());
+    assert_eq!(current_status(&pool, risk_id).await, "Identified");
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn risks_cannot_be_created_past_the_initial_status() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+    let title = format!("Test statut initial {}", Uuid::new_v4());
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": title, "impact": 2, "probability": 2, "status": "Closed" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["status"].is_array());
+    let created = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM risks WHERE title = $1")
+        .bind(&title)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(created, 0);
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": title, "impact": 2, "probability": 2, "status": "Identified" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert!(response.status().is_success());
+    let risk: Value = test::read_body_json(response).await;
+    assert_eq!(risk["status"], "Identified");
+
+    delete_risk(&pool, risk["id"].as_str().unwrap().parse().unwrap()).await;
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,257 @@
+// Tests d'intégration des plans de traitement et de leurs actions
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use chrono::{Duration, Utc};
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+
+#[actix_web::test]
+async fn open_mandatory_actions_block_monitoring() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Risque en traitement", "InTreatment").await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/treatment-plans", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "title": "Sécuriser les accès distants",
+            "strategy": "Mitigate",
+            "description": "Durcir les accès distants",
+            "owner": "RSSI",
+            "due_date": (Utc::now() + Duration::days(30)).date_naive(),
+        }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let plan: Value = test::read_body_json(response).await;
+    assert_eq!(plan["actions"], json!([]));
+    assert_eq!((&plan["title"], &plan["owner"]), (&json!("Sécuriser les accès distants"), &json!("RSSI")));
+
+    let yesterday = (Utc::now() - Duration::days(1)).date_naive();
+    let add_action = |body: Value| {
+        test::TestRequest::post()
+            .uri(&format!("/treatment-plans/{}/actions", plan["id"].as_str().unwrap()))
+            .insert_header(bearer(&token))
+            .set_json(body)
+    };
+
+    let response = test::call_service(&app, add_action(json!({ "title": "Avancement hors bornes", "progress": 150 })).to_request()).await;
+    assert_eq!(response.status(), 422);
+
+    let request = add_action(json!({ "title": "Déployer le MFA", "assignee_id": user.id, "due_date": yesterday }));
+    let mandatory: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!((&mandatory["status"], &mandatory["overdue"]), (&json!("Open"), &json!(true)));
+    let request = add_action(json!({ "title": "Sensibiliser les équipes", "mandatory": false }));
+    let optional: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(optional["mandatory"], false);
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/actions?risk_id={}&overdue=true", risk_id))
+        .insert_header(bearer(&token));
+    let overdue: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(overdue.len(), 1);
+    assert_eq!((&overdue[0]["id"], &overdue[0]["assignee_username"]), (&mandatory["id"], &json!(user.username)));
+
+    let to_monitoring = |version: i32| {
+        test::TestRequest::patch()
+            .uri(&format!("/risks/{}/status", risk_id))
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+            .set_json(json!({ "status": "Monitoring" }))
+    };
+
+    // L'action obligatoire ouverte bloque le passage en suivi, pas l'action facultative
+    let response = test::call_service(&app, to_monitoring(1).to_request()).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "invalid_transition");
+    let open: Vec<Value> = serde_json::from_value(body["details"]["open_actions"].clone()).unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0]["id"], mandatory["id"]);
+
+    let request = test::TestRequest::put()
+        .uri(&format!("/actions/{}", mandatory["id"].as_str().unwrap()))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(json!({ "title": "Déployer le MFA", "assignee_id": user.id, "due_date": yesterday, "status": "Done" }));
+    let done: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!((&done["progress"], &done["overdue"]), (&json!(100), &json!(false)));
+    assert!(done["completed_at"].is_string());
+
+    let response = test::call_service(&app, to_monitoring(1).to_request()).await;
+    assert!(response.status().is_success());
+
+    let request = test::TestRequest::get()
+        .uri(&format!("/risks/{}/treatment-plans", risk_id))
+        .insert_header(bearer(&token));
+    let plans: Vec<Value> = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(plans.len(), 1);
+    assert_eq!(plans[0]["actions"].as_array().map(Vec::len), Some(2));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn mandatory_action_waits_for_a_concurrent_move_to_monitoring() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Risque presque traité", "InTreatment").await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/treatment-plans", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Plan de réduction", "strategy": "Mitigate", "owner": "RSSI" }));
+    let plan: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+
+    // Passage en suivi en cours, risque verrouillé comme le fait `change_status`
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SELECT id FROM risks WHERE id = $1 FOR UPDATE")
+        .bind(risk_id)
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+    let request = test::TestRequest::post()
+        .uri(&format!("/treatment-plans/{}/actions", plan["id"].as_str().unwrap()))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Action ajoutée pendant le passage en suivi" }));
+    let started = std::time::Instant::now();
+    let (response, _) = tokio::join!(test::call_service(&app, request.to_request()), async move {
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        sqlx::query("UPDATE risks SET status = 'Monitoring' WHERE id = $1")
+            .bind(risk_id)
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+    });
+
+    // L'ajout a attendu la fin du passage en suivi, puis a été refusé
+    assert!(started.elapsed() >= std::time::Duration::from_millis(300));
+    assert_eq!(response.status(), 409);
+    let open: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM action_items a JOIN treatment_plans p ON p.id = a.plan_id WHERE p.risk_id = $1"
+    )
+    .bind(risk_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(open, 0);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn invalid_treatment_plans_are_rejected() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Risque à traiter", "InTreatment").await;
+    let app = init_app(&pool).await;
+
+    let yesterday = (Utc::now() - Duration::days(1)).date_naive();
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/treatment-plans", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "", "strategy": "Avoid", "owner": "  ", "due_date": yesterday }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "validation_failed");
+    let fields = body["details"]["fields"].as_object().unwrap();
+    for field in ["title", "owner", "due_date"] {
+        assert!(fields.contains_key(field), "{field} absent de {body}");
+    }
+
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/treatment-plans", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Abandonner le service", "strategy": "Avoid", "owner": "DSI" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let plan: Value = test::read_body_json(response).await;
+
+    // Même contrôle en modification, avant toute écriture
+    let request = test::TestRequest::put()
+        .uri(&format!("/treatment-plans/{}", plan["id"].as_str().unwrap()))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""))
+        .set_json(json!({ "title": "Abandonner le service", "strategy": "Avoid" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["owner"].is_array());
+
+    let plans: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM treatment_plans WHERE risk_id = $1 AND owner = 'DSI'")
+        .bind(risk_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(plans, 1);
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn overdue_plans_stay_editable() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::RiskOwner).await;
+    let risk_id = create_risk(&pool, "Risque en retard de traitement", "InTreatment").await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/treatment-plans", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "title": "Chiffrer les sauvegardes",
+            "strategy": "Mitigate",
+            "owner": "DSI",
+            "due_date": (Utc::now() + Duration::days(10)).date_naive(),
+        }));
+    let plan: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    let plan_id = plan["id"].as_str().unwrap();
+
+    // L'échéance est dépassée depuis la création du plan
+    let overdue = (Utc::now() - Duration::days(5)).date_naive();
+    sqlx::query("UPDATE treatment_plans SET due_date = $1 WHERE id = $2::uuid")
+        .bind(overdue)
+        .bind(plan_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let update = |body: Value, version: i32| {
+        test::TestRequest::put()
+            .uri(&format!("/treatment-plans/{}", plan_id))
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", format!("\"{}\"", version)))
+            .set_json(body)
+    };
+
+    // Échéance inchangée : le plan reste modifiable
+    let request = update(json!({ "title": "Chiffrer les sauvegardes", "strategy": "Transfer", "owner": "RSSI", "due_date": overdue }), 1);
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 200);
+    let plan: Value = test::read_body_json(response).await;
+    assert_eq!((&plan["strategy"], &plan["owner"]), (&json!("Transfer"), &json!("RSSI")));
+
+    // Une nouvelle échéance dans le passé reste refusée
+    let request = update(
+        json!({ "title": "Chiffrer les sauvegardes", "strategy": "Transfer", "owner": "RSSI", "due_date": overdue - Duration::days(1) }),
+        2,
+    );
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert!(body["details"]["fields"]["due_date"].is_array());
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
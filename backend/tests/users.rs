@@ -0,0 +1,71 @@
+// Tests d'intégration de la gestion des comptes (`/users`)
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn weak_passwords_are_rejected() {
+    let pool = test_pool().await;
+    let (admin, token) = create_user(&pool, Role::Admin).await;
+    let app = init_app(&pool).await;
+    let username = format!("test-{}", Uuid::new_v4());
+
+    for password in ["", "court", username.as_str(), "Admin"] {
+        let request = test::TestRequest::post()
+            .uri("/users")
+            .insert_header(bearer(&token))
+            .set_json(json!({ "username": username, "password": password }));
+        let response = test::call_service(&app, request.to_request()).await;
+        assert_eq!(response.status(), 422, "mot de passe « {} »", password);
+        let body: Value = test::read_body_json(response).await;
+        assert!(body["details"]["fields"]["password"].is_array());
+    }
+
+    let request = test::TestRequest::post()
+        .uri("/users")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "username": username, "password": "mot-de-passe-solide" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let created: Value = test::read_body_json(response).await;
+    assert_eq!(created["role"], "Viewer");
+
+    sqlx::query("DELETE FROM users WHERE username = $1").bind(&username).execute(&pool).await.unwrap();
+    delete_user(&pool, &admin).await;
+}
+
+#[actix_web::test]
+async fn usernames_are_stored_trimmed() {
+    let pool = test_pool().await;
+    let (admin, token) = create_user(&pool, Role::Admin).await;
+    let app = init_app(&pool).await;
+    let username = format!("test-{}", Uuid::new_v4());
+
+    let create = |username: String| {
+        test::TestRequest::post()
+            .uri("/users")
+            .insert_header(bearer(&token))
+            .set_json(json!({ "username": username, "password": "mot-de-passe-solide" }))
+    };
+    let response = test::call_service(&app, create(format!("  {} ", username)).to_request()).await;
+    assert_eq!(response.status(), 201);
+    let created: Value = test::read_body_json(response).await;
+    assert_eq!(created["username"], json!(username));
+
+    // Même nom aux espaces près : déjà utilisé
+    let response = test::call_service(&app, create(username.clone()).to_request()).await;
+    assert_eq!(response.status(), 409);
+
+    let request = test::TestRequest::post()
+        .uri("/auth/login")
+        .set_json(json!({ "username": username, "password": "mot-de-passe-solide" }));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 200);
+
+    sqlx::query("DELETE FROM users WHERE username = $1").bind(&username).execute(&pool).await.unwrap();
+    delete_user(&pool, &admin).await;
+}
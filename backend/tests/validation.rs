@@ -0,0 +1,56 @@
+// Tests d'intégration de la validation des saisies (422 avec erreurs par champ)
+
+mod common;
+
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_risk, create_user, delete_risk, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+
+#[actix_web::test]
+async fn invalid_inputs_are_rejected_field_by_field() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Analyst).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/risks")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "x", "impact": -40, "probability": 3 }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "validation_failed");
+    let fields = body["details"]["fields"].as_object().unwrap();
+    assert!(fields.contains_key("title"));
+    assert!(fields.contains_key("impact"));
+    assert!(!fields.contains_key("probability"));
+
+    let request = test::TestRequest::post()
+        .uri("/incidents")
+        .insert_header(bearer(&token))
+        .set_json(json!({ "title": "Test incident", "severity": "banana" }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "validation_failed");
+
+    let risk_id = create_risk(&pool, "Test validation", "Identified").await;
+    let request = test::TestRequest::post()
+        .uri(&format!("/risks/{}/evaluation", risk_id))
+        .insert_header(bearer(&token))
+        .set_json(json!({ "severity": 0, "likelihood": 2, "detectability": 11 }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    let fields = body["details"]["fields"].as_object().unwrap();
+    assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["detectability", "severity"]);
+
+    let request = test::TestRequest::get().uri("/validation/rules").insert_header(bearer(&token));
+    let rules: Value = test::read_body_json(test::call_service(&app, request.to_request()).await).await;
+    assert_eq!(rules["impact_scale"], json!({ "min": 1, "max": 5 }));
+    assert_eq!(rules["probability_scale"], json!({ "min": 1, "max": 5 }));
+
+    delete_risk(&pool, risk_id).await;
+    delete_user(&pool, &user).await;
+}
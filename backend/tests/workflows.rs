@@ -0,0 +1,158 @@
+// Tests d'intégration de l'administration des workflows : le workflow par défaut reste actif
+
+mod common;
+
+use actix_web::http::header::ETAG;
+use actix_web::test;
+use backend::models::user::Role;
+use common::{bearer, create_user, delete_user, init_app, test_pool};
+use serde_json::{json, Value};
+
+#[actix_web::test]
+async fn the_default_workflow_cannot_be_deactivated_or_deleted() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Admin).await;
+    let app = init_app(&pool).await;
+
+    let list = || test::TestRequest::get().uri("/workflows").insert_header(bearer(&token)).to_request();
+    let workflows: Vec<Value> = test::read_body_json(test::call_service(&app, list()).await).await;
+    let default = workflows
+        .iter()
+        .find(|w| w["business_unit"].is_null() && w["is_active"] == json!(true))
+        .expect("workflow par défaut")
+        .clone();
+    let id = default["id"].as_str().unwrap().to_string();
+
+    let payload = |business_unit: Value, is_active: bool| {
+        json!({
+            "name": default["name"],
+            "business_unit": business_unit,
+            "states": default["states"],
+            "transitions": default["transitions"],
+            "is_active": is_active,
+        })
+    };
+    let version = format!("\"{}\"", default["version"]);
+    let put = |body: Value| {
+        test::TestRequest::put()
+            .uri(&format!("/workflows/{}", id))
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", version.clone()))
+            .set_json(body)
+            .to_request()
+    };
+
+    // Désactivé ou rattaché à une unité métier, il ne laisserait aucun workflow aux autres risques
+    assert_eq!(test::call_service(&app, put(payload(Value::Null, false))).await.status(), 409);
+    assert_eq!(test::call_service(&app, put(payload(json!("Unité de test"), true))).await.status(), 409);
+
+    let request = test::TestRequest::delete()
+        .uri(&format!("/workflows/{}", id))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", version.clone()));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 409);
+
+    let workflows: Vec<Value> = test::read_body_json(test::call_service(&app, list()).await).await;
+    let unchanged = workflows.iter().find(|w| w["id"] == default["id"]).unwrap();
+    assert_eq!((&unchanged["business_unit"], &unchanged["is_active"]), (&Value::Null, &json!(true)));
+
+    // Un workflow inactif d'unité métier se supprime librement
+    let request = test::TestRequest::post()
+        .uri("/workflows")
+        .insert_header(bearer(&token))
+        .set_json(payload(json!(format!("Unité {}", user.id)), false));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    let created: Value = test::read_body_json(response).await;
+
+    let request = test::TestRequest::delete()
+        .uri(&format!("/workflows/{}", created["id"].as_str().unwrap()))
+        .insert_header(bearer(&token))
+        .insert_header(("If-Match", "\"1\""));
+    assert_eq!(test::call_service(&app, request.to_request()).await.status(), 204);
+
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn invalid_definitions_are_rejected_field_by_field() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Admin).await;
+    let app = init_app(&pool).await;
+
+    let request = test::TestRequest::post()
+        .uri("/workflows")
+        .insert_header(bearer(&token))
+        .set_json(json!({
+            "name": " ",
+            "states": ["Identified", "Assessed"],
+            "transitions": [
+                { "from_status": "Identified", "to_status": "Closed" },
+                { "from_status": "Identified", "to_status": "Assessed", "required_fields": ["couleur"] },
+            ],
+        }));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 422);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["code"], "validation_failed");
+    let fields = body["details"]["fields"].as_object().unwrap();
+    assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["name", "transitions"]);
+    assert_eq!(fields["transitions"].as_array().unwrap().len(), 2);
+
+    delete_user(&pool, &user).await;
+}
+
+#[actix_web::test]
+async fn concurrent_workflow_edits_require_the_current_version() {
+    let pool = test_pool().await;
+    let (user, token) = create_user(&pool, Role::Admin).await;
+    let app = init_app(&pool).await;
+
+    let definition = |name: &str| {
+        json!({
+            "name": name,
+            "business_unit": format!("Unité {}", user.id),
+            "states": ["Identified", "Assessed"],
+            "transitions": [{ "from_status": "Identified", "to_status": "Assessed" }],
+        })
+    };
+    let request = test::TestRequest::post().uri("/workflows").insert_header(bearer(&token)).set_json(definition("Initial"));
+    let response = test::call_service(&app, request.to_request()).await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"1\"");
+    let created: Value = test::read_body_json(response).await;
+    let uri = format!("/workflows/{}", created["id"].as_str().unwrap());
+
+    let put = |if_match: Option<&str>, name: &str| {
+        let request = test::TestRequest::put().uri(&uri).insert_header(bearer(&token)).set_json(definition(name));
+        match if_match {
+            Some(value) => request.insert_header(("If-Match", value.to_string())),
+            None => request,
+        }
+        .to_request()
+    };
+
+    assert_eq!(test::call_service(&app, put(None, "Sans version")).await.status(), 428);
+
+    let response = test::call_service(&app, put(Some("\"1\""), "Premier administrateur")).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers().get(ETAG).unwrap(), "\"2\"");
+
+    // Le second administrateur part de la version 1 : sa modification n'écrase pas la première
+    let response = test::call_service(&app, put(Some("\"1\""), "Second administrateur")).await;
+    assert_eq!(response.status(), 412);
+    let body: Value = test::read_body_json(response).await;
+    assert_eq!(body["details"]["current"]["name"], "Premier administrateur");
+
+    let delete = |version: &str| {
+        test::TestRequest::delete()
+            .uri(&uri)
+            .insert_header(bearer(&token))
+            .insert_header(("If-Match", version.to_string()))
+            .to_request()
+    };
+    assert_eq!(test::call_service(&app, delete("\"1\"")).await.status(), 412);
+    assert_eq!(test::call_service(&app, delete("\"2\"")).await.status(), 204);
+
+    delete_user(&pool, &user).await;
+}
@@ -0,0 +1,37 @@
+use gloo_net::http::Response;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+// Corps des réponses d'erreur de l'API : { code, message, details }
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct ApiError {
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub details: Option<Value>,
+}
+
+impl ApiError {
+    // État courant de la ressource joint à un conflit de version (412)
+    pub fn current<T: DeserializeOwned>(&self) -> Option<T> {
+        let current = self.details.as_ref()?.get("current")?;
+        serde_json::from_value(current.clone()).ok()
+    }
+}
+
+pub async fn read_error(response: &Response) -> ApiError {
+    match response.json::<ApiError>().await {
+        Ok(error) => error,
+        Err(_) => ApiError {
+            code: "unknown".to_string(),
+            message: format!("Erreur du serveur ({})", response.status()),
+            details: None,
+        },
+    }
+}
+
+// Message à afficher à l'utilisateur pour une réponse en erreur
+pub async fn error_message(response: &Response) -> String {
+    read_error(response).await.message
+}
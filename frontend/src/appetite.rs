@@ -0,0 +1,55 @@
+use serde::Deserialize;
+
+// Seuils d'une catégorie, sur le score inhérent (`models::appetite::RiskAppetite`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct RiskAppetite {
+    pub id: String,
+    pub category: String,
+    pub appetite: i32,
+    pub tolerance: i32,
+    // Seuils des scores AMDEC et pondérés, absents si la catégorie n'en fixe pas
+    pub fmea_appetite: Option<i32>,
+    pub fmea_tolerance: Option<i32>,
+    pub weighted_appetite: Option<i32>,
+    pub weighted_tolerance: Option<i32>,
+    pub version: i32,
+}
+
+// Risque au-delà de la tolérance de sa catégorie (`GET /risks/out-of-tolerance`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct ToleranceExcess {
+    pub risk_id: String,
+    pub title: String,
+    pub status: String,
+    pub category: String,
+    pub scoring_method: String,
+    pub score: i32,
+    pub level: Option<String>,
+    pub appetite: i32,
+    pub tolerance: i32,
+    pub excess: i32,
+}
+
+// Dépassement de tolérance enregistré (`GET /appetite-breaches`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct AppetiteBreach {
+    pub id: String,
+    pub risk_id: String,
+    pub risk_title: String,
+    pub category: String,
+    pub scoring_method: String,
+    pub score: i32,
+    pub tolerance: i32,
+    pub trigger: String,
+    pub triggered_by_username: Option<String>,
+    pub created_at: String,
+}
+
+pub fn breach_trigger_label(trigger: &str) -> &'static str {
+    match trigger {
+        "Create" => "Création",
+        "Update" => "Modification",
+        "Evaluation" => "Évaluation",
+        _ => "Inconnu",
+    }
+}
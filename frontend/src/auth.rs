@@ -0,0 +1,67 @@
+use gloo_storage::{LocalStorage, Storage};
+
+const TOKEN_KEY: &str = "sentinelrisk.token";
+const USERNAME_KEY: &str = "sentinelrisk.username";
+const ROLE_KEY: &str = "sentinelrisk.role";
+const EXPIRES_AT_KEY: &str = "sentinelrisk.expires_at";
+
+// Session stockée dans le localStorage du navigateur après `POST /auth/login`
+pub fn save_session(token: &str, username: &str, role: &str, expires_at: &str) {
+    let _ = LocalStorage::set(TOKEN_KEY, token);
+    let _ = LocalStorage::set(USERNAME_KEY, username);
+    let _ = LocalStorage::set(ROLE_KEY, role);
+    let _ = LocalStorage::set(EXPIRES_AT_KEY, expires_at);
+}
+
+pub fn clear_session() {
+    LocalStorage::delete(TOKEN_KEY);
+    LocalStorage::delete(USERNAME_KEY);
+    LocalStorage::delete(ROLE_KEY);
+    LocalStorage::delete(EXPIRES_AT_KEY);
+}
+
+pub fn token() -> Option<String> {
+    let token: String = LocalStorage::get(TOKEN_KEY).ok()?;
+    let expires_at: String = LocalStorage::get(EXPIRES_AT_KEY).ok()?;
+    if js_sys::Date::parse(&expires_at) <= js_sys::Date::now() {
+        clear_session();
+        return None;
+    }
+    Some(token)
+}
+
+pub fn username() -> Option<String> {
+    LocalStorage::get(USERNAME_KEY).ok()
+}
+
+// Rang du rôle, dans l'ordre du backend : Viewer < Analyst < RiskOwner < Admin
+fn role_rank() -> u8 {
+    let role: String = LocalStorage::get(ROLE_KEY).unwrap_or_default();
+    match role.as_str() {
+        "Analyst" => 1,
+        "RiskOwner" => 2,
+        "Admin" => 3,
+        _ => 0,
+    }
+}
+
+pub fn can_edit() -> bool {
+    role_rank() >= 1
+}
+
+pub fn can_change_status() -> bool {
+    role_rank() >= 2
+}
+
+pub fn can_delete() -> bool {
+    role_rank() >= 3
+}
+
+pub fn is_authenticated() -> bool {
+    token().is_some()
+}
+
+// Valeur de l'en-tête Authorization à joindre à chaque appel de l'API
+pub fn bearer() -> String {
+    format!("Bearer {}", token().unwrap_or_default())
+}
@@ -0,0 +1,188 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use crate::api;
+use crate::auth;
+use serde::Deserialize;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{Blob, FormData, HtmlAnchorElement, HtmlInputElement, Url};
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+struct Attachment {
+    id: String,
+    filename: String,
+    content_type: String,
+    size_bytes: i64,
+    sha256: String,
+    created_at: String,
+}
+
+// Le téléchargement passe par fetch pour joindre le jeton, puis par une URL blob temporaire
+async fn download(id: String, filename: String) {
+    let Ok(response) = Request::get(&format!("http://localhost:8081/attachments/{}/download", id))
+        .header("Authorization", &auth::bearer())
+        .send()
+        .await
+    else {
+        return;
+    };
+    if !response.ok() {
+        return;
+    }
+    let Ok(bytes) = response.binary().await else { return };
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes.as_slice()));
+    let Ok(blob) = Blob::new_with_u8_array_sequence(&parts) else { return };
+    let Ok(url) = Url::create_object_url_with_blob(&blob) else { return };
+
+    if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+        if let Ok(element) = document.create_element("a") {
+            let anchor: HtmlAnchorElement = element.unchecked_into();
+            anchor.set_href(&url);
+            anchor.set_download(&filename);
+            anchor.click();
+        }
+    }
+    let _ = Url::revoke_object_url(&url);
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AttachmentPanelProps {
+    // Chemin de l'entité côté API, par ex. "risks/<id>" ou "incidents/<id>"
+    pub entity_path: String,
+}
+
+#[function_component(AttachmentPanel)]
+pub fn attachment_panel(props: &AttachmentPanelProps) -> Html {
+    let attachments = use_state(Vec::<Attachment>::new);
+    let message = use_state(|| None as Option<String>);
+    let refresh = use_state(|| 0u32);
+    let file_input = use_node_ref();
+
+    {
+        let attachments = attachments.clone();
+        let entity_path = props.entity_path.clone();
+        use_effect_with((entity_path.clone(), *refresh), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/{}/attachments", entity_path))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<Attachment>>().await {
+                        attachments.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_upload = {
+        let file_input = file_input.clone();
+        let message = message.clone();
+        let refresh = refresh.clone();
+        let entity_path = props.entity_path.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(input) = file_input.cast::<HtmlInputElement>() else { return };
+            let Some(file) = input.files().and_then(|files| files.get(0)) else {
+                message.set(Some("Veuillez choisir un fichier.".to_string()));
+                return;
+            };
+
+            let form_data = FormData::new().unwrap();
+            form_data.append_with_blob_and_filename("file", &file, &file.name()).unwrap();
+
+            let message = message.clone();
+            let refresh = refresh.clone();
+            let entity_path = entity_path.clone();
+            spawn_local(async move {
+                let resp = Request::post(&format!("http://localhost:8081/{}/attachments", entity_path))
+                    .header("Authorization", &auth::bearer())
+                    .body(form_data)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        message.set(Some("Fichier ajouté.".to_string()));
+                        refresh.set(*refresh + 1);
+                    }
+                    Ok(response) => message.set(Some(api::error_message(&response).await)),
+                    Err(_) => message.set(Some("Erreur réseau : impossible d'envoyer le fichier.".to_string())),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let refresh = refresh.clone();
+        Callback::from(move |id: String| {
+            let refresh = refresh.clone();
+            spawn_local(async move {
+                let res = Request::delete(&format!("http://localhost:8081/attachments/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+                if let Ok(response) = res {
+                    if response.ok() {
+                        refresh.set(*refresh + 1);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div>
+            <h3>{ "Pièces jointes" }</h3>
+            if attachments.is_empty() {
+                <p>{ "Aucune pièce jointe." }</p>
+            } else {
+                <ul>
+                    { for attachments.iter().map(|attachment| html! {
+                        <li key={attachment.id.clone()}>
+                            <a href="#" onclick={
+                                let id = attachment.id.clone();
+                                let filename = attachment.filename.clone();
+                                Callback::from(move |e: MouseEvent| {
+                                    e.prevent_default();
+                                    spawn_local(download(id.clone(), filename.clone()));
+                                })
+                            }>
+                                { &attachment.filename }
+                            </a>
+                            { format!(" ({}, {} octets, ajouté le {})", attachment.content_type, attachment.size_bytes, attachment.created_at) }
+                            <small title={attachment.sha256.clone()}>{ format!(" SHA-256 : {}…", &attachment.sha256[..12]) }</small>
+                            if auth::can_delete() {
+                                <button onclick={
+                                    let on_delete = on_delete.clone();
+                                    let id = attachment.id.clone();
+                                    Callback::from(move |_| {
+                                        if web_sys::window().unwrap().confirm_with_message("Supprimer cette pièce jointe ?").unwrap_or(false) {
+                                            on_delete.emit(id.clone());
+                                        }
+                                    })
+                                }>
+                                    { "🗑 Supprimer" }
+                                </button>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+            if auth::can_edit() {
+                <form onsubmit={on_upload}>
+                    <input type="file" ref={file_input} />
+                    <button type="submit">{ "📎 Joindre" }</button>
+                </form>
+            }
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,105 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use wasm_bindgen_futures::spawn_local;
+
+use crate::auth;
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+struct FieldChange {
+    before: Value,
+    after: Value,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+struct AuditEntry {
+    id: String,
+    action: String,
+    actor_username: String,
+    changes: Map<String, Value>,
+    created_at: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AuditTimelineProps {
+    // Type d'entité côté API : "risk", "incident" ou "control"
+    pub entity: String,
+    pub id: String,
+    // Incrémenté par le parent pour recharger le journal après une modification
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "∅".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(display_value).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+fn action_label(action: &str) -> &'static str {
+    match action {
+        "Create" => "🆕 Création",
+        "Update" => "✏ Modification",
+        "Delete" => "🗑 Suppression",
+        _ => "•",
+    }
+}
+
+#[function_component(AuditTimeline)]
+pub fn audit_timeline(props: &AuditTimelineProps) -> Html {
+    let entries = use_state(Vec::<AuditEntry>::new);
+
+    {
+        let entries = entries.clone();
+        let url = format!("http://localhost:8081/audit?entity={}&id={}", props.entity, props.id);
+        use_effect_with((url.clone(), props.refresh), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&url)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<AuditEntry>>().await {
+                        entries.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div>
+            <h3>{ "Historique des modifications" }</h3>
+            if entries.is_empty() {
+                <p>{ "Aucune modification enregistrée." }</p>
+            } else {
+                <ul class="list-unstyled border-start ps-3">
+                    { for entries.iter().rev().map(|entry| html! {
+                        <li key={entry.id.clone()} class="mb-3">
+                            <strong>{ format!("{} — {}", action_label(&entry.action), entry.actor_username) }</strong>
+                            <small class="text-muted">{ format!(" ({})", entry.created_at) }</small>
+                            if entry.action == "Update" {
+                                <ul>
+                                    { for entry.changes.iter().filter_map(|(field, change)| {
+                                        let change: FieldChange = serde_json::from_value(change.clone()).ok()?;
+                                        Some(html! {
+                                            <li key={field.clone()}>
+                                                { format!("{} : {} → {}", field, display_value(&change.before), display_value(&change.after)) }
+                                            </li>
+                                        })
+                                    }) }
+                                </ul>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+        </div>
+    }
+}
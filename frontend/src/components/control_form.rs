@@ -0,0 +1,167 @@
+use yew::prelude::*;
+use serde::Serialize;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::components::risk_picker::RiskChecklist;
+use crate::control::{effectiveness_label, frequency_label, type_label, Control, CONTROL_TYPES, EFFECTIVENESS, FREQUENCIES};
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
+
+// Corps de `POST /controls` et `PUT /controls/{id}`
+#[derive(Serialize, Clone, PartialEq)]
+pub struct ControlData {
+    pub name: String,
+    pub description: Option<String>,
+    pub control_type: String,
+    pub owner: Option<String>,
+    pub frequency: Option<String>,
+    pub design_effectiveness: String,
+    pub operating_effectiveness: String,
+    pub risk_ids: Vec<String>,
+}
+
+impl Default for ControlData {
+    fn default() -> Self {
+        ControlData {
+            name: String::new(),
+            description: None,
+            control_type: "Preventive".to_string(),
+            owner: None,
+            frequency: None,
+            design_effectiveness: "NotTested".to_string(),
+            operating_effectiveness: "NotTested".to_string(),
+            risk_ids: Vec::new(),
+        }
+    }
+}
+
+impl From<&Control> for ControlData {
+    fn from(control: &Control) -> Self {
+        ControlData {
+            name: control.name.clone(),
+            description: control.description.clone(),
+            control_type: control.control_type.clone(),
+            owner: control.owner.clone(),
+            frequency: control.frequency.clone(),
+            design_effectiveness: control.design_effectiveness.clone(),
+            operating_effectiveness: control.operating_effectiveness.clone(),
+            risk_ids: control.risk_ids.clone(),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ControlFormProps {
+    pub data: ControlData,
+    pub on_change: Callback<ControlData>,
+    // Données validées côté client, prêtes à être envoyées
+    pub on_submit: Callback<ControlData>,
+    // Erreurs par champ renvoyées par l'API (422)
+    #[prop_or_default]
+    pub errors: FieldErrors,
+    pub submit_label: AttrValue,
+}
+
+#[function_component(ControlForm)]
+pub fn control_form(props: &ControlFormProps) -> Html {
+    let rules = use_validation_rules();
+    let local_errors = use_state(FieldErrors::new);
+
+    let update = |apply: fn(&mut ControlData, String)| {
+        let data = props.data.clone();
+        let on_change = props.on_change.clone();
+        move |value: String| {
+            let mut updated = data.clone();
+            apply(&mut updated, value);
+            on_change.emit(updated);
+        }
+    };
+    let on_text = |apply: fn(&mut ControlData, String)| {
+        let update = update(apply);
+        Callback::from(move |e: InputEvent| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_select = |apply: fn(&mut ControlData, String)| {
+        let update = update(apply);
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+
+    let on_risks = {
+        let data = props.data.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |risk_ids: Vec<String>| {
+            on_change.emit(ControlData { risk_ids, ..data.clone() })
+        })
+    };
+
+    let on_submit = {
+        let data = props.data.clone();
+        let on_submit = props.on_submit.clone();
+        let local_errors = local_errors.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let invalid = validation::validate_control(&data.name, &rules);
+            let is_valid = invalid.is_empty();
+            local_errors.set(invalid);
+            if is_valid {
+                on_submit.emit(data.clone());
+            }
+        })
+    };
+
+    // Erreurs locales prioritaires, puis celles du serveur
+    let mut errors = props.errors.clone();
+    errors.extend((*local_errors).clone());
+
+    let effectiveness_select = |selected: &str, onchange: Callback<Event>| html! {
+        <select {onchange}>
+            { for EFFECTIVENESS.iter().map(|value| html! {
+                <option value={*value} selected={selected == *value}>{ effectiveness_label(value) }</option>
+            }) }
+        </select>
+    };
+
+    html! {
+        <form onsubmit={on_submit}>
+            <input type="text" placeholder="Nom" value={props.data.name.clone()} oninput={on_text(|d, v| d.name = v)} />
+            <FieldError errors={errors.clone()} field="name" />
+            <input
+                type="text"
+                placeholder="Description"
+                value={props.data.description.clone().unwrap_or_default()}
+                oninput={on_text(|d, v| d.description = Some(v).filter(|v| !v.is_empty()))}
+            />
+            <label>{ "Type : " }
+                <select onchange={on_select(|d, v| d.control_type = v)}>
+                    { for CONTROL_TYPES.iter().map(|value| html! {
+                        <option value={*value} selected={props.data.control_type == *value}>{ type_label(value) }</option>
+                    }) }
+                </select>
+            </label>
+            <input
+                type="text"
+                placeholder="Responsable"
+                value={props.data.owner.clone().unwrap_or_default()}
+                oninput={on_text(|d, v| d.owner = Some(v).filter(|v| !v.trim().is_empty()))}
+            />
+            <label>{ "Fréquence : " }
+                <select onchange={on_select(|d, v| d.frequency = Some(v).filter(|v| !v.is_empty()))}>
+                    <option value="" selected={props.data.frequency.is_none()}>{ "Non précisée" }</option>
+                    { for FREQUENCIES.iter().map(|value| html! {
+                        <option value={*value} selected={props.data.frequency.as_deref() == Some(*value)}>{ frequency_label(value) }</option>
+                    }) }
+                </select>
+            </label>
+            <label>{ "Efficacité de conception : " }
+                { effectiveness_select(&props.data.design_effectiveness, on_select(|d, v| d.design_effectiveness = v)) }
+            </label>
+            <label>{ "Efficacité de fonctionnement : " }
+                { effectiveness_select(&props.data.operating_effectiveness, on_select(|d, v| d.operating_effectiveness = v)) }
+            </label>
+            <fieldset>
+                <legend>{ "Risques couverts" }</legend>
+                <RiskChecklist selected={props.data.risk_ids.clone()} on_change={on_risks} />
+            </fieldset>
+            <FieldError errors={errors} field="risk_ids" />
+            <button type="submit">{ props.submit_label.clone() }</button>
+        </form>
+    }
+}
@@ -0,0 +1,276 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::api;
+use crate::auth;
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Evaluation {
+    id: String,
+    basis: String,
+    severity: i32,
+    likelihood: i32,
+    detectability: i32,
+    score: i32,
+    risk_score: Option<i32>,
+    notes: Option<String>,
+    evaluated_by_username: Option<String>,
+    created_at: String,
+}
+
+#[derive(Serialize, Clone, PartialEq)]
+struct EvaluationPayload {
+    basis: String,
+    severity: i32,
+    likelihood: i32,
+    detectability: i32,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    notes: String,
+}
+
+impl Default for EvaluationPayload {
+    fn default() -> Self {
+        EvaluationPayload { basis: "Inherent".to_string(), severity: 1, likelihood: 1, detectability: 1, notes: String::new() }
+    }
+}
+
+// Bases d'évaluation (`ScoreBasis` côté backend), libellé et couleur de la courbe
+const BASES: &[(&str, &str, &str)] = &[
+    ("Inherent", "Inhérent", "#1565c0"),
+    ("Residual", "Résiduel", "#2e7d32"),
+    ("Target", "Cible", "#ef6c00"),
+];
+
+fn basis_label(basis: &str) -> &str {
+    BASES.iter().find(|(value, _, _)| *value == basis).map_or(basis, |(_, label, _)| label)
+}
+
+const CHART_WIDTH: f64 = 480.0;
+const CHART_HEIGHT: f64 = 160.0;
+const CHART_MARGIN: f64 = 24.0;
+
+// Courbes du score du risque après chaque évaluation, une par base, abscisses proportionnelles aux dates
+fn score_chart(evaluations: &[Evaluation]) -> Html {
+    let points: Vec<(f64, i32, &Evaluation)> = evaluations
+        .iter()
+        .filter_map(|e| Some((js_sys::Date::parse(&e.created_at), e.risk_score?, e)))
+        .filter(|(time, _, _)| !time.is_nan())
+        .collect();
+    if points.len() < 2 {
+        return html! {};
+    }
+
+    let first = points.iter().map(|(time, _, _)| *time).fold(f64::INFINITY, f64::min);
+    let last = points.iter().map(|(time, _, _)| *time).fold(f64::NEG_INFINITY, f64::max);
+    let max_score = points.iter().map(|(_, score, _)| *score).max().unwrap_or(1).max(1);
+    let x = |time: f64| {
+        let span = (last - first).max(1.0);
+        CHART_MARGIN + (time - first) / span * (CHART_WIDTH - 2.0 * CHART_MARGIN)
+    };
+    let y = |score: i32| CHART_HEIGHT - CHART_MARGIN - f64::from(score) / f64::from(max_score) * (CHART_HEIGHT - 2.0 * CHART_MARGIN);
+
+    html! {
+        <svg width={CHART_WIDTH.to_string()} height={CHART_HEIGHT.to_string()} style="border: 1px solid #ccc;">
+            <text x="4" y="14" font-size="11">{ format!("max {}", max_score) }</text>
+            { for BASES.iter().enumerate().map(|(index, (basis, label, color))| {
+                let series: Vec<&(f64, i32, &Evaluation)> = points.iter().filter(|(_, _, e)| e.basis == *basis).collect();
+                let polyline = series
+                    .iter()
+                    .map(|(time, score, _)| format!("{:.1},{:.1}", x(*time), y(*score)))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                html! {
+                    <g key={*basis}>
+                        if !series.is_empty() {
+                            <text x={(CHART_WIDTH - 80.0).to_string()} y={(14 + 12 * index).to_string()} font-size="11" fill={*color}>{ *label }</text>
+                        }
+                        <polyline points={polyline} fill="none" stroke={*color} stroke-width="2" />
+                        { for series.iter().map(|(time, score, e)| html! {
+                            <circle key={e.id.clone()} cx={format!("{:.1}", x(*time))} cy={format!("{:.1}", y(*score))} r="3" fill={*color}>
+                                <title>{ format!("{} ({}) : {}", e.created_at, label, score) }</title>
+                            </circle>
+                        }) }
+                    </g>
+                }
+            }) }
+        </svg>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct EvaluationHistoryProps {
+    pub risk_id: String,
+    #[prop_or_default]
+    pub refresh: u32,
+    // Appelé après une nouvelle évaluation (le score du risque a pu changer)
+    pub on_evaluated: Callback<()>,
+}
+
+// Historique des évaluations (`GET /risks/{id}/evaluations`) et saisie d'une nouvelle évaluation
+#[function_component(EvaluationHistory)]
+pub fn evaluation_history(props: &EvaluationHistoryProps) -> Html {
+    let evaluations = use_state(Vec::<Evaluation>::new);
+    let form = use_state(EvaluationPayload::default);
+    let errors = use_state(FieldErrors::new);
+    let message = use_state(|| None as Option<String>);
+    let rules = use_validation_rules();
+
+    {
+        let evaluations = evaluations.clone();
+        let risk_id = props.risk_id.clone();
+        use_effect_with((risk_id.clone(), props.refresh), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/risks/{}/evaluations", risk_id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<Evaluation>>().await {
+                        evaluations.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_input = |field: &'static str| {
+        let form = form.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form).clone();
+            match field {
+                "severity" => data.severity = input.value().parse().unwrap_or(0),
+                "likelihood" => data.likelihood = input.value().parse().unwrap_or(0),
+                "detectability" => data.detectability = input.value().parse().unwrap_or(0),
+                "notes" => data.notes = input.value(),
+                _ => {}
+            }
+            form.set(data);
+        })
+    };
+
+    let on_basis = {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let mut data = (*form).clone();
+            data.basis = e.target_unchecked_into::<HtmlSelectElement>().value();
+            form.set(data);
+        })
+    };
+
+    let on_submit = {
+        let form = form.clone();
+        let errors = errors.clone();
+        let message = message.clone();
+        let rules = rules.clone();
+        let risk_id = props.risk_id.clone();
+        let on_evaluated = props.on_evaluated.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let payload = (*form).clone();
+            let local = validation::validate_evaluation(payload.severity, payload.likelihood, payload.detectability, &rules);
+            if !local.is_empty() {
+                errors.set(local);
+                return;
+            }
+            let form = form.clone();
+            let errors = errors.clone();
+            let message = message.clone();
+            let risk_id = risk_id.clone();
+            let on_evaluated = on_evaluated.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let resp = Request::post(&format!("http://localhost:8081/risks/{}/evaluation", risk_id))
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        errors.set(FieldErrors::new());
+                        message.set(None);
+                        form.set(EvaluationPayload::default());
+                        on_evaluated.emit(());
+                    }
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : évaluation non enregistrée.".to_string())),
+                }
+            });
+        })
+    };
+
+    let scale = rules.evaluation_scale;
+    let number = |field: &'static str, label: &str, value: i32| html! {
+        <label>{ format!("{} ({}-{}) : ", label, scale.min, scale.max) }
+            <input
+                type="number"
+                min={scale.min.to_string()}
+                max={scale.max.to_string()}
+                value={value.to_string()}
+                oninput={on_input(field)}
+            />
+            <FieldError errors={(*errors).clone()} field={field} />
+        </label>
+    };
+
+    html! {
+        <div>
+            <h3>{ "Historique des évaluations" }</h3>
+            { score_chart(&evaluations) }
+            if evaluations.is_empty() {
+                <p>{ "Aucune évaluation." }</p>
+            } else {
+                <ul>
+                    { for evaluations.iter().rev().map(|e| html! {
+                        <li key={e.id.clone()}>
+                            { format!(
+                                "{} [{}] — gravité {}, vraisemblance {}, détectabilité {} (AMDEC {}) : score du risque {}",
+                                e.created_at,
+                                basis_label(&e.basis),
+                                e.severity,
+                                e.likelihood,
+                                e.detectability,
+                                e.score,
+                                e.risk_score.map(|score| score.to_string()).unwrap_or_else(|| "–".to_string()),
+                            ) }
+                            if let Some(evaluator) = &e.evaluated_by_username {
+                                { format!(", par {}", evaluator) }
+                            }
+                            if let Some(notes) = &e.notes {
+                                <p><small>{ notes }</small></p>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+            if auth::can_edit() {
+                <form onsubmit={on_submit}>
+                    <select onchange={on_basis}>
+                        { for BASES.iter().map(|(value, label, _)| html! {
+                            <option value={*value} selected={form.basis == *value}>{ format!("Risque {}", label.to_lowercase()) }</option>
+                        }) }
+                    </select>
+                    { number("severity", "Gravité", form.severity) }
+                    { number("likelihood", "Vraisemblance", form.likelihood) }
+                    { number("detectability", "Détectabilité", form.detectability) }
+                    <input type="text" placeholder="Notes" value={form.notes.clone()} oninput={on_input("notes")} />
+                    <button type="submit">{ "Ajouter une évaluation" }</button>
+                </form>
+            }
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,151 @@
+use yew::prelude::*;
+use serde::Serialize;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::components::risk_picker::{RiskChecklist, RiskPicker};
+use crate::incident::{severity_label, Incident, SEVERITIES};
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
+
+// Corps de `POST /incidents` et `PUT /incidents/{id}` : le statut suit le cycle de vie à part
+#[derive(Serialize, Clone, PartialEq)]
+pub struct IncidentData {
+    pub title: String,
+    pub description: Option<String>,
+    pub severity: String,
+    pub related_risk_id: Option<String>,
+    pub risk_ids: Vec<String>,
+}
+
+impl Default for IncidentData {
+    fn default() -> Self {
+        IncidentData {
+            title: String::new(),
+            description: None,
+            severity: "Medium".to_string(),
+            related_risk_id: None,
+            risk_ids: Vec::new(),
+        }
+    }
+}
+
+impl From<&Incident> for IncidentData {
+    fn from(incident: &Incident) -> Self {
+        IncidentData {
+            title: incident.title.clone(),
+            description: incident.description.clone(),
+            severity: incident.severity.clone(),
+            related_risk_id: incident.related_risk_id.clone(),
+            risk_ids: incident.risk_ids.clone(),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct IncidentFormProps {
+    pub data: IncidentData,
+    pub on_change: Callback<IncidentData>,
+    // Données validées côté client, prêtes à être envoyées
+    pub on_submit: Callback<IncidentData>,
+    // Erreurs par champ renvoyées par l'API (422)
+    #[prop_or_default]
+    pub errors: FieldErrors,
+    pub submit_label: AttrValue,
+}
+
+#[function_component(IncidentForm)]
+pub fn incident_form(props: &IncidentFormProps) -> Html {
+    let rules = use_validation_rules();
+    let local_errors = use_state(FieldErrors::new);
+
+    let update = |apply: fn(&mut IncidentData, String)| {
+        let data = props.data.clone();
+        let on_change = props.on_change.clone();
+        move |value: String| {
+            let mut updated = data.clone();
+            apply(&mut updated, value);
+            on_change.emit(updated);
+        }
+    };
+
+    let on_title = {
+        let update = update(|d, v| d.title = v);
+        Callback::from(move |e: InputEvent| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_description = {
+        let update = update(|d, v| d.description = Some(v).filter(|v| !v.is_empty()));
+        Callback::from(move |e: InputEvent| update(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_severity = {
+        let update = update(|d, v| d.severity = v);
+        Callback::from(move |e: Event| update(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+    let on_risk = {
+        let data = props.data.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |risk_id: Option<String>| {
+            on_change.emit(IncidentData { related_risk_id: risk_id, ..data.clone() })
+        })
+    };
+
+    let on_risks = {
+        let data = props.data.clone();
+        let on_change = props.on_change.clone();
+        Callback::from(move |risk_ids: Vec<String>| {
+            on_change.emit(IncidentData { risk_ids, ..data.clone() })
+        })
+    };
+
+    let on_submit = {
+        let data = props.data.clone();
+        let on_submit = props.on_submit.clone();
+        let local_errors = local_errors.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let invalid = validation::validate_incident(&data.title, &rules);
+            let is_valid = invalid.is_empty();
+            local_errors.set(invalid);
+            if is_valid {
+                on_submit.emit(data.clone());
+            }
+        })
+    };
+
+    // Erreurs locales prioritaires, puis celles du serveur
+    let mut errors = props.errors.clone();
+    errors.extend((*local_errors).clone());
+
+    html! {
+        <form onsubmit={on_submit}>
+            <input type="text" placeholder="Titre" value={props.data.title.clone()} oninput={on_title} />
+            <FieldError errors={errors.clone()} field="title" />
+            <input
+                type="text"
+                placeholder="Description"
+                value={props.data.description.clone().unwrap_or_default()}
+                oninput={on_description}
+            />
+            <label>{ "Gravité : " }
+                <select onchange={on_severity}>
+                    { for SEVERITIES.iter().map(|severity| html! {
+                        <option value={*severity} selected={props.data.severity == *severity}>{ severity_label(severity) }</option>
+                    }) }
+                </select>
+            </label>
+            <FieldError errors={errors.clone()} field="severity" />
+            <label>{ "Risque principal : " }
+                <RiskPicker selected={props.data.related_risk_id.clone()} on_change={on_risk} />
+            </label>
+            <FieldError errors={errors.clone()} field="related_risk_id" />
+            <fieldset>
+                <legend>{ "Autres risques liés" }</legend>
+                <RiskChecklist
+                    selected={props.data.risk_ids.clone()}
+                    locked={props.data.related_risk_id.clone()}
+                    on_change={on_risks}
+                />
+            </fieldset>
+            <FieldError errors={errors} field="risk_ids" />
+            <button type="submit">{ props.submit_label.clone() }</button>
+        </form>
+    }
+}
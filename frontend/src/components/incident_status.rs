@@ -0,0 +1,191 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::api;
+use crate::auth;
+use crate::incident::{next_statuses, status_label};
+
+#[derive(Serialize)]
+struct StatusPayload {
+    status: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    comment: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct IncidentStatusChangeProps {
+    pub incident_id: String,
+    pub current_status: String,
+    // Version de l'incident affiché, envoyée en If-Match
+    pub version: i32,
+    pub on_changed: Callback<()>,
+}
+
+// Passage à l'étape suivante du cycle de vie de l'incident
+#[function_component(IncidentStatusChange)]
+pub fn incident_status_change(props: &IncidentStatusChangeProps) -> Html {
+    let options = next_statuses(&props.current_status);
+    let selected = use_state(|| options.first().map(|s| s.to_string()).unwrap_or_default());
+    let comment = use_state(String::new);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        // Le statut courant change après chaque transition : on repart de la première étape possible
+        let selected = selected.clone();
+        use_effect_with(props.current_status.clone(), move |status| {
+            selected.set(next_statuses(status).first().map(|s| s.to_string()).unwrap_or_default());
+            || ()
+        });
+    }
+
+    let on_select = {
+        let selected = selected.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            selected.set(select.value());
+        })
+    };
+
+    let on_comment = {
+        let comment = comment.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            comment.set(input.value());
+        })
+    };
+
+    let on_submit = {
+        let selected = selected.clone();
+        let comment = comment.clone();
+        let message = message.clone();
+        let incident_id = props.incident_id.clone();
+        let version = props.version;
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let payload = StatusPayload { status: (*selected).clone(), comment: (*comment).clone() };
+            let comment = comment.clone();
+            let message = message.clone();
+            let incident_id = incident_id.clone();
+            let on_changed = on_changed.clone();
+            spawn_local(async move {
+                let resp = Request::patch(&format!("http://localhost:8081/incidents/{}/status", incident_id))
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .header("If-Match", &format!("\"{}\"", version))
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        message.set(None);
+                        comment.set(String::new());
+                        on_changed.emit(());
+                    }
+                    Ok(response) if response.status() == 412 => {
+                        message.set(Some("L'incident a été modifié entre-temps : vérifiez son état puis réessayez.".to_string()));
+                        on_changed.emit(());
+                    }
+                    Ok(response) => message.set(Some(api::error_message(&response).await)),
+                    Err(_) => message.set(Some("Erreur réseau : impossible de changer le statut.".to_string())),
+                }
+            });
+        })
+    };
+
+    if options.is_empty() {
+        return html! { <p>{ "Incident clos : plus aucune transition possible." }</p> };
+    }
+
+    html! {
+        <form onsubmit={on_submit}>
+            <select onchange={on_select}>
+                { for options.iter().map(|status| html! {
+                    <option value={*status} selected={*selected == *status}>{ status_label(status) }</option>
+                }) }
+            </select>
+            <input type="text" placeholder="Commentaire" value={(*comment).clone()} oninput={on_comment} />
+            <button type="submit">{ "Changer le statut" }</button>
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </form>
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+struct IncidentStatusEntry {
+    id: String,
+    old_status: String,
+    new_status: String,
+    comment: Option<String>,
+    changed_by_username: Option<String>,
+    changed_at: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct IncidentHistoryProps {
+    pub incident_id: String,
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+#[function_component(IncidentHistory)]
+pub fn incident_history(props: &IncidentHistoryProps) -> Html {
+    let entries = use_state(Vec::<IncidentStatusEntry>::new);
+
+    {
+        let entries = entries.clone();
+        let url = format!("http://localhost:8081/incidents/{}/history", props.incident_id);
+        use_effect_with((url.clone(), props.refresh), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&url)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<IncidentStatusEntry>>().await {
+                        entries.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div>
+            <h3>{ "Historique des statuts" }</h3>
+            if entries.is_empty() {
+                <p>{ "Aucun changement de statut." }</p>
+            } else {
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Date" }</th>
+                            <th>{ "Transition" }</th>
+                            <th>{ "Auteur" }</th>
+                            <th>{ "Commentaire" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for entries.iter().rev().map(|entry| html! {
+                            <tr key={entry.id.clone()}>
+                                <td>{ &entry.changed_at }</td>
+                                <td>{ format!("{} → {}", status_label(&entry.old_status), status_label(&entry.new_status)) }</td>
+                                <td>{ entry.changed_by_username.clone().unwrap_or_else(|| "—".to_string()) }</td>
+                                <td>{ entry.comment.clone().unwrap_or_default() }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+        </div>
+    }
+}
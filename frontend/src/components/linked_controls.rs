@@ -0,0 +1,150 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlSelectElement;
+
+use crate::api;
+use crate::auth;
+use crate::control::{effectiveness_label, type_label, Control};
+use crate::Route;
+
+#[derive(Properties, PartialEq)]
+pub struct LinkedControlsProps {
+    pub risk_id: String,
+    // Appelé après un ajout ou un retrait (le risque résiduel a pu changer)
+    pub on_changed: Callback<()>,
+}
+
+// Contrôles couvrant un risque (`GET /risks/{id}/controls`), avec ajout et retrait depuis la bibliothèque
+#[function_component(LinkedControls)]
+pub fn linked_controls(props: &LinkedControlsProps) -> Html {
+    let linked = use_state(Vec::<Control>::new);
+    let library = use_state(Vec::<Control>::new);
+    let selected = use_state(String::new);
+    let reload = use_state(|| 0u32);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        let linked = linked.clone();
+        let library = library.clone();
+        let risk_id = props.risk_id.clone();
+        use_effect_with((risk_id.clone(), *reload), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/risks/{}/controls", risk_id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<Control>>().await {
+                        linked.set(data);
+                    }
+                }
+
+                if auth::can_edit() {
+                    let response = Request::get("http://localhost:8081/controls")
+                        .query([("sort", "name"), ("per_page", "200")])
+                        .header("Authorization", &auth::bearer())
+                        .send()
+                        .await;
+                    if let Ok(resp) = response {
+                        if let Ok(data) = resp.json::<Vec<Control>>().await {
+                            library.set(data);
+                        }
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    // Ajout (POST) ou retrait (DELETE) du lien risque-contrôle
+    let toggle = {
+        let reload = reload.clone();
+        let message = message.clone();
+        let selected = selected.clone();
+        let risk_id = props.risk_id.clone();
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |(control_id, attach): (String, bool)| {
+            let reload = reload.clone();
+            let message = message.clone();
+            let selected = selected.clone();
+            let on_changed = on_changed.clone();
+            let url = format!("http://localhost:8081/risks/{}/controls/{}", risk_id, control_id);
+            spawn_local(async move {
+                let request = if attach { Request::post(&url) } else { Request::delete(&url) };
+                match request.header("Authorization", &auth::bearer()).send().await {
+                    Ok(response) if response.ok() => {
+                        message.set(None);
+                        selected.set(String::new());
+                        reload.set(*reload + 1);
+                        on_changed.emit(());
+                    }
+                    Ok(response) => message.set(Some(api::error_message(&response).await)),
+                    Err(_) => message.set(Some("Erreur réseau : lien non modifié.".to_string())),
+                }
+            });
+        })
+    };
+
+    let on_select = {
+        let selected = selected.clone();
+        Callback::from(move |e: Event| selected.set(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+
+    let on_attach = {
+        let toggle = toggle.clone();
+        let selected = selected.clone();
+        Callback::from(move |_: MouseEvent| {
+            if !selected.is_empty() {
+                toggle.emit(((*selected).clone(), true));
+            }
+        })
+    };
+
+    let available: Vec<&Control> = library.iter().filter(|c| !linked.iter().any(|l| l.id == c.id)).collect();
+
+    html! {
+        <div>
+            <h3>{ "Contrôles" }</h3>
+            if linked.is_empty() {
+                <p>{ "Aucun contrôle ne couvre ce risque." }</p>
+            } else {
+                <ul>
+                    { for linked.iter().map(|control| html! {
+                        <li key={control.id.clone()}>
+                            <Link<Route> to={Route::ViewControl { id: control.id.clone() }}>{ &control.name }</Link<Route>>
+                            { format!(
+                                " — {} (conception : {}, fonctionnement : {})",
+                                type_label(&control.control_type),
+                                effectiveness_label(&control.design_effectiveness),
+                                effectiveness_label(&control.operating_effectiveness),
+                            ) }
+                            if auth::can_edit() {
+                                <button onclick={
+                                    let toggle = toggle.clone();
+                                    let id = control.id.clone();
+                                    Callback::from(move |_| toggle.emit((id.clone(), false)))
+                                }>
+                                    { "Retirer" }
+                                </button>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+            if auth::can_edit() && !available.is_empty() {
+                <select onchange={on_select}>
+                    <option value="" selected={selected.is_empty()}>{ "Choisir un contrôle" }</option>
+                    { for available.iter().map(|control| html! {
+                        <option value={control.id.clone()} selected={*selected == control.id}>{ &control.name }</option>
+                    }) }
+                </select>
+                <button onclick={on_attach}>{ "Lier le contrôle" }</button>
+            }
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
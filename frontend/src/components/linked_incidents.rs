@@ -0,0 +1,59 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::auth;
+use crate::incident::{severity_label, status_label, Incident};
+use crate::Route;
+
+#[derive(Properties, PartialEq)]
+pub struct LinkedIncidentsProps {
+    pub risk_id: String,
+}
+
+// Incidents liés à un risque, comme risque principal ou secondaire
+#[function_component(LinkedIncidents)]
+pub fn linked_incidents(props: &LinkedIncidentsProps) -> Html {
+    let incidents = use_state(Vec::<Incident>::new);
+
+    {
+        let incidents = incidents.clone();
+        let url = format!("http://localhost:8081/incidents?related_risk_id={}", props.risk_id);
+        use_effect_with(url.clone(), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&url)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<Incident>>().await {
+                        incidents.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div>
+            <h3>{ "Incidents liés" }</h3>
+            if incidents.is_empty() {
+                <p>{ "Aucun incident lié à ce risque." }</p>
+            } else {
+                <ul>
+                    { for incidents.iter().map(|incident| html! {
+                        <li key={incident.id.clone()}>
+                            <Link<Route> to={Route::ViewIncident { id: incident.id.clone() }}>
+                                { &incident.title }
+                            </Link<Route>>
+                            { format!(" — {} / {}", severity_label(&incident.severity), status_label(&incident.status)) }
+                        </li>
+                    }) }
+                </ul>
+            }
+        </div>
+    }
+}
@@ -1 +1,17 @@
-pub mod sidebar;
\ No newline at end of file
+pub mod sidebar;
+pub mod attachment_panel;
+pub mod status_change;
+pub mod audit_timeline;
+pub mod status_history;
+pub mod risk_picker;
+pub mod incident_form;
+pub mod incident_status;
+pub mod linked_incidents;
+pub mod probability_suggestion;
+pub mod pager;
+pub mod scoring_method;
+pub mod risk_level;
+pub mod evaluation_history;
+pub mod control_form;
+pub mod linked_controls;
+pub mod treatment_plans;
@@ -0,0 +1,48 @@
+use yew::prelude::*;
+
+// Taille de page demandée aux listes paginées de l'API
+pub const PER_PAGE: u32 = 20;
+
+// Nombre total de résultats, lu dans l'en-tête `X-Total-Count`
+pub fn total_count(response: &gloo_net::http::Response) -> u32 {
+    response
+        .headers()
+        .get("x-total-count")
+        .and_then(|total| total.parse().ok())
+        .unwrap_or(0)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PagerProps {
+    // Page courante, à partir de 1
+    pub page: u32,
+    pub total: u32,
+    pub on_page: Callback<u32>,
+}
+
+#[function_component(Pager)]
+pub fn pager(props: &PagerProps) -> Html {
+    let pages = props.total.div_ceil(PER_PAGE).max(1);
+    let previous = {
+        let on_page = props.on_page.clone();
+        let page = props.page;
+        Callback::from(move |_| on_page.emit(page - 1))
+    };
+    let next = {
+        let on_page = props.on_page.clone();
+        let page = props.page;
+        Callback::from(move |_| on_page.emit(page + 1))
+    };
+
+    html! {
+        <div class="d-flex align-items-center gap-2">
+            <button class="btn btn-sm btn-outline-secondary" disabled={props.page <= 1} onclick={previous}>
+                { "◀ Précédent" }
+            </button>
+            <span>{ format!("Page {} / {} ({} résultat(s))", props.page, pages, props.total) }</span>
+            <button class="btn btn-sm btn-outline-secondary" disabled={props.page >= pages} onclick={next}>
+                { "Suivant ▶" }
+            </button>
+        </div>
+    }
+}
@@ -0,0 +1,89 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+
+use crate::api;
+use crate::auth;
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Suggestion {
+    current_probability: i32,
+    suggested_probability: i32,
+    window_days: i32,
+    incident_count: i64,
+    annual_frequency: f64,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ProbabilitySuggestionProps {
+    pub risk_id: String,
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+// Probabilité suggérée d'après les incidents liés (`GET /risks/{id}/probability-suggestion`)
+#[function_component(ProbabilitySuggestion)]
+pub fn probability_suggestion(props: &ProbabilitySuggestionProps) -> Html {
+    let suggestion = use_state(|| None::<Suggestion>);
+    let window_days = use_state(|| None::<i32>);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        let suggestion = suggestion.clone();
+        let message = message.clone();
+        let mut url = format!("http://localhost:8081/risks/{}/probability-suggestion", props.risk_id);
+        if let Some(days) = *window_days {
+            url.push_str(&format!("?window_days={}", days));
+        }
+        use_effect_with((url.clone(), props.refresh), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&url)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                match response {
+                    Ok(resp) if resp.ok() => {
+                        if let Ok(data) = resp.json::<Suggestion>().await {
+                            message.set(None);
+                            suggestion.set(Some(data));
+                        }
+                    }
+                    Ok(resp) => message.set(Some(api::error_message(&resp).await)),
+                    Err(_) => message.set(Some("Erreur réseau : suggestion indisponible.".to_string())),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_window = {
+        let window_days = window_days.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            window_days.set(input.value().parse().ok());
+        })
+    };
+
+    html! {
+        <div>
+            <h3>{ "Probabilité suggérée" }</h3>
+            if let Some(s) = &*suggestion {
+                <label>{ "Fenêtre (jours) : " }
+                    <input type="number" min="1" value={s.window_days.to_string()} onchange={on_window} />
+                </label>
+                <p>
+                    { format!(
+                        "{} incident(s) sur {} jours, fréquence pondérée de {:.1} par an : probabilité suggérée {} (actuelle {}).",
+                        s.incident_count, s.window_days, s.annual_frequency, s.suggested_probability, s.current_probability
+                    ) }
+                </p>
+            }
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,68 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+
+use crate::auth;
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct RiskLevel {
+    pub name: String,
+    pub min_score: i32,
+    pub color: String,
+}
+
+#[derive(Deserialize)]
+struct ScoringConfig {
+    levels: Vec<RiskLevel>,
+}
+
+// Niveaux configurés côté backend (`GET /scoring/config`), triés par seuil croissant
+#[hook]
+pub fn use_risk_levels() -> UseStateHandle<Vec<RiskLevel>> {
+    let levels = use_state(Vec::<RiskLevel>::new);
+
+    {
+        let levels = levels.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get("http://localhost:8081/scoring/config")
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(config) = resp.json::<ScoringConfig>().await {
+                        levels.set(config.levels);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    levels
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RiskLevelBadgeProps {
+    pub level: Option<String>,
+    pub levels: Vec<RiskLevel>,
+}
+
+// Pastille du niveau d'un risque, dans la couleur configurée
+#[function_component(RiskLevelBadge)]
+pub fn risk_level_badge(props: &RiskLevelBadgeProps) -> Html {
+    let Some(level) = &props.level else {
+        return html! { <span class="badge bg-secondary">{ "Non évalué" }</span> };
+    };
+    let color = props
+        .levels
+        .iter()
+        .find(|l| &l.name == level)
+        .map(|l| l.color.clone())
+        .unwrap_or_else(|| "#6c757d".to_string());
+
+    html! {
+        <span class="badge" style={format!("background-color: {}; color: #fff;", color)}>{ level }</span>
+    }
+}
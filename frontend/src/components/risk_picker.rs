@@ -0,0 +1,120 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::auth;
+
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct RiskOption {
+    pub id: String,
+    pub title: String,
+}
+
+// Risques proposés au choix, chargés depuis `GET /risks` (une page de 200 au plus)
+#[hook]
+pub fn use_risk_options() -> UseStateHandle<Vec<RiskOption>> {
+    let risks = use_state(Vec::<RiskOption>::new);
+
+    {
+        let risks = risks.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get("http://localhost:8081/risks")
+                    .query([("sort", "title"), ("per_page", "200")])
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<RiskOption>>().await {
+                        risks.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    risks
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RiskPickerProps {
+    pub selected: Option<String>,
+    pub on_change: Callback<Option<String>>,
+}
+
+// Sélection du risque lié à un incident (`related_risk_id`)
+#[function_component(RiskPicker)]
+pub fn risk_picker(props: &RiskPickerProps) -> Html {
+    let risks = use_risk_options();
+
+    let on_select = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            on_change.emit(Some(select.value()).filter(|id| !id.is_empty()));
+        })
+    };
+
+    html! {
+        <select onchange={on_select}>
+            <option value="" selected={props.selected.is_none()}>{ "Aucun risque lié" }</option>
+            { for risks.iter().map(|risk| html! {
+                <option value={risk.id.clone()} selected={props.selected.as_ref() == Some(&risk.id)}>
+                    { &risk.title }
+                </option>
+            }) }
+        </select>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RiskChecklistProps {
+    pub selected: Vec<String>,
+    // Risque principal : toujours lié, donc non décochable
+    #[prop_or_default]
+    pub locked: Option<String>,
+    pub on_change: Callback<Vec<String>>,
+}
+
+// Sélection des autres risques liés à un incident (`risk_ids`)
+#[function_component(RiskChecklist)]
+pub fn risk_checklist(props: &RiskChecklistProps) -> Html {
+    let risks = use_risk_options();
+
+    html! {
+        <ul class="list-unstyled">
+            { for risks.iter().map(|risk| {
+                let locked = props.locked.as_ref() == Some(&risk.id);
+                let onchange = {
+                    let selected = props.selected.clone();
+                    let on_change = props.on_change.clone();
+                    let id = risk.id.clone();
+                    Callback::from(move |e: Event| {
+                        let checked = e.target_unchecked_into::<HtmlInputElement>().checked();
+                        let mut updated: Vec<String> = selected.iter().filter(|s| **s != id).cloned().collect();
+                        if checked {
+                            updated.push(id.clone());
+                        }
+                        on_change.emit(updated);
+                    })
+                };
+                html! {
+                    <li key={risk.id.clone()}>
+                        <label>
+                            <input
+                                type="checkbox"
+                                checked={locked || props.selected.contains(&risk.id)}
+                                disabled={locked}
+                                {onchange}
+                            />
+                            { format!(" {}", risk.title) }
+                        </label>
+                    </li>
+                }
+            }) }
+        </ul>
+    }
+}
@@ -0,0 +1,45 @@
+use yew::prelude::*;
+use web_sys::HtmlSelectElement;
+
+// Méthodes de `ScoringService` côté backend
+pub const SCORING_METHODS: &[&str] = &["ImpactProbability", "Fmea", "Weighted"];
+
+pub fn scoring_method_label(method: &str) -> &str {
+    match method {
+        "ImpactProbability" => "Impact × Probabilité",
+        "Fmea" => "AMDEC (gravité × vraisemblance × détectabilité)",
+        "Weighted" => "Somme pondérée",
+        other => other,
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct ScoringMethodSelectProps {
+    // Vide : méthode par défaut du serveur
+    pub selected: String,
+    pub on_change: Callback<String>,
+    // Propose l'option « méthode par défaut » (création)
+    #[prop_or_default]
+    pub allow_default: bool,
+}
+
+#[function_component(ScoringMethodSelect)]
+pub fn scoring_method_select(props: &ScoringMethodSelectProps) -> Html {
+    let onchange = {
+        let on_change = props.on_change.clone();
+        Callback::from(move |e: Event| on_change.emit(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+
+    html! {
+        <label>{ "Méthode de score : " }
+            <select {onchange}>
+                if props.allow_default {
+                    <option value="" selected={props.selected.is_empty()}>{ "Par défaut" }</option>
+                }
+                { for SCORING_METHODS.iter().map(|method| html! {
+                    <option value={*method} selected={props.selected == *method}>{ scoring_method_label(method) }</option>
+                }) }
+            </select>
+        </label>
+    }
+}
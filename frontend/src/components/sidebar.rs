@@ -1,14 +1,18 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use yew_router::hooks::use_location;
+use web_sys::HtmlInputElement;
 use crate::Route;
-use std::rc::Rc;
-use std::cell::RefCell;
+use crate::auth;
+use crate::pages::search::SearchParams;
 
 #[function_component(Sidebar)]
 pub fn sidebar() -> Html {
     let show_risks = use_state(|| true);
+    let show_incidents = use_state(|| true);
+    let show_controls = use_state(|| true);
     let location = use_location().unwrap();
+    let navigator = use_navigator().unwrap();
     let current_path = location.path();
 
     let toggle_risks = {
@@ -16,11 +20,51 @@ pub fn sidebar() -> Html {
         Callback::from(move |_| show_risks.set(!*show_risks))
     };
 
+    let toggle_incidents = {
+        let show_incidents = show_incidents.clone();
+        Callback::from(move |_| show_incidents.set(!*show_incidents))
+    };
+
+    let toggle_controls = {
+        let show_controls = show_controls.clone();
+        Callback::from(move |_| show_controls.set(!*show_controls))
+    };
+
+    let search_input = use_node_ref();
+    let on_search = {
+        let navigator = navigator.clone();
+        let search_input = search_input.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let q = search_input
+                .cast::<HtmlInputElement>()
+                .map(|input| input.value().trim().to_string())
+                .unwrap_or_default();
+            if !q.is_empty() {
+                let _ = navigator.push_with_query(&Route::Search, &SearchParams { q });
+            }
+        })
+    };
+
+    let logout = Callback::from(move |_| {
+        auth::clear_session();
+        navigator.push(&Route::Login);
+    });
+
     html! {
         <div class="d-flex flex-column bg-light" style="width: 220px; height: 100vh; border-right: 1px solid #ccc;">
             <div class="navbar navbar-expand-lg navbar-light bg-light">
                 <a class="navbar-brand" href="#">{ "SentinelRisk" }</a>
             </div>
+            if let Some(username) = auth::username().filter(|_| auth::is_authenticated()) {
+                <div class="p-2">
+                    <small>{ format!("👤 {}", username) }</small>
+                    <button class="btn btn-link btn-sm" onclick={logout}>{ "Se déconnecter" }</button>
+                </div>
+                <form class="p-2" onsubmit={on_search}>
+                    <input ref={search_input} type="search" class="form-control form-control-sm" placeholder="🔎 Rechercher..." />
+                </form>
+            }
             <div>
                 <button 
                     class={classes!(
@@ -46,20 +90,126 @@ pub fn sidebar() -> Html {
                                 { "📋 Liste des Risques" }
                             </Link<Route>>
                         </li>
+                        if auth::can_edit() {
+                            <li class="list-group-item p-2">
+                                <Link<Route>
+                                    to={Route::AddRisk}
+                                    classes={classes!(
+                                        "nav-link",
+                                        if current_path == "/risks/add" { "active" } else { "text-muted" }
+                                    )}
+                                >
+                                    { "➕ Ajouter un Risque" }
+                                </Link<Route>>
+                            </li>
+                        }
+                        <li class="list-group-item p-2">
+                            <Link<Route>
+                                to={Route::Actions}
+                                classes={classes!(
+                                    "nav-link",
+                                    if current_path == "/actions" { "active" } else { "text-muted" }
+                                )}
+                            >
+                                { "✅ Actions de traitement" }
+                            </Link<Route>>
+                        </li>
+                        <li class="list-group-item p-2">
+                            <Link<Route>
+                                to={Route::Appetite}
+                                classes={classes!(
+                                    "nav-link",
+                                    if current_path == "/appetite" { "active" } else { "text-muted" }
+                                )}
+                            >
+                                { "🎯 Appétence au risque" }
+                            </Link<Route>>
+                        </li>
+                    </ul>
+                }
+            </div>
+            <div>
+                <button 
+                    class={classes!(
+                        "btn",
+                        "btn-link",
+                        "w-100",
+                        "text-left",
+                        if *show_incidents { "bg-primary" } else { "" }
+                    )}
+                    onclick={toggle_incidents}>
+                    { format!("{} Gestion des incidents", if *show_incidents { "🔽" } else { "▶️" }) }
+                </button>
+                if *show_incidents {
+                    <ul class="list-group list-group-flush mt-2">
+                        <li class="list-group-item p-2">
+                            <Link<Route>
+                                to={Route::Incidents}
+                                classes={classes!(
+                                    "nav-link",
+                                    if current_path == "/incidents" { "active" } else { "text-muted" }
+                                )}
+                            >
+                                { "📋 Liste des Incidents" }
+                            </Link<Route>>
+                        </li>
+                        if auth::can_edit() {
+                            <li class="list-group-item p-2">
+                                <Link<Route>
+                                    to={Route::AddIncident}
+                                    classes={classes!(
+                                        "nav-link",
+                                        if current_path == "/incidents/new" { "active" } else { "text-muted" }
+                                    )}
+                                >
+                                    { "➕ Déclarer un Incident" }
+                                </Link<Route>>
+                            </li>
+                        }
+                    </ul>
+                }
+            </div>
+            <div>
+                <button 
+                    class={classes!(
+                        "btn",
+                        "btn-link",
+                        "w-100",
+                        "text-left",
+                        if *show_controls { "bg-primary" } else { "" }
+                    )}
+                    onclick={toggle_controls}>
+                    { format!("{} Contrôles", if *show_controls { "🔽" } else { "▶️" }) }
+                </button>
+                if *show_controls {
+                    <ul class="list-group list-group-flush mt-2">
                         <li class="list-group-item p-2">
                             <Link<Route>
-                                to={Route::AddRisk}
+                                to={Route::Controls}
                                 classes={classes!(
                                     "nav-link",
-                                    if current_path == "/risks/add" { "active" } else { "text-muted" }
+                                    if current_path == "/controls" { "active" } else { "text-muted" }
                                 )}
                             >
-                                { "➕ Ajouter un Risque" }
+                                { "📋 Bibliothèque de contrôles" }
                             </Link<Route>>
                         </li>
+                        if auth::can_edit() {
+                            <li class="list-group-item p-2">
+                                <Link<Route>
+                                    to={Route::AddControl}
+                                    classes={classes!(
+                                        "nav-link",
+                                        if current_path == "/controls/new" { "active" } else { "text-muted" }
+                                    )}
+                                >
+                                    { "➕ Ajouter un Contrôle" }
+                                </Link<Route>>
+                            </li>
+                        }
                     </ul>
                 }
             </div>
         </div>
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,175 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::api;
+use crate::auth;
+
+pub const STATUSES: &[&str] = &[
+    "Identified",
+    "Assessed",
+    "InTreatment",
+    "Monitoring",
+    "Accepted",
+    "Rejected",
+    "Transferred",
+    "Closed",
+];
+
+#[derive(Serialize, Clone)]
+struct StatusPayload {
+    status: String,
+    // Exigée par certaines transitions du workflow
+    #[serde(skip_serializing_if = "String::is_empty")]
+    justification: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    approver_id: Option<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Approver {
+    id: String,
+    username: String,
+}
+
+// Statuts pour lesquels le backend exige une justification
+fn requires_justification(status: &str) -> bool {
+    matches!(status, "Accepted" | "Transferred")
+}
+
+#[derive(Properties, PartialEq)]
+pub struct StatusChangeProps {
+    pub risk_id: String,
+    pub current_status: String,
+    // Version du risque affiché, envoyée en If-Match
+    pub version: i32,
+    pub on_changed: Callback<()>,
+}
+
+#[function_component(StatusChange)]
+pub fn status_change(props: &StatusChangeProps) -> Html {
+    let selected = use_state(|| props.current_status.clone());
+    let justification = use_state(String::new);
+    let approver = use_state(String::new);
+    let approvers = use_state(Vec::<Approver>::new);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        let approvers = approvers.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get("http://localhost:8081/users/approvers")
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<Approver>>().await {
+                        // On ne peut pas approuver son propre changement
+                        let current = auth::username();
+                        approvers.set(data.into_iter().filter(|a| Some(&a.username) != current.as_ref()).collect());
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_select = {
+        let selected = selected.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            selected.set(select.value());
+        })
+    };
+
+    let on_justification = {
+        let justification = justification.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            justification.set(input.value());
+        })
+    };
+
+    let on_approver = {
+        let approver = approver.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            approver.set(select.value());
+        })
+    };
+
+    let on_submit = {
+        let selected = selected.clone();
+        let justification = justification.clone();
+        let approver = approver.clone();
+        let message = message.clone();
+        let risk_id = props.risk_id.clone();
+        let version = props.version;
+        let on_changed = props.on_changed.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let payload = StatusPayload {
+                status: (*selected).clone(),
+                justification: (*justification).clone(),
+                approver_id: Some((*approver).clone()).filter(|id| !id.is_empty()),
+            };
+            let message = message.clone();
+            let justification = justification.clone();
+            let risk_id = risk_id.clone();
+            let on_changed = on_changed.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let resp = Request::patch(&format!("http://localhost:8081/risks/{}/status", risk_id))
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .header("If-Match", &format!("\"{}\"", version))
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        message.set(None);
+                        justification.set(String::new());
+                        on_changed.emit(());
+                    }
+                    Ok(response) if response.status() == 412 => {
+                        message.set(Some("Le risque a été modifié entre-temps : vérifiez son état puis réessayez.".to_string()));
+                        on_changed.emit(());
+                    }
+                    Ok(response) => message.set(Some(api::error_message(&response).await)),
+                    Err(_) => message.set(Some("Erreur réseau : impossible de changer le statut.".to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit}>
+            <select onchange={on_select}>
+                { for STATUSES.iter().map(|status| html! {
+                    <option value={*status} selected={*status == props.current_status}>{ *status }</option>
+                }) }
+            </select>
+            <input
+                type="text"
+                placeholder={if requires_justification(&selected) { "Justification (obligatoire)" } else { "Justification" }}
+                required={requires_justification(&selected)}
+                value={(*justification).clone()}
+                oninput={on_justification}
+            />
+            <select onchange={on_approver}>
+                <option value="" selected={approver.is_empty()}>{ "Sans approbateur" }</option>
+                { for approvers.iter().map(|a| html! {
+                    <option value={a.id.clone()} selected={*approver == a.id}>{ format!("Approuvé par {}", a.username) }</option>
+                }) }
+            </select>
+            <button type="submit">{ "Changer le statut" }</button>
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </form>
+    }
+}
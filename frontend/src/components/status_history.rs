@@ -0,0 +1,82 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+use wasm_bindgen_futures::spawn_local;
+
+use crate::auth;
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+struct StatusChangeEntry {
+    id: String,
+    old_status: String,
+    new_status: String,
+    justification: Option<String>,
+    changed_by_username: Option<String>,
+    approved_by_username: Option<String>,
+    changed_at: String,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct StatusHistoryProps {
+    pub risk_id: String,
+    // Incrémenté par le parent pour recharger l'historique après un changement de statut
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+#[function_component(StatusHistory)]
+pub fn status_history(props: &StatusHistoryProps) -> Html {
+    let entries = use_state(Vec::<StatusChangeEntry>::new);
+
+    {
+        let entries = entries.clone();
+        let url = format!("http://localhost:8081/risks/{}/history", props.risk_id);
+        use_effect_with((url.clone(), props.refresh), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&url)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<StatusChangeEntry>>().await {
+                        entries.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div>
+            <h3>{ "Historique des statuts" }</h3>
+            if entries.is_empty() {
+                <p>{ "Aucun changement de statut." }</p>
+            } else {
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Date" }</th>
+                            <th>{ "Transition" }</th>
+                            <th>{ "Auteur" }</th>
+                            <th>{ "Approbateur" }</th>
+                            <th>{ "Justification" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for entries.iter().rev().map(|entry| html! {
+                            <tr key={entry.id.clone()}>
+                                <td>{ &entry.changed_at }</td>
+                                <td>{ format!("{} → {}", entry.old_status, entry.new_status) }</td>
+                                <td>{ entry.changed_by_username.clone().unwrap_or_else(|| "—".to_string()) }</td>
+                                <td>{ entry.approved_by_username.clone().unwrap_or_else(|| "—".to_string()) }</td>
+                                <td>{ entry.justification.clone().unwrap_or_default() }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+        </div>
+    }
+}
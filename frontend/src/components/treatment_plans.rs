@@ -0,0 +1,484 @@
+use yew::prelude::*;
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+use crate::api;
+use crate::auth;
+use crate::treatment::{action_status_label, strategy_label, ActionItem, TreatmentPlan, ACTION_STATUSES, STRATEGIES};
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct Assignee {
+    id: String,
+    username: String,
+}
+
+// Corps de `POST /risks/{id}/treatment-plans`
+#[derive(Serialize, Clone, PartialEq)]
+struct PlanPayload {
+    title: String,
+    strategy: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    due_date: Option<String>,
+}
+
+impl Default for PlanPayload {
+    fn default() -> Self {
+        PlanPayload {
+            title: String::new(),
+            strategy: "Mitigate".to_string(),
+            description: None,
+            owner: String::new(),
+            due_date: None,
+        }
+    }
+}
+
+// Corps de `POST /treatment-plans/{id}/actions` et `PUT /actions/{id}`
+#[derive(Serialize, Clone, PartialEq)]
+struct ActionPayload {
+    title: String,
+    description: Option<String>,
+    assignee_id: Option<String>,
+    due_date: Option<String>,
+    progress: i32,
+    status: String,
+    mandatory: bool,
+}
+
+impl Default for ActionPayload {
+    fn default() -> Self {
+        ActionPayload {
+            title: String::new(),
+            description: None,
+            assignee_id: None,
+            due_date: None,
+            progress: 0,
+            status: "Open".to_string(),
+            mandatory: true,
+        }
+    }
+}
+
+impl From<&ActionItem> for ActionPayload {
+    fn from(action: &ActionItem) -> Self {
+        ActionPayload {
+            title: action.title.clone(),
+            description: action.description.clone(),
+            assignee_id: action.assignee_id.clone(),
+            due_date: action.due_date.clone(),
+            progress: action.progress,
+            status: action.status.clone(),
+            mandatory: action.mandatory,
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ActionFormProps {
+    plan_id: String,
+    assignees: Vec<Assignee>,
+    on_added: Callback<()>,
+}
+
+// Ajout d'une action à un plan
+#[function_component(ActionForm)]
+fn action_form(props: &ActionFormProps) -> Html {
+    let form = use_state(ActionPayload::default);
+    let errors = use_state(FieldErrors::new);
+    let message = use_state(|| None as Option<String>);
+    let rules = use_validation_rules();
+
+    let on_input = |field: &'static str| {
+        let form = form.clone();
+        Callback::from(move |e: InputEvent| {
+            let value = e.target_unchecked_into::<HtmlInputElement>().value();
+            let mut data = (*form).clone();
+            match field {
+                "title" => data.title = value,
+                "due_date" => data.due_date = Some(value).filter(|v| !v.is_empty()),
+                _ => {}
+            }
+            form.set(data);
+        })
+    };
+
+    let on_assignee = {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let mut data = (*form).clone();
+            data.assignee_id = Some(e.target_unchecked_into::<HtmlSelectElement>().value()).filter(|v| !v.is_empty());
+            form.set(data);
+        })
+    };
+
+    let on_mandatory = {
+        let form = form.clone();
+        Callback::from(move |e: Event| {
+            let mut data = (*form).clone();
+            data.mandatory = e.target_unchecked_into::<HtmlInputElement>().checked();
+            form.set(data);
+        })
+    };
+
+    let on_submit = {
+        let form = form.clone();
+        let errors = errors.clone();
+        let message = message.clone();
+        let plan_id = props.plan_id.clone();
+        let on_added = props.on_added.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let payload = (*form).clone();
+            let local = validation::validate_action(&payload.title, payload.progress, &rules);
+            if !local.is_empty() {
+                errors.set(local);
+                return;
+            }
+            let form = form.clone();
+            let errors = errors.clone();
+            let message = message.clone();
+            let on_added = on_added.clone();
+            let url = format!("http://localhost:8081/treatment-plans/{}/actions", plan_id);
+            spawn_local(async move {
+                let resp = Request::post(&url)
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        errors.set(FieldErrors::new());
+                        message.set(None);
+                        form.set(ActionPayload::default());
+                        on_added.emit(());
+                    }
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : action non enregistrée.".to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit}>
+            <input type="text" placeholder="Nouvelle action" value={form.title.clone()} oninput={on_input("title")} />
+            <FieldError errors={(*errors).clone()} field="title" />
+            <label>{ "Échéance : " }
+                <input type="date" value={form.due_date.clone().unwrap_or_default()} oninput={on_input("due_date")} />
+            </label>
+            <select onchange={on_assignee}>
+                <option value="" selected={form.assignee_id.is_none()}>{ "Non assignée" }</option>
+                { for props.assignees.iter().map(|user| html! {
+                    <option value={user.id.clone()} selected={form.assignee_id.as_ref() == Some(&user.id)}>{ &user.username }</option>
+                }) }
+            </select>
+            <label>
+                <input type="checkbox" checked={form.mandatory} onchange={on_mandatory} />
+                { " Obligatoire" }
+            </label>
+            <button type="submit">{ "Ajouter l'action" }</button>
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </form>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TreatmentPlansProps {
+    pub risk_id: String,
+    #[prop_or_default]
+    pub refresh: u32,
+}
+
+// Plans de traitement d'un risque (`GET /risks/{id}/treatment-plans`) et suivi de leurs actions
+#[function_component(TreatmentPlans)]
+pub fn treatment_plans(props: &TreatmentPlansProps) -> Html {
+    let plans = use_state(Vec::<TreatmentPlan>::new);
+    let assignees = use_state(Vec::<Assignee>::new);
+    let plan_form = use_state(PlanPayload::default);
+    let plan_errors = use_state(FieldErrors::new);
+    let rules = use_validation_rules();
+    let reload = use_state(|| 0u32);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        let plans = plans.clone();
+        let risk_id = props.risk_id.clone();
+        use_effect_with((risk_id.clone(), props.refresh, *reload), move |_| {
+            spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/risks/{}/treatment-plans", risk_id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Vec<TreatmentPlan>>().await {
+                        plans.set(data);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    {
+        let assignees = assignees.clone();
+        use_effect_with((), move |_| {
+            if auth::can_edit() {
+                spawn_local(async move {
+                    let response = Request::get("http://localhost:8081/users/assignees")
+                        .header("Authorization", &auth::bearer())
+                        .send()
+                        .await;
+                    if let Ok(resp) = response {
+                        if let Ok(data) = resp.json::<Vec<Assignee>>().await {
+                            assignees.set(data);
+                        }
+                    }
+                });
+            }
+            || ()
+        });
+    }
+
+    let on_reload = {
+        let reload = reload.clone();
+        Callback::from(move |_: ()| reload.set(*reload + 1))
+    };
+
+    // Enregistre une action modifiée (statut ou avancement), avec sa version en If-Match
+    let save_action = {
+        let on_reload = on_reload.clone();
+        let message = message.clone();
+        Callback::from(move |(action, payload): (ActionItem, ActionPayload)| {
+            let on_reload = on_reload.clone();
+            let message = message.clone();
+            spawn_local(async move {
+                let resp = Request::put(&format!("http://localhost:8081/actions/{}", action.id))
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .header("If-Match", &format!("\"{}\"", action.version))
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => message.set(None),
+                    Ok(response) if response.status() == 412 => {
+                        message.set(Some("Cette action a été modifiée entre-temps : elle a été rechargée.".to_string()))
+                    }
+                    Ok(response) => message.set(Some(api::error_message(&response).await)),
+                    Err(_) => message.set(Some("Erreur réseau : action non enregistrée.".to_string())),
+                }
+                on_reload.emit(());
+            });
+        })
+    };
+
+    let on_add_plan = {
+        let plan_form = plan_form.clone();
+        let plan_errors = plan_errors.clone();
+        let message = message.clone();
+        let on_reload = on_reload.clone();
+        let risk_id = props.risk_id.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let payload = (*plan_form).clone();
+            let local = validation::validate_plan(&payload.title, &payload.owner, &rules);
+            if !local.is_empty() {
+                plan_errors.set(local);
+                return;
+            }
+            let plan_form = plan_form.clone();
+            let plan_errors = plan_errors.clone();
+            let message = message.clone();
+            let on_reload = on_reload.clone();
+            let url = format!("http://localhost:8081/risks/{}/treatment-plans", risk_id);
+            spawn_local(async move {
+                let resp = Request::post(&url)
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        plan_form.set(PlanPayload::default());
+                        plan_errors.set(FieldErrors::new());
+                        message.set(None);
+                        on_reload.emit(());
+                    }
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        plan_errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : plan non enregistré.".to_string())),
+                }
+            });
+        })
+    };
+
+    let on_strategy = {
+        let plan_form = plan_form.clone();
+        Callback::from(move |e: Event| {
+            let mut data = (*plan_form).clone();
+            data.strategy = e.target_unchecked_into::<HtmlSelectElement>().value();
+            plan_form.set(data);
+        })
+    };
+    let on_plan_input = |field: &'static str| {
+        let plan_form = plan_form.clone();
+        Callback::from(move |e: InputEvent| {
+            let value = e.target_unchecked_into::<HtmlInputElement>().value();
+            let mut data = (*plan_form).clone();
+            match field {
+                "title" => data.title = value,
+                "description" => data.description = Some(value).filter(|v| !v.trim().is_empty()),
+                "owner" => data.owner = value,
+                "due_date" => data.due_date = Some(value).filter(|v| !v.is_empty()),
+                _ => {}
+            }
+            plan_form.set(data);
+        })
+    };
+
+    let action_row = |action: &ActionItem| {
+        let on_status = {
+            let save_action = save_action.clone();
+            let action = action.clone();
+            Callback::from(move |e: Event| {
+                let mut payload = ActionPayload::from(&action);
+                payload.status = e.target_unchecked_into::<HtmlSelectElement>().value();
+                save_action.emit((action.clone(), payload));
+            })
+        };
+        let on_progress = {
+            let save_action = save_action.clone();
+            let action = action.clone();
+            Callback::from(move |e: Event| {
+                let mut payload = ActionPayload::from(&action);
+                payload.progress = e.target_unchecked_into::<HtmlInputElement>().value().parse().unwrap_or(action.progress);
+                save_action.emit((action.clone(), payload));
+            })
+        };
+        html! {
+            <tr key={action.id.clone()}>
+                <td>
+                    { &action.title }
+                    if action.mandatory {
+                        { " *" }
+                    }
+                </td>
+                <td>{ action.assignee_username.clone().unwrap_or_else(|| "–".to_string()) }</td>
+                <td style={if action.overdue { "color: #c62828; font-weight: bold;" } else { "" }}>
+                    { action.due_date.clone().unwrap_or_else(|| "–".to_string()) }
+                    if action.overdue {
+                        { " (en retard)" }
+                    }
+                </td>
+                <td>
+                    <progress max="100" value={action.progress.to_string()} />
+                    if auth::can_edit() {
+                        <input type="number" min="0" max="100" value={action.progress.to_string()} onchange={on_progress} />
+                    } else {
+                        { format!(" {} %", action.progress) }
+                    }
+                </td>
+                <td>
+                    if auth::can_edit() {
+                        <select onchange={on_status}>
+                            { for ACTION_STATUSES.iter().map(|value| html! {
+                                <option value={*value} selected={action.status == *value}>{ action_status_label(value) }</option>
+                            }) }
+                        </select>
+                    } else {
+                        { action_status_label(&action.status) }
+                    }
+                </td>
+            </tr>
+        }
+    };
+
+    html! {
+        <div>
+            <h3>{ "Plans de traitement" }</h3>
+            if plans.is_empty() {
+                <p>{ "Aucun plan de traitement." }</p>
+            }
+            { for plans.iter().map(|plan| html! {
+                <div key={plan.id.clone()}>
+                    <h4>{ format!("{} ({})", plan.title, strategy_label(&plan.strategy)) }</h4>
+                    <p>
+                        { format!("Responsable : {}", plan.owner.clone().unwrap_or_else(|| "–".to_string())) }
+                        if let Some(due_date) = &plan.due_date {
+                            { format!(" — échéance : {}", due_date) }
+                        }
+                    </p>
+                    if let Some(description) = &plan.description {
+                        <p>{ description }</p>
+                    }
+                    if plan.actions.is_empty() {
+                        <p>{ "Aucune action." }</p>
+                    } else {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>{ "Action (* obligatoire)" }</th>
+                                    <th>{ "Assignée à" }</th>
+                                    <th>{ "Échéance" }</th>
+                                    <th>{ "Avancement" }</th>
+                                    <th>{ "Statut" }</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { for plan.actions.iter().map(action_row) }
+                            </tbody>
+                        </table>
+                    }
+                    if auth::can_edit() {
+                        <ActionForm plan_id={plan.id.clone()} assignees={(*assignees).clone()} on_added={on_reload.clone()} />
+                    }
+                </div>
+            }) }
+            if auth::can_edit() {
+                <form onsubmit={on_add_plan}>
+                    <input type="text" placeholder="Intitulé du plan" value={plan_form.title.clone()} oninput={on_plan_input("title")} />
+                    <FieldError errors={(*plan_errors).clone()} field="title" />
+                    <select onchange={on_strategy}>
+                        { for STRATEGIES.iter().map(|value| html! {
+                            <option value={*value} selected={plan_form.strategy == *value}>{ strategy_label(value) }</option>
+                        }) }
+                    </select>
+                    <input type="text" placeholder="Description du plan" value={plan_form.description.clone().unwrap_or_default()} oninput={on_plan_input("description")} />
+                    <input type="text" placeholder="Responsable" value={plan_form.owner.clone()} oninput={on_plan_input("owner")} />
+                    <FieldError errors={(*plan_errors).clone()} field="owner" />
+                    <label>{ "Échéance : " }
+                        <input type="date" value={plan_form.due_date.clone().unwrap_or_default()} oninput={on_plan_input("due_date")} />
+                    </label>
+                    <FieldError errors={(*plan_errors).clone()} field="due_date" />
+                    <button type="submit">{ "Ajouter un plan de traitement" }</button>
+                </form>
+            }
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+// Contrôle tel que renvoyé par l'API (`models::control::Control`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Control {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub control_type: String,
+    pub owner: Option<String>,
+    pub frequency: Option<String>,
+    pub design_effectiveness: String,
+    pub operating_effectiveness: String,
+    pub version: i32,
+    pub updated_at: String,
+    #[serde(default)]
+    pub risk_ids: Vec<String>,
+}
+
+pub const CONTROL_TYPES: &[&str] = &["Preventive", "Detective", "Corrective", "Directive"];
+
+pub const FREQUENCIES: &[&str] = &["Continuous", "Daily", "Weekly", "Monthly", "Quarterly", "Annually", "AdHoc"];
+
+pub const EFFECTIVENESS: &[&str] = &["Effective", "PartiallyEffective", "Ineffective", "NotTested"];
+
+pub fn type_label(control_type: &str) -> &str {
+    match control_type {
+        "Preventive" => "Préventif",
+        "Detective" => "Détectif",
+        "Corrective" => "Correctif",
+        "Directive" => "Directif",
+        other => other,
+    }
+}
+
+pub fn frequency_label(frequency: &str) -> &str {
+    match frequency {
+        "Continuous" => "Continu",
+        "Daily" => "Quotidien",
+        "Weekly" => "Hebdomadaire",
+        "Monthly" => "Mensuel",
+        "Quarterly" => "Trimestriel",
+        "Annually" => "Annuel",
+        "AdHoc" => "Ponctuel",
+        other => other,
+    }
+}
+
+pub fn effectiveness_label(effectiveness: &str) -> &str {
+    match effectiveness {
+        "Effective" => "Efficace",
+        "PartiallyEffective" => "Partiellement efficace",
+        "Ineffective" => "Inefficace",
+        "NotTested" => "Non testé",
+        other => other,
+    }
+}
@@ -0,0 +1,53 @@
+use serde::Deserialize;
+
+// Incident tel que renvoyé par l'API (`models::incident::Incident`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct Incident {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub severity: String,
+    pub status: String,
+    pub related_risk_id: Option<String>,
+    pub created_at: String,
+    pub version: i32,
+    // Tous les risques liés, risque principal compris
+    #[serde(default)]
+    pub risk_ids: Vec<String>,
+}
+
+pub const SEVERITIES: &[&str] = &["Low", "Medium", "High", "Critical"];
+
+pub fn severity_label(severity: &str) -> &str {
+    match severity {
+        "Low" => "Faible",
+        "Medium" => "Moyenne",
+        "High" => "Élevée",
+        "Critical" => "Critique",
+        other => other,
+    }
+}
+
+pub fn status_label(status: &str) -> &str {
+    match status {
+        "New" => "Nouveau",
+        "Triaged" => "Qualifié",
+        "Investigating" => "En investigation",
+        "Contained" => "Contenu",
+        "Resolved" => "Résolu",
+        "Closed" => "Clos",
+        other => other,
+    }
+}
+
+// Même cycle de vie que `IncidentStatus::next_statuses` côté backend
+pub fn next_statuses(status: &str) -> &'static [&'static str] {
+    match status {
+        "New" => &["Triaged"],
+        "Triaged" => &["Investigating"],
+        "Investigating" => &["Contained"],
+        "Contained" => &["Resolved"],
+        "Resolved" => &["Closed"],
+        _ => &[],
+    }
+}
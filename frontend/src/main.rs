@@ -1,12 +1,30 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
-use web_sys::HtmlInputElement;
 
+mod api;
+mod auth;
+mod incident;
+mod control;
+mod treatment;
+mod appetite;
+mod validation;
 mod pages;
 use pages::risk_list::RiskList;
 use pages::add_risk::AddRisk;
 use pages::edit_risk::EditRisk;
 use pages::view_risk::ViewRisk;
+use pages::login::Login;
+use pages::incident_list::IncidentList;
+use pages::add_incident::AddIncident;
+use pages::edit_incident::EditIncident;
+use pages::view_incident::ViewIncident;
+use pages::search::Search;
+use pages::control_list::ControlList;
+use pages::add_control::AddControl;
+use pages::edit_control::EditControl;
+use pages::view_control::ViewControl;
+use pages::action_list::ActionList;
+use pages::appetite_overview::AppetiteOverview;
 mod components;
 use components::sidebar::Sidebar;
 
@@ -22,6 +40,30 @@ enum Route {
     EditRisk { id: String },
     #[at("/risks/view/:id")]
     ViewRisk { id: String },
+    #[at("/incidents")]
+    Incidents,
+    #[at("/incidents/new")]
+    AddIncident,
+    #[at("/incidents/edit/:id")]
+    EditIncident { id: String },
+    #[at("/incidents/view/:id")]
+    ViewIncident { id: String },
+    #[at("/controls")]
+    Controls,
+    #[at("/controls/new")]
+    AddControl,
+    #[at("/controls/edit/:id")]
+    EditControl { id: String },
+    #[at("/controls/view/:id")]
+    ViewControl { id: String },
+    #[at("/actions")]
+    Actions,
+    #[at("/appetite")]
+    Appetite,
+    #[at("/search")]
+    Search,
+    #[at("/login")]
+    Login,
     #[not_found]
     #[at("/404")]
     NotFound,
@@ -42,11 +84,28 @@ fn app() -> Html {
 }
 
 fn switch(routes: Route) -> Html {
+    // Toutes les pages hors connexion exigent une session valide
+    if routes != Route::Login && !auth::is_authenticated() {
+        return html! { <Redirect<Route> to={Route::Login} /> };
+    }
+
     match routes {
         Route::Home | Route::Risks => html! { <RiskList /> },
         Route::AddRisk => html! { <AddRisk /> },
-        Route::EditRisk { id } => html! { <EditRisk /> },
-        Route::ViewRisk { id } => html! { <ViewRisk /> },
+        Route::EditRisk { .. } => html! { <EditRisk /> },
+        Route::ViewRisk { .. } => html! { <ViewRisk /> },
+        Route::Incidents => html! { <IncidentList /> },
+        Route::AddIncident => html! { <AddIncident /> },
+        Route::EditIncident { .. } => html! { <EditIncident /> },
+        Route::ViewIncident { .. } => html! { <ViewIncident /> },
+        Route::Controls => html! { <ControlList /> },
+        Route::AddControl => html! { <AddControl /> },
+        Route::EditControl { .. } => html! { <EditControl /> },
+        Route::ViewControl { .. } => html! { <ViewControl /> },
+        Route::Actions => html! { <ActionList /> },
+        Route::Appetite => html! { <AppetiteOverview /> },
+        Route::Search => html! { <Search /> },
+        Route::Login => html! { <Login /> },
         Route::NotFound => html! { <h1>{ "404 - Page non trouvée" }</h1> },
     }
 }
@@ -0,0 +1,119 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console, HtmlInputElement};
+
+use crate::auth;
+use crate::components::pager::{total_count, Pager, PER_PAGE};
+use crate::treatment::{action_status_label, ActionItem};
+use crate::Route;
+
+// Actions de traitement de tous les risques, par échéance
+#[function_component(ActionList)]
+pub fn action_list() -> Html {
+    let actions = use_state(Vec::<ActionItem>::new);
+    let loading = use_state(|| true);
+    let page = use_state(|| 1u32);
+    let total = use_state(|| 0u32);
+    // Actions encore ouvertes uniquement, ou seulement celles en retard
+    let open_only = use_state(|| true);
+    let overdue_only = use_state(|| false);
+
+    {
+        let actions = actions.clone();
+        let loading = loading.clone();
+        let total = total.clone();
+        use_effect_with((*page, *open_only, *overdue_only), move |(page, open_only, overdue_only)| {
+            let mut query = vec![("page", page.to_string()), ("per_page", PER_PAGE.to_string())];
+            if *open_only {
+                query.push(("status", "Open,InProgress".to_string()));
+            }
+            if *overdue_only {
+                query.push(("overdue", "true".to_string()));
+            }
+            spawn_local(async move {
+                match Request::get("http://localhost:8081/actions")
+                    .query(query)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await
+                {
+                    Ok(resp) => {
+                        total.set(total_count(&resp));
+                        match resp.json::<Vec<ActionItem>>().await {
+                            Ok(data) => actions.set(data),
+                            Err(err) => console::log_1(&format!("Erreur JSON: {:?}", err).into()),
+                        }
+                    }
+                    Err(err) => console::log_1(&format!("Erreur: {:?}", err).into()),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_page = {
+        let page = page.clone();
+        Callback::from(move |next: u32| page.set(next))
+    };
+
+    let toggle = |flag: &UseStateHandle<bool>| {
+        let flag = flag.clone();
+        let page = page.clone();
+        Callback::from(move |e: Event| {
+            flag.set(e.target_unchecked_into::<HtmlInputElement>().checked());
+            page.set(1);
+        })
+    };
+
+    html! {
+        <div>
+            <h2>{ "Actions de traitement" }</h2>
+            <label>
+                <input type="checkbox" checked={*open_only} onchange={toggle(&open_only)} />
+                { " Actions ouvertes" }
+            </label>
+            <label>
+                <input type="checkbox" checked={*overdue_only} onchange={toggle(&overdue_only)} />
+                { " En retard uniquement" }
+            </label>
+            if *loading {
+                <p>{ "Chargement en cours..." }</p>
+            } else if actions.is_empty() {
+                <p>{ "Aucune action." }</p>
+            } else {
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "Action" }</th>
+                            <th>{ "Risque" }</th>
+                            <th>{ "Assignée à" }</th>
+                            <th>{ "Échéance" }</th>
+                            <th>{ "Avancement" }</th>
+                            <th>{ "Statut" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for actions.iter().map(|action| html! {
+                            <tr key={action.id.clone()}>
+                                <td>{ &action.title }</td>
+                                <td>
+                                    <Link<Route> to={Route::ViewRisk { id: action.risk_id.clone() }}>{ "Voir le risque" }</Link<Route>>
+                                </td>
+                                <td>{ action.assignee_username.clone().unwrap_or_else(|| "–".to_string()) }</td>
+                                <td style={if action.overdue { "color: #c62828; font-weight: bold;" } else { "" }}>
+                                    { action.due_date.clone().unwrap_or_else(|| "–".to_string()) }
+                                </td>
+                                <td>{ format!("{} %", action.progress) }</td>
+                                <td>{ action_status_label(&action.status) }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+            <Pager page={*page} total={*total} on_page={on_page} />
+        </div>
+    }
+}
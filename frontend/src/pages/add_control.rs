@@ -0,0 +1,76 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use serde::Deserialize;
+
+use crate::api;
+use crate::auth;
+use crate::components::control_form::{ControlData, ControlForm};
+use crate::validation::{self, FieldErrors};
+use crate::Route;
+
+#[derive(Deserialize)]
+struct Created {
+    id: String,
+}
+
+#[function_component(AddControl)]
+pub fn add_control() -> Html {
+    let navigator = use_navigator().unwrap();
+    let data = use_state(ControlData::default);
+    let errors = use_state(FieldErrors::new);
+    let message = use_state(|| None as Option<String>);
+
+    let on_change = {
+        let data = data.clone();
+        Callback::from(move |updated: ControlData| data.set(updated))
+    };
+
+    let on_submit = {
+        let errors = errors.clone();
+        let message = message.clone();
+        Callback::from(move |control: ControlData| {
+            let errors = errors.clone();
+            let message = message.clone();
+            let navigator = navigator.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let resp = Request::post("http://localhost:8081/controls")
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .json(&control)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => match response.json::<Created>().await {
+                        Ok(created) => navigator.push(&Route::ViewControl { id: created.id }),
+                        Err(_) => navigator.push(&Route::Controls),
+                    },
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : impossible de créer le contrôle.".to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div>
+            <h2>{ "Ajouter un Contrôle" }</h2>
+            <ControlForm
+                data={(*data).clone()}
+                on_change={on_change}
+                on_submit={on_submit}
+                errors={(*errors).clone()}
+                submit_label="Créer"
+            />
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
@@ -1,5 +1,9 @@
 use yew::prelude::*;
 use gloo_net::http::Request;
+use crate::api;
+use crate::auth;
+use crate::components::scoring_method::ScoringMethodSelect;
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
 use serde::Serialize;
 use web_sys::HtmlInputElement;
 
@@ -9,28 +13,69 @@ struct NewRisk {
     description: String,
     impact: i32,
     probability: i32,
-    status: String,
+    residual_impact: Option<i32>,
+    residual_probability: Option<i32>,
+    target_impact: Option<i32>,
+    target_probability: Option<i32>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    scoring_method: String,
     external_id: String,
     category: String,
     location: String,
     regulation: String,
     control_measure_id: String,
+    risk_map: String,
+    threat_map: String,
+    technology: String,
+    team: String,
+    business_unit: String,
+    owner: String,
+    owner_delegate: String,
+    source: String,
+    evaluation_method: String,
+    evaluation_notes: String,
+    additional_notes: String,
+    tags: Vec<String>,
+    assets: Vec<String>,
+    stakeholders: Vec<String>,
+}
+
+// Les listes (tags, actifs, parties prenantes) sont saisies séparées par des virgules
+pub fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(|item| item.to_string()).collect()
 }
 
 #[function_component(AddRisk)]
 pub fn add_risk() -> Html {
     let form = use_state(NewRisk::default);
     let message = use_state(|| None as Option<String>);
+    let errors = use_state(FieldErrors::new);
+    let rules = use_validation_rules();
 
     let on_submit = {
         let form = form.clone();
         let message = message.clone();
+        let errors = errors.clone();
+        let rules = rules.clone();
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
             let risk = (*form).clone();
+
+            let mut invalid = validation::validate_risk(&risk.title, risk.impact, risk.probability, &rules);
+            validation::check_score_pair(&mut invalid, ("residual_impact", risk.residual_impact), ("residual_probability", risk.residual_probability), &rules);
+            validation::check_score_pair(&mut invalid, ("target_impact", risk.target_impact), ("target_probability", risk.target_probability), &rules);
+            if !invalid.is_empty() {
+                errors.set(invalid);
+                message.set(Some("Certains champs sont invalides.".to_string()));
+                return;
+            }
+            errors.set(FieldErrors::new());
+
             let message = message.clone();
+            let errors = errors.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 let resp = Request::post("http://localhost:8081/risks")
+                    .header("Authorization", &auth::bearer())
                     .header("Content-Type", "application/json")
                     .json(&risk)
                     .unwrap()
@@ -42,7 +87,9 @@ pub fn add_risk() -> Html {
                         if response.ok() {
                             message.set(Some("Risque ajouté avec succès.".to_string()));
                         } else {
-                            message.set(Some(format!("Erreur du serveur: {}", response.status())));
+                            let error = api::read_error(&response).await;
+                            errors.set(validation::field_errors(&error));
+                            message.set(Some(error.message));
                         }
                     }
                     Err(_) => {
@@ -63,32 +110,119 @@ pub fn add_risk() -> Html {
                 "description" => data.description = input.value(),
                 "impact" => data.impact = input.value().parse().unwrap_or(0),
                 "probability" => data.probability = input.value().parse().unwrap_or(0),
-                "status" => data.status = input.value(),
+                "residual_impact" => data.residual_impact = input.value().parse().ok(),
+                "residual_probability" => data.residual_probability = input.value().parse().ok(),
+                "target_impact" => data.target_impact = input.value().parse().ok(),
+                "target_probability" => data.target_probability = input.value().parse().ok(),
                 "external_id" => data.external_id = input.value(),
                 "category" => data.category = input.value(),
                 "location" => data.location = input.value(),
                 "regulation" => data.regulation = input.value(),
                 "control_measure_id" => data.control_measure_id = input.value(),
+                "risk_map" => data.risk_map = input.value(),
+                "threat_map" => data.threat_map = input.value(),
+                "technology" => data.technology = input.value(),
+                "team" => data.team = input.value(),
+                "business_unit" => data.business_unit = input.value(),
+                "owner" => data.owner = input.value(),
+                "owner_delegate" => data.owner_delegate = input.value(),
+                "source" => data.source = input.value(),
+                "evaluation_method" => data.evaluation_method = input.value(),
+                "evaluation_notes" => data.evaluation_notes = input.value(),
+                "additional_notes" => data.additional_notes = input.value(),
+                "tags" => data.tags = split_list(&input.value()),
+                "assets" => data.assets = split_list(&input.value()),
+                "stakeholders" => data.stakeholders = split_list(&input.value()),
                 _ => (),
             }
             form.set(data);
         })
     };
 
+    let on_scoring_method = {
+        let form = form.clone();
+        Callback::from(move |method: String| {
+            let mut data = (*form).clone();
+            data.scoring_method = method;
+            form.set(data);
+        })
+    };
+
     html! {
         <form onsubmit={on_submit}>
             <h2>{ "Ajouter un Risque" }</h2>
 
             <input type="text" placeholder="Titre" oninput={on_input("title")} />
+            <FieldError errors={(*errors).clone()} field="title" />
             <input type="text" placeholder="Description" oninput={on_input("description")} />
-            <input type="number" placeholder="Impact (1-5)" oninput={on_input("impact")} />
-            <input type="number" placeholder="Probabilité (1-5)" oninput={on_input("probability")} />
-            <input type="text" placeholder="Statut (Nouveau/Accepté/...)" oninput={on_input("status")} />
+            <input
+                type="number"
+                min={rules.impact_scale.min.to_string()}
+                max={rules.impact_scale.max.to_string()}
+                placeholder={format!("Impact ({}-{})", rules.impact_scale.min, rules.impact_scale.max)}
+                oninput={on_input("impact")}
+            />
+            <FieldError errors={(*errors).clone()} field="impact" />
+            <input
+                type="number"
+                min={rules.probability_scale.min.to_string()}
+                max={rules.probability_scale.max.to_string()}
+                placeholder={format!("Probabilité ({}-{})", rules.probability_scale.min, rules.probability_scale.max)}
+                oninput={on_input("probability")}
+            />
+            <FieldError errors={(*errors).clone()} field="probability" />
+            <input
+                type="number"
+                min={rules.impact_scale.min.to_string()}
+                max={rules.impact_scale.max.to_string()}
+                placeholder={format!("Impact résiduel ({}-{})", rules.impact_scale.min, rules.impact_scale.max)}
+                oninput={on_input("residual_impact")}
+            />
+            <FieldError errors={(*errors).clone()} field="residual_impact" />
+            <input
+                type="number"
+                min={rules.probability_scale.min.to_string()}
+                max={rules.probability_scale.max.to_string()}
+                placeholder={format!("Probabilité résiduel ({}-{})", rules.probability_scale.min, rules.probability_scale.max)}
+                oninput={on_input("residual_probability")}
+            />
+            <FieldError errors={(*errors).clone()} field="residual_probability" />
+            <input
+                type="number"
+                min={rules.impact_scale.min.to_string()}
+                max={rules.impact_scale.max.to_string()}
+                placeholder={format!("Impact cible ({}-{})", rules.impact_scale.min, rules.impact_scale.max)}
+                oninput={on_input("target_impact")}
+            />
+            <FieldError errors={(*errors).clone()} field="target_impact" />
+            <input
+                type="number"
+                min={rules.probability_scale.min.to_string()}
+                max={rules.probability_scale.max.to_string()}
+                placeholder={format!("Probabilité cible ({}-{})", rules.probability_scale.min, rules.probability_scale.max)}
+                oninput={on_input("target_probability")}
+            />
+            <FieldError errors={(*errors).clone()} field="target_probability" />
+            <ScoringMethodSelect selected={form.scoring_method.clone()} on_change={on_scoring_method} allow_default=true />
             <input type="text" placeholder="Réf. Externe" oninput={on_input("external_id")} />
             <input type="text" placeholder="Catégorie" oninput={on_input("category")} />
             <input type="text" placeholder="Localisation" oninput={on_input("location")} />
             <input type="text" placeholder="Règlement" oninput={on_input("regulation")} />
             <input type="text" placeholder="Mesure de contrôle" oninput={on_input("control_measure_id")} />
+            <input type="text" placeholder="Cartographie des risques" oninput={on_input("risk_map")} />
+            <input type="text" placeholder="Cartographie des menaces" oninput={on_input("threat_map")} />
+            <input type="text" placeholder="Technologie" oninput={on_input("technology")} />
+            <input type="text" placeholder="Équipe" oninput={on_input("team")} />
+            <input type="text" placeholder="Unité métier" oninput={on_input("business_unit")} />
+            <input type="text" placeholder="Propriétaire" oninput={on_input("owner")} />
+            <input type="text" placeholder="Délégué du propriétaire" oninput={on_input("owner_delegate")} />
+            <input type="text" placeholder="Source" oninput={on_input("source")} />
+            <input type="text" placeholder="Méthode d'évaluation" oninput={on_input("evaluation_method")} />
+            <input type="text" placeholder="Notes d'évaluation" oninput={on_input("evaluation_notes")} />
+            <input type="text" placeholder="Notes complémentaires" oninput={on_input("additional_notes")} />
+            <input type="text" placeholder="Tags (séparés par des virgules)" oninput={on_input("tags")} />
+            <input type="text" placeholder="Actifs (séparés par des virgules)" oninput={on_input("assets")} />
+            <input type="text" placeholder="Parties prenantes (séparées par des virgules)" oninput={on_input("stakeholders")} />
             <button type="submit">{ "Ajouter" }</button>
 
             if let Some(msg) = &*message {
@@ -0,0 +1,360 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console, HtmlInputElement};
+
+use crate::api;
+use crate::appetite::{breach_trigger_label, AppetiteBreach, RiskAppetite, ToleranceExcess};
+use crate::auth;
+use crate::components::risk_level::{use_risk_levels, RiskLevelBadge};
+use crate::components::scoring_method::scoring_method_label;
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
+use crate::Route;
+
+// Corps de `POST /risk-appetites` et `PUT /risk-appetites/{id}`
+#[derive(Serialize, Clone, PartialEq, Default)]
+struct AppetitePayload {
+    category: String,
+    appetite: i32,
+    tolerance: i32,
+    fmea_appetite: Option<i32>,
+    fmea_tolerance: Option<i32>,
+    weighted_appetite: Option<i32>,
+    weighted_tolerance: Option<i32>,
+}
+
+// Appétence et tolérance d'une méthode, « – » si la catégorie n'en fixe pas
+fn thresholds_label(appetite: Option<i32>, tolerance: Option<i32>) -> String {
+    match (appetite, tolerance) {
+        (Some(appetite), Some(tolerance)) => format!("{} / {}", appetite, tolerance),
+        _ => "–".to_string(),
+    }
+}
+
+fn optional_value(value: Option<i32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+// Seuils en cours de modification : identifiant et version attendue
+type Editing = Option<(String, i32)>;
+
+async fn fetch_json<T: DeserializeOwned>(url: &str) -> Option<T> {
+    match Request::get(url).header("Authorization", &auth::bearer()).send().await {
+        Ok(resp) if resp.ok() => resp.json::<T>().await.ok(),
+        Ok(resp) => {
+            console::log_1(&format!("Erreur {} sur {}", resp.status(), url).into());
+            None
+        }
+        Err(err) => {
+            console::log_1(&format!("Erreur: {:?}", err).into());
+            None
+        }
+    }
+}
+
+// Appétence et tolérance par catégorie, risques hors tolérance et derniers dépassements
+#[function_component(AppetiteOverview)]
+pub fn appetite_overview() -> Html {
+    let appetites = use_state(Vec::<RiskAppetite>::new);
+    let excesses = use_state(Vec::<ToleranceExcess>::new);
+    let breaches = use_state(Vec::<AppetiteBreach>::new);
+    let reload = use_state(|| 0u32);
+    let form = use_state(AppetitePayload::default);
+    let editing = use_state(|| None as Editing);
+    let errors = use_state(FieldErrors::new);
+    let message = use_state(|| None as Option<String>);
+    let rules = use_validation_rules();
+    let levels = use_risk_levels();
+    // Seuls les administrateurs définissent les seuils
+    let can_manage = auth::can_delete();
+
+    {
+        let appetites = appetites.clone();
+        let excesses = excesses.clone();
+        let breaches = breaches.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                if let Some(data) = fetch_json::<Vec<RiskAppetite>>("http://localhost:8081/risk-appetites").await {
+                    appetites.set(data);
+                }
+                if let Some(data) = fetch_json::<Vec<ToleranceExcess>>("http://localhost:8081/risks/out-of-tolerance").await {
+                    excesses.set(data);
+                }
+                if let Some(data) = fetch_json::<Vec<AppetiteBreach>>("http://localhost:8081/appetite-breaches?per_page=20").await {
+                    breaches.set(data);
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_input = |field: &'static str| {
+        let form = form.clone();
+        Callback::from(move |e: InputEvent| {
+            let value = e.target_unchecked_into::<HtmlInputElement>().value();
+            let mut data = (*form).clone();
+            match field {
+                "category" => data.category = value,
+                "appetite" => data.appetite = value.parse().unwrap_or(0),
+                "tolerance" => data.tolerance = value.parse().unwrap_or(0),
+                "fmea_appetite" => data.fmea_appetite = value.parse().ok(),
+                "fmea_tolerance" => data.fmea_tolerance = value.parse().ok(),
+                "weighted_appetite" => data.weighted_appetite = value.parse().ok(),
+                "weighted_tolerance" => data.weighted_tolerance = value.parse().ok(),
+                _ => {}
+            }
+            form.set(data);
+        })
+    };
+
+    let reset_form = {
+        let form = form.clone();
+        let editing = editing.clone();
+        let errors = errors.clone();
+        Callback::from(move |_: ()| {
+            form.set(AppetitePayload::default());
+            editing.set(None);
+            errors.set(FieldErrors::new());
+        })
+    };
+
+    let on_submit = {
+        let form = form.clone();
+        let editing = editing.clone();
+        let errors = errors.clone();
+        let message = message.clone();
+        let reload = reload.clone();
+        let reset_form = reset_form.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let payload = (*form).clone();
+            let local = validation::validate_appetite(&payload.category, payload.appetite, payload.tolerance, &rules);
+            if !local.is_empty() {
+                errors.set(local);
+                return;
+            }
+            let editing = (*editing).clone();
+            let errors = errors.clone();
+            let message = message.clone();
+            let reload = reload.clone();
+            let reset_form = reset_form.clone();
+            spawn_local(async move {
+                let request = match &editing {
+                    Some((id, version)) => Request::put(&format!("http://localhost:8081/risk-appetites/{}", id))
+                        .header("If-Match", &format!("\"{}\"", version)),
+                    None => Request::post("http://localhost:8081/risk-appetites"),
+                };
+                let resp = request
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .json(&payload)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => {
+                        message.set(None);
+                        reset_form.emit(());
+                        reload.set(*reload + 1);
+                    }
+                    Ok(response) if response.status() == 412 => {
+                        message.set(Some("Ces seuils ont été modifiés entre-temps : rechargés, à ressaisir.".to_string()));
+                        reset_form.emit(());
+                        reload.set(*reload + 1);
+                    }
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : seuils non enregistrés.".to_string())),
+                }
+            });
+        })
+    };
+
+    let on_delete = {
+        let message = message.clone();
+        let reload = reload.clone();
+        Callback::from(move |(id, version): (String, i32)| {
+            let confirmed = web_sys::window()
+                .and_then(|win| win.confirm_with_message("Supprimer les seuils de cette catégorie ?").ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+            let message = message.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                let resp = Request::delete(&format!("http://localhost:8081/risk-appetites/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .header("If-Match", &format!("\"{}\"", version))
+                    .send()
+                    .await;
+                match resp {
+                    Ok(response) if response.ok() => reload.set(*reload + 1),
+                    Ok(response) => message.set(Some(api::error_message(&response).await)),
+                    Err(_) => message.set(Some("Erreur réseau : seuils non supprimés.".to_string())),
+                }
+            });
+        })
+    };
+
+    html! {
+        <div>
+            <h2>{ "Appétence au risque" }</h2>
+            <h4>{ "Seuils par catégorie" }</h4>
+            <p>{ "Seuils appliqués au score inhérent des risques de la catégorie : l'appétence est le niveau recherché, la tolérance le niveau maximal acceptable. Les scores AMDEC et pondérés ont leurs propres seuils ; sans eux, ces risques ne sont pas comparés." }</p>
+            if appetites.is_empty() {
+                <p>{ "Aucun seuil défini." }</p>
+            } else {
+                <table class="table table-sm" style="width: auto;">
+                    <thead>
+                        <tr>
+                            <th>{ "Catégorie" }</th>
+                            <th>{ "Appétence" }</th>
+                            <th>{ "Tolérance" }</th>
+                            <th>{ "AMDEC (appétence / tolérance)" }</th>
+                            <th>{ "Pondéré (appétence / tolérance)" }</th>
+                            if can_manage {
+                                <th></th>
+                            }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for appetites.iter().map(|appetite| html! {
+                            <tr key={appetite.id.clone()}>
+                                <td>{ &appetite.category }</td>
+                                <td>{ appetite.appetite }</td>
+                                <td>{ appetite.tolerance }</td>
+                                <td>{ thresholds_label(appetite.fmea_appetite, appetite.fmea_tolerance) }</td>
+                                <td>{ thresholds_label(appetite.weighted_appetite, appetite.weighted_tolerance) }</td>
+                                if can_manage {
+                                    <td>
+                                        <button onclick={
+                                            let form = form.clone();
+                                            let editing = editing.clone();
+                                            let appetite = appetite.clone();
+                                            Callback::from(move |_| {
+                                                form.set(AppetitePayload {
+                                                    category: appetite.category.clone(),
+                                                    appetite: appetite.appetite,
+                                                    tolerance: appetite.tolerance,
+                                                    fmea_appetite: appetite.fmea_appetite,
+                                                    fmea_tolerance: appetite.fmea_tolerance,
+                                                    weighted_appetite: appetite.weighted_appetite,
+                                                    weighted_tolerance: appetite.weighted_tolerance,
+                                                });
+                                                editing.set(Some((appetite.id.clone(), appetite.version)));
+                                            })
+                                        }>{ "✏ Modifier" }</button>
+                                        <button onclick={
+                                            let on_delete = on_delete.clone();
+                                            let key = (appetite.id.clone(), appetite.version);
+                                            Callback::from(move |_| on_delete.emit(key.clone()))
+                                        }>{ "🗑 Supprimer" }</button>
+                                    </td>
+                                }
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+            if can_manage {
+                <form onsubmit={on_submit}>
+                    <input type="text" placeholder="Catégorie" value={form.category.clone()} oninput={on_input("category")} />
+                    <FieldError errors={(*errors).clone()} field="category" />
+                    <label>{ " Appétence : " }
+                        <input type="number" min="0" value={form.appetite.to_string()} oninput={on_input("appetite")} />
+                    </label>
+                    <FieldError errors={(*errors).clone()} field="appetite" />
+                    <label>{ " Tolérance : " }
+                        <input type="number" min="0" value={form.tolerance.to_string()} oninput={on_input("tolerance")} />
+                    </label>
+                    <FieldError errors={(*errors).clone()} field="tolerance" />
+                    <label>{ " AMDEC : " }
+                        <input type="number" min="0" placeholder="Appétence" value={optional_value(form.fmea_appetite)} oninput={on_input("fmea_appetite")} />
+                        <input type="number" min="0" placeholder="Tolérance" value={optional_value(form.fmea_tolerance)} oninput={on_input("fmea_tolerance")} />
+                    </label>
+                    <FieldError errors={(*errors).clone()} field="fmea_appetite" />
+                    <FieldError errors={(*errors).clone()} field="fmea_tolerance" />
+                    <label>{ " Pondéré : " }
+                        <input type="number" min="0" placeholder="Appétence" value={optional_value(form.weighted_appetite)} oninput={on_input("weighted_appetite")} />
+                        <input type="number" min="0" placeholder="Tolérance" value={optional_value(form.weighted_tolerance)} oninput={on_input("weighted_tolerance")} />
+                    </label>
+                    <FieldError errors={(*errors).clone()} field="weighted_appetite" />
+                    <FieldError errors={(*errors).clone()} field="weighted_tolerance" />
+                    <button type="submit">{ if editing.is_some() { "Enregistrer" } else { "Ajouter" } }</button>
+                    if editing.is_some() {
+                        <button type="button" onclick={reset_form.reform(|_: MouseEvent| ())}>{ "Annuler" }</button>
+                    }
+                </form>
+            }
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+
+            <h4>{ "Risques hors tolérance" }</h4>
+            if excesses.is_empty() {
+                <p>{ "Aucun risque ne dépasse la tolérance de sa catégorie." }</p>
+            } else {
+                <table class="table table-sm">
+                    <thead>
+                        <tr>
+                            <th>{ "Risque" }</th>
+                            <th>{ "Catégorie" }</th>
+                            <th>{ "Score" }</th>
+                            <th>{ "Tolérance" }</th>
+                            <th>{ "Écart" }</th>
+                            <th>{ "Statut" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { for excesses.iter().map(|excess| html! {
+                            <tr key={excess.risk_id.clone()}>
+                                <td>
+                                    <Link<Route> to={Route::ViewRisk { id: excess.risk_id.clone() }}>{ &excess.title }</Link<Route>>
+                                </td>
+                                <td>{ &excess.category }</td>
+                                <td title={scoring_method_label(&excess.scoring_method).to_string()}>
+                                    { excess.score }{ " " }
+                                    <RiskLevelBadge level={excess.level.clone()} levels={(*levels).clone()} />
+                                </td>
+                                <td>{ format!("{} (appétence {})", excess.tolerance, excess.appetite) }</td>
+                                <td style="color: #c62828; font-weight: bold;">{ format!("+{}", excess.excess) }</td>
+                                <td>{ &excess.status }</td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+
+            <h4>{ "Derniers dépassements" }</h4>
+            if breaches.is_empty() {
+                <p>{ "Aucun dépassement enregistré." }</p>
+            } else {
+                <ul>
+                    { for breaches.iter().map(|breach| html! {
+                        <li key={breach.id.clone()}>
+                            { format!("{} – ", breach.created_at.get(..16).unwrap_or(&breach.created_at).replace('T', " ")) }
+                            <Link<Route> to={Route::ViewRisk { id: breach.risk_id.clone() }}>{ &breach.risk_title }</Link<Route>>
+                            { format!(
+                                " : score {} ({}) au-delà de la tolérance {} ({}) – {} par {}",
+                                breach.score,
+                                scoring_method_label(&breach.scoring_method),
+                                breach.tolerance,
+                                breach.category,
+                                breach_trigger_label(&breach.trigger),
+                                breach.triggered_by_username.clone().unwrap_or_else(|| "–".to_string()),
+                            ) }
+                        </li>
+                    }) }
+                </ul>
+            }
+        </div>
+    }
+}
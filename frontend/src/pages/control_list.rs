@@ -0,0 +1,166 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console, window, HtmlSelectElement};
+
+use crate::api;
+use crate::auth;
+use crate::components::pager::{total_count, Pager, PER_PAGE};
+use crate::control::{effectiveness_label, type_label, Control, CONTROL_TYPES};
+use crate::Route;
+
+#[function_component(ControlList)]
+pub fn control_list() -> Html {
+    let controls = use_state(Vec::<Control>::new);
+    let loading = use_state(|| true);
+    let page = use_state(|| 1u32);
+    let total = use_state(|| 0u32);
+    // Filtre sur le type de contrôle, vide pour tous
+    let control_type = use_state(String::new);
+    let navigator = use_navigator().unwrap();
+
+    let delete_control = {
+        let controls = controls.clone();
+        let total = total.clone();
+        Callback::from(move |(id, version): (String, i32)| {
+            let controls = controls.clone();
+            let total = total.clone();
+            spawn_local(async move {
+                let res = Request::delete(&format!("http://localhost:8081/controls/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .header("If-Match", &format!("\"{}\"", version))
+                    .send()
+                    .await;
+                if let Ok(response) = res {
+                    let alert = if response.ok() {
+                        let updated: Vec<Control> = controls.iter().filter(|c| c.id != id).cloned().collect();
+                        controls.set(updated);
+                        total.set(total.saturating_sub(1));
+                        "Contrôle supprimé avec succès !".to_string()
+                    } else if response.status() == 412 {
+                        "Ce contrôle a été modifié entre-temps : rechargez la liste avant de le supprimer.".to_string()
+                    } else {
+                        format!("Échec de la suppression : {}", api::error_message(&response).await)
+                    };
+                    if let Some(win) = window() {
+                        let _ = win.alert_with_message(&alert);
+                    }
+                }
+            });
+        })
+    };
+
+    {
+        let controls = controls.clone();
+        let loading = loading.clone();
+        let total = total.clone();
+        use_effect_with((*page, (*control_type).clone()), move |(page, control_type)| {
+            let mut query = vec![("page", page.to_string()), ("per_page", PER_PAGE.to_string())];
+            if !control_type.is_empty() {
+                query.push(("control_type", control_type.clone()));
+            }
+            spawn_local(async move {
+                match Request::get("http://localhost:8081/controls")
+                    .query(query)
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await
+                {
+                    Ok(resp) => {
+                        total.set(total_count(&resp));
+                        match resp.json::<Vec<Control>>().await {
+                            Ok(data) => controls.set(data),
+                            Err(err) => console::log_1(&format!("Erreur JSON: {:?}", err).into()),
+                        }
+                    }
+                    Err(err) => console::log_1(&format!("Erreur: {:?}", err).into()),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_page = {
+        let page = page.clone();
+        Callback::from(move |next: u32| page.set(next))
+    };
+
+    let on_type = {
+        let control_type = control_type.clone();
+        let page = page.clone();
+        Callback::from(move |e: Event| {
+            control_type.set(e.target_unchecked_into::<HtmlSelectElement>().value());
+            page.set(1);
+        })
+    };
+
+    html! {
+        <div>
+            <h2>{ "Bibliothèque de contrôles" }</h2>
+            <label>{ "Type : " }
+                <select onchange={on_type}>
+                    <option value="" selected={control_type.is_empty()}>{ "Tous" }</option>
+                    { for CONTROL_TYPES.iter().map(|value| html! {
+                        <option value={*value} selected={*control_type == *value}>{ type_label(value) }</option>
+                    }) }
+                </select>
+            </label>
+            if *loading {
+                <p>{ "Chargement en cours..." }</p>
+            } else if controls.is_empty() {
+                <p>{ "Aucun contrôle enregistré." }</p>
+            } else {
+                <ul>
+                    { for controls.iter().map(|control| html! {
+                        <li key={control.id.clone()}>
+                            <strong>{ format!("{} [{}]", &control.name, type_label(&control.control_type)) }</strong>
+                            <p>{ format!(
+                                "Conception : {} — Fonctionnement : {}",
+                                effectiveness_label(&control.design_effectiveness),
+                                effectiveness_label(&control.operating_effectiveness),
+                            ) }</p>
+                            <p>{ format!(
+                                "Responsable : {} — {} risque(s) couvert(s)",
+                                control.owner.clone().unwrap_or_else(|| "–".to_string()),
+                                control.risk_ids.len(),
+                            ) }</p>
+                            if auth::can_edit() {
+                                <button onclick={
+                                    let navigator = navigator.clone();
+                                    let id = control.id.clone();
+                                    Callback::from(move |_| navigator.push(&Route::EditControl { id: id.clone() }))
+                                }>
+                                    { "✏ Modifier" }
+                                </button>
+                            }
+                            <button onclick={
+                                let navigator = navigator.clone();
+                                let id = control.id.clone();
+                                Callback::from(move |_| navigator.push(&Route::ViewControl { id: id.clone() }))
+                            }>
+                                { "🔍 Voir les détails" }
+                            </button>
+                            if auth::can_delete() {
+                                <button onclick={
+                                    let delete_control = delete_control.clone();
+                                    let id = control.id.clone();
+                                    let version = control.version;
+                                    Callback::from(move |_| {
+                                        if window().unwrap().confirm_with_message("Voulez-vous vraiment supprimer ce contrôle ?").unwrap_or(false) {
+                                            delete_control.emit((id.clone(), version));
+                                        }
+                                    })
+                                }>
+                                    { "🗑 Supprimer" }
+                                </button>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+            <Pager page={*page} total={*total} on_page={on_page} />
+        </div>
+    }
+}
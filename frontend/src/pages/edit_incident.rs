@@ -0,0 +1,153 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+
+use crate::api;
+use crate::auth;
+use crate::components::incident_form::{IncidentData, IncidentForm};
+use crate::incident::Incident;
+use crate::validation::{self, FieldErrors};
+use crate::Route;
+
+#[function_component(EditIncident)]
+pub fn edit_incident() -> Html {
+    let navigator = use_navigator().unwrap();
+    let route = use_route::<Route>().unwrap();
+    let id = if let Route::EditIncident { id } = route { id } else { String::new() };
+
+    let data = use_state(|| None::<IncidentData>);
+    let etag = use_state(String::new);
+    // Version du serveur reçue avec un 412
+    let conflict = use_state(|| None::<(Incident, String)>);
+    let errors = use_state(FieldErrors::new);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        let data = data.clone();
+        let etag = etag.clone();
+        let id = id.clone();
+        use_effect_with(id.clone(), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/incidents/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    etag.set(resp.headers().get("etag").unwrap_or_default());
+                    if let Ok(incident) = resp.json::<Incident>().await {
+                        data.set(Some(IncidentData::from(&incident)));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_change = {
+        let data = data.clone();
+        Callback::from(move |updated: IncidentData| data.set(Some(updated)))
+    };
+
+    let on_submit = {
+        let etag = etag.clone();
+        let conflict = conflict.clone();
+        let errors = errors.clone();
+        let message = message.clone();
+        let id = id.clone();
+        Callback::from(move |incident: IncidentData| {
+            let if_match = (*etag).clone();
+            let conflict = conflict.clone();
+            let errors = errors.clone();
+            let message = message.clone();
+            let navigator = navigator.clone();
+            let id = id.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let res = Request::put(&format!("http://localhost:8081/incidents/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .header("Content-Type", "application/json")
+                    .header("If-Match", &if_match)
+                    .json(&incident)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match res {
+                    Ok(response) if response.ok() => navigator.push(&Route::ViewIncident { id }),
+                    Ok(response) if response.status() == 412 => {
+                        let server_etag = response.headers().get("etag").unwrap_or_default();
+                        match api::read_error(&response).await.current::<Incident>() {
+                            Some(current) => conflict.set(Some((current, server_etag))),
+                            None => message.set(Some("Conflit de version : rechargez la page.".to_string())),
+                        }
+                    }
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : impossible d'enregistrer.".to_string())),
+                }
+            });
+        })
+    };
+
+    // Conserver mes modifications : le prochain enregistrement écrasera la version du serveur
+    let on_keep_mine = {
+        let etag = etag.clone();
+        let conflict = conflict.clone();
+        let message = message.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some((_, server_etag)) = &*conflict {
+                etag.set(server_etag.clone());
+                message.set(Some("Enregistrez de nouveau pour écraser la version du serveur.".to_string()));
+            }
+            conflict.set(None);
+        })
+    };
+
+    let on_reload = {
+        let data = data.clone();
+        let etag = etag.clone();
+        let conflict = conflict.clone();
+        let message = message.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some((current, server_etag)) = &*conflict {
+                data.set(Some(IncidentData::from(current)));
+                etag.set(server_etag.clone());
+            }
+            message.set(None);
+            conflict.set(None);
+        })
+    };
+
+    html! {
+        <div>
+            <h2>{ "Modifier un Incident" }</h2>
+            if let Some(current) = &*data {
+                <IncidentForm
+                    data={current.clone()}
+                    on_change={on_change}
+                    on_submit={on_submit}
+                    errors={(*errors).clone()}
+                    submit_label="Enregistrer"
+                />
+            } else {
+                <p>{ "Chargement..." }</p>
+            }
+
+            if conflict.is_some() {
+                <div class="alert alert-warning" role="alertdialog">
+                    <h4>{ "Conflit de modification" }</h4>
+                    <p>{ "Cet incident a été modifié par un autre utilisateur depuis l'ouverture du formulaire." }</p>
+                    <button type="button" onclick={on_keep_mine}>{ "Conserver mes modifications" }</button>
+                    <button type="button" onclick={on_reload}>{ "Reprendre la version actuelle" }</button>
+                </div>
+            }
+
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </div>
+    }
+}
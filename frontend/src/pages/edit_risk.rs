@@ -1,26 +1,79 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use gloo_net::http::Request;
+use crate::api;
+use crate::auth;
+use crate::components::scoring_method::ScoringMethodSelect;
+use crate::validation::{self, use_validation_rules, FieldError, FieldErrors};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use web_sys::HtmlInputElement;
 use std::rc::Rc;
 
 use crate::Route;
+use crate::pages::add_risk::split_list;
 
-#[derive(Serialize, Deserialize, Clone, Default)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
 struct Risk {
-    #[serde(skip_serializing)]
-    id: String,
     title: String,
     description: Option<String>,
     impact: i32,
     probability: i32,
+    residual_impact: Option<i32>,
+    residual_probability: Option<i32>,
+    target_impact: Option<i32>,
+    target_probability: Option<i32>,
     status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scoring_method: Option<String>,
     external_id: Option<String>,
     category: Option<String>,
     location: Option<String>,
     regulation: Option<String>,
     control_measure_id: Option<String>,
+    risk_map: Option<String>,
+    threat_map: Option<String>,
+    technology: Option<String>,
+    team: Option<String>,
+    business_unit: Option<String>,
+    owner: Option<String>,
+    owner_delegate: Option<String>,
+    source: Option<String>,
+    evaluation_method: Option<String>,
+    evaluation_notes: Option<String>,
+    additional_notes: Option<String>,
+    tags: Vec<String>,
+    assets: Vec<String>,
+    stakeholders: Vec<String>,
+}
+
+// Version du serveur reçue avec un 412, et son ETag
+#[derive(Clone, PartialEq)]
+struct Conflict {
+    current: Risk,
+    etag: String,
+}
+
+// Champs modifiés localement qui diffèrent de la version du serveur : (champ, ma valeur, valeur serveur)
+fn conflicting_fields(mine: &Risk, current: &Risk) -> Vec<(String, String, String)> {
+    let (Ok(Value::Object(mine)), Ok(Value::Object(current))) = (serde_json::to_value(mine), serde_json::to_value(current)) else {
+        return Vec::new();
+    };
+    mine.iter()
+        .filter_map(|(field, value)| {
+            let other = current.get(field).cloned().unwrap_or(Value::Null);
+            (*value != other).then(|| (field.clone(), display_value(value), display_value(&other)))
+        })
+        .collect()
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Null => "∅".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(display_value).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
 }
 
 #[function_component(EditRisk)]
@@ -35,16 +88,24 @@ pub fn edit_risk() -> Html {
     });
 
     let risk = use_state(Risk::default);
+    let etag = use_state(String::new);
+    let conflict = use_state(|| None::<Conflict>);
+    let message = use_state(|| None as Option<String>);
+    let errors = use_state(FieldErrors::new);
+    let rules = use_validation_rules();
     {
         let risk = risk.clone();
+        let etag = etag.clone();
         let rc_id = rc_id.clone();
         use_effect_with(rc_id.clone(), move |_| {
             wasm_bindgen_futures::spawn_local(async move {
                 let response = Request::get(&format!("http://localhost:8081/risks/{}", rc_id.clone()))
+                .header("Authorization", &auth::bearer())
                 .send()
                 .await;
             
             if let Ok(resp) = response {
+                etag.set(resp.headers().get("etag").unwrap_or_default());
                 if let Ok(fetched) = resp.json::<Risk>().await {
                     risk.set(fetched);
                 }
@@ -65,12 +126,30 @@ pub fn edit_risk() -> Html {
                 "description" => updated.description = Some(value),
                 "impact" => updated.impact = value.parse().unwrap_or(0),
                 "probability" => updated.probability = value.parse().unwrap_or(0),
+                "residual_impact" => updated.residual_impact = value.parse().ok(),
+                "residual_probability" => updated.residual_probability = value.parse().ok(),
+                "target_impact" => updated.target_impact = value.parse().ok(),
+                "target_probability" => updated.target_probability = value.parse().ok(),
                 "status" => updated.status = value,
                 "external_id" => updated.external_id = Some(value),
                 "category" => updated.category = Some(value),
                 "location" => updated.location = Some(value),
                 "regulation" => updated.regulation = Some(value),
                 "control_measure_id" => updated.control_measure_id = Some(value),
+                "risk_map" => updated.risk_map = Some(value),
+                "threat_map" => updated.threat_map = Some(value),
+                "technology" => updated.technology = Some(value),
+                "team" => updated.team = Some(value),
+                "business_unit" => updated.business_unit = Some(value),
+                "owner" => updated.owner = Some(value),
+                "owner_delegate" => updated.owner_delegate = Some(value),
+                "source" => updated.source = Some(value),
+                "evaluation_method" => updated.evaluation_method = Some(value),
+                "evaluation_notes" => updated.evaluation_notes = Some(value),
+                "additional_notes" => updated.additional_notes = Some(value),
+                "tags" => updated.tags = split_list(&value),
+                "assets" => updated.assets = split_list(&value),
+                "stakeholders" => updated.stakeholders = split_list(&value),
                 _ => {}
             }
             form.set(updated);
@@ -79,46 +158,218 @@ pub fn edit_risk() -> Html {
 
     let onsubmit = {
         let risk = risk.clone();
+        let etag = etag.clone();
+        let conflict = conflict.clone();
+        let message = message.clone();
+        let errors = errors.clone();
+        let rules = rules.clone();
         let navigator = navigator.clone();
         let rc_id_submit = rc_id.clone();
     
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
             let data = (*risk).clone();
+
+            let mut invalid = validation::validate_risk(&data.title, data.impact, data.probability, &rules);
+            validation::check_score_pair(&mut invalid, ("residual_impact", data.residual_impact), ("residual_probability", data.residual_probability), &rules);
+            validation::check_score_pair(&mut invalid, ("target_impact", data.target_impact), ("target_probability", data.target_probability), &rules);
+            if !invalid.is_empty() {
+                errors.set(invalid);
+                message.set(Some("Certains champs sont invalides.".to_string()));
+                return;
+            }
+            errors.set(FieldErrors::new());
+
+            let if_match = (*etag).clone();
+            let conflict = conflict.clone();
+            let message = message.clone();
+            let errors = errors.clone();
             let navigator = navigator.clone(); // <-- cloner ici pour éviter FnOnce
             let rc_id_inner = (*rc_id_submit).clone();
     
             wasm_bindgen_futures::spawn_local(async move {
                 let res = Request::put(&format!("http://localhost:8081/risks/{}", rc_id_inner))
+                    .header("Authorization", &auth::bearer())
                     .header("Content-Type", "application/json")
+                    .header("If-Match", &if_match)
                     .json(&data)
                     .unwrap()
                     .send()
                     .await;
     
-                if let Ok(response) = res {
-                    if response.ok() {
-                        navigator.push(&Route::Risks);
+                match res {
+                    Ok(response) if response.ok() => navigator.push(&Route::Risks),
+                    // Modifié par quelqu'un d'autre depuis le chargement du formulaire
+                    Ok(response) if response.status() == 412 => {
+                        let server_etag = response.headers().get("etag").unwrap_or_default();
+                        match api::read_error(&response).await.current::<Risk>() {
+                            Some(current) => conflict.set(Some(Conflict { current, etag: server_etag })),
+                            None => message.set(Some("Conflit de version : rechargez la page.".to_string())),
+                        }
                     }
+                    Ok(response) => {
+                        let error = api::read_error(&response).await;
+                        errors.set(validation::field_errors(&error));
+                        message.set(Some(error.message));
+                    }
+                    Err(_) => message.set(Some("Erreur réseau : impossible d'enregistrer.".to_string())),
                 }
             });
         })
     };
 
+    // Conserver mes modifications : le prochain enregistrement écrasera la version du serveur
+    let on_keep_mine = {
+        let etag = etag.clone();
+        let conflict = conflict.clone();
+        let message = message.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(c) = &*conflict {
+                etag.set(c.etag.clone());
+                message.set(Some("Enregistrez de nouveau pour écraser la version du serveur.".to_string()));
+            }
+            conflict.set(None);
+        })
+    };
+
+    let on_reload = {
+        let risk = risk.clone();
+        let etag = etag.clone();
+        let conflict = conflict.clone();
+        let message = message.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(c) = &*conflict {
+                risk.set(c.current.clone());
+                etag.set(c.etag.clone());
+            }
+            message.set(None);
+            conflict.set(None);
+        })
+    };
+
+    let on_scoring_method = {
+        let form = form.clone();
+        Callback::from(move |method: String| {
+            let mut updated = (*form).clone();
+            updated.scoring_method = Some(method);
+            form.set(updated);
+        })
+    };
+
     html! {
         <form onsubmit={onsubmit}>
             <h2>{ "Modifier un Risque" }</h2>
             <input type="text" value={risk.title.clone()} oninput={oninput("title")} />
+            <FieldError errors={(*errors).clone()} field="title" />
             <input type="text" value={risk.description.clone().unwrap_or_default()} oninput={oninput("description")} />
-            <input type="number" value={risk.impact.to_string()} oninput={oninput("impact")} />
-            <input type="number" value={risk.probability.to_string()} oninput={oninput("probability")} />
+            <input
+                type="number"
+                min={rules.impact_scale.min.to_string()}
+                max={rules.impact_scale.max.to_string()}
+                value={risk.impact.to_string()}
+                oninput={oninput("impact")}
+            />
+            <FieldError errors={(*errors).clone()} field="impact" />
+            <input
+                type="number"
+                min={rules.probability_scale.min.to_string()}
+                max={rules.probability_scale.max.to_string()}
+                value={risk.probability.to_string()}
+                oninput={oninput("probability")}
+            />
+            <FieldError errors={(*errors).clone()} field="probability" />
+            <input
+                type="number"
+                min={rules.impact_scale.min.to_string()}
+                max={rules.impact_scale.max.to_string()}
+                placeholder={format!("Impact résiduel ({}-{})", rules.impact_scale.min, rules.impact_scale.max)}
+                value={risk.residual_impact.map(|v| v.to_string()).unwrap_or_default()}
+                oninput={oninput("residual_impact")}
+            />
+            <FieldError errors={(*errors).clone()} field="residual_impact" />
+            <input
+                type="number"
+                min={rules.probability_scale.min.to_string()}
+                max={rules.probability_scale.max.to_string()}
+                placeholder={format!("Probabilité résiduel ({}-{})", rules.probability_scale.min, rules.probability_scale.max)}
+                value={risk.residual_probability.map(|v| v.to_string()).unwrap_or_default()}
+                oninput={oninput("residual_probability")}
+            />
+            <FieldError errors={(*errors).clone()} field="residual_probability" />
+            <input
+                type="number"
+                min={rules.impact_scale.min.to_string()}
+                max={rules.impact_scale.max.to_string()}
+                placeholder={format!("Impact cible ({}-{})", rules.impact_scale.min, rules.impact_scale.max)}
+                value={risk.target_impact.map(|v| v.to_string()).unwrap_or_default()}
+                oninput={oninput("target_impact")}
+            />
+            <FieldError errors={(*errors).clone()} field="target_impact" />
+            <input
+                type="number"
+                min={rules.probability_scale.min.to_string()}
+                max={rules.probability_scale.max.to_string()}
+                placeholder={format!("Probabilité cible ({}-{})", rules.probability_scale.min, rules.probability_scale.max)}
+                value={risk.target_probability.map(|v| v.to_string()).unwrap_or_default()}
+                oninput={oninput("target_probability")}
+            />
+            <FieldError errors={(*errors).clone()} field="target_probability" />
+            <ScoringMethodSelect
+                selected={risk.scoring_method.clone().unwrap_or_default()}
+                on_change={on_scoring_method}
+            />
             <input type="text" value={risk.status.clone()} oninput={oninput("status")} />
             <input type="text" value={risk.external_id.clone().unwrap_or_default()} oninput={oninput("external_id")} />
             <input type="text" value={risk.category.clone().unwrap_or_default()} oninput={oninput("category")} />
             <input type="text" value={risk.location.clone().unwrap_or_default()} oninput={oninput("location")} />
             <input type="text" value={risk.regulation.clone().unwrap_or_default()} oninput={oninput("regulation")} />
             <input type="text" value={risk.control_measure_id.clone().unwrap_or_default()} oninput={oninput("control_measure_id")} />
+            <input type="text" placeholder="Cartographie des risques" value={risk.risk_map.clone().unwrap_or_default()} oninput={oninput("risk_map")} />
+            <input type="text" placeholder="Cartographie des menaces" value={risk.threat_map.clone().unwrap_or_default()} oninput={oninput("threat_map")} />
+            <input type="text" placeholder="Technologie" value={risk.technology.clone().unwrap_or_default()} oninput={oninput("technology")} />
+            <input type="text" placeholder="Équipe" value={risk.team.clone().unwrap_or_default()} oninput={oninput("team")} />
+            <input type="text" placeholder="Unité métier" value={risk.business_unit.clone().unwrap_or_default()} oninput={oninput("business_unit")} />
+            <input type="text" placeholder="Propriétaire" value={risk.owner.clone().unwrap_or_default()} oninput={oninput("owner")} />
+            <input type="text" placeholder="Délégué du propriétaire" value={risk.owner_delegate.clone().unwrap_or_default()} oninput={oninput("owner_delegate")} />
+            <input type="text" placeholder="Source" value={risk.source.clone().unwrap_or_default()} oninput={oninput("source")} />
+            <input type="text" placeholder="Méthode d'évaluation" value={risk.evaluation_method.clone().unwrap_or_default()} oninput={oninput("evaluation_method")} />
+            <input type="text" placeholder="Notes d'évaluation" value={risk.evaluation_notes.clone().unwrap_or_default()} oninput={oninput("evaluation_notes")} />
+            <input type="text" placeholder="Notes complémentaires" value={risk.additional_notes.clone().unwrap_or_default()} oninput={oninput("additional_notes")} />
+            <input type="text" placeholder="Tags (séparés par des virgules)" value={risk.tags.join(",")} oninput={oninput("tags")} />
+            <input type="text" placeholder="Actifs (séparés par des virgules)" value={risk.assets.join(",")} oninput={oninput("assets")} />
+            <input type="text" placeholder="Parties prenantes (séparées par des virgules)" value={risk.stakeholders.join(",")} oninput={oninput("stakeholders")} />
             <button type="submit">{ "Enregistrer" }</button>
+
+            if let Some(c) = &*conflict {
+                <div class="alert alert-warning" role="alertdialog">
+                    <h4>{ "Conflit de modification" }</h4>
+                    <p>{ "Ce risque a été modifié par un autre utilisateur depuis l'ouverture du formulaire." }</p>
+                    <table class="table table-sm">
+                        <thead>
+                            <tr>
+                                <th>{ "Champ" }</th>
+                                <th>{ "Votre valeur" }</th>
+                                <th>{ "Valeur actuelle" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for conflicting_fields(&risk, &c.current).into_iter().map(|(field, mine, theirs)| html! {
+                                <tr key={field.clone()}>
+                                    <td>{ field }</td>
+                                    <td>{ mine }</td>
+                                    <td>{ theirs }</td>
+                                </tr>
+                            }) }
+                        </tbody>
+                    </table>
+                    <button type="button" onclick={on_keep_mine}>{ "Conserver mes modifications" }</button>
+                    <button type="button" onclick={on_reload}>{ "Reprendre la version actuelle" }</button>
+                </div>
+            }
+
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
         </form>
     }
 }
@@ -0,0 +1,137 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{console, window};
+
+use crate::api;
+use crate::auth;
+use crate::components::pager::{total_count, Pager, PER_PAGE};
+use crate::incident::{severity_label, status_label, Incident};
+use crate::Route;
+
+#[function_component(IncidentList)]
+pub fn incident_list() -> Html {
+    let incidents = use_state(Vec::<Incident>::new);
+    let loading = use_state(|| true);
+    let page = use_state(|| 1u32);
+    let total = use_state(|| 0u32);
+    let navigator = use_navigator().unwrap();
+
+    let delete_incident = {
+        let incidents = incidents.clone();
+        let total = total.clone();
+        Callback::from(move |(id, version): (String, i32)| {
+            let incidents = incidents.clone();
+            let total = total.clone();
+            spawn_local(async move {
+                let res = Request::delete(&format!("http://localhost:8081/incidents/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .header("If-Match", &format!("\"{}\"", version))
+                    .send()
+                    .await;
+                if let Ok(response) = res {
+                    let alert = if response.ok() {
+                        let updated: Vec<Incident> = incidents.iter().filter(|i| i.id != id).cloned().collect();
+                        incidents.set(updated);
+                        total.set(total.saturating_sub(1));
+                        "Incident supprimé avec succès !".to_string()
+                    } else if response.status() == 412 {
+                        "Cet incident a été modifié entre-temps : rechargez la liste avant de le supprimer.".to_string()
+                    } else {
+                        format!("Échec de la suppression : {}", api::error_message(&response).await)
+                    };
+                    if let Some(win) = window() {
+                        let _ = win.alert_with_message(&alert);
+                    }
+                }
+            });
+        })
+    };
+
+    {
+        let incidents = incidents.clone();
+        let loading = loading.clone();
+        let total = total.clone();
+        use_effect_with(*page, move |page| {
+            let page = page.to_string();
+            spawn_local(async move {
+                match Request::get("http://localhost:8081/incidents")
+                    .query([("page", page), ("per_page", PER_PAGE.to_string())])
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await
+                {
+                    Ok(resp) => {
+                        total.set(total_count(&resp));
+                        match resp.json::<Vec<Incident>>().await {
+                            Ok(data) => incidents.set(data),
+                            Err(err) => console::log_1(&format!("Erreur JSON: {:?}", err).into()),
+                        }
+                    }
+                    Err(err) => console::log_1(&format!("Erreur: {:?}", err).into()),
+                }
+                loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_page = {
+        let page = page.clone();
+        Callback::from(move |next: u32| page.set(next))
+    };
+
+    html! {
+        <div>
+            <h2>{ "Liste des Incidents" }</h2>
+            if *loading {
+                <p>{ "Chargement en cours..." }</p>
+            } else if incidents.is_empty() {
+                <p>{ "Aucun incident enregistré." }</p>
+            } else {
+                <ul>
+                    { for incidents.iter().map(|incident| html! {
+                        <li key={incident.id.clone()}>
+                            <strong>{ format!("{} [{}]", &incident.title, status_label(&incident.status)) }</strong>
+                            <p>{ format!("Gravité : {}", severity_label(&incident.severity)) }</p>
+                            <p>{ format!("Déclaré le : {}", &incident.created_at) }</p>
+                            <p>{ format!("Description : {}", incident.description.clone().unwrap_or_default()) }</p>
+                            if auth::can_edit() {
+                                <button onclick={
+                                    let navigator = navigator.clone();
+                                    let id = incident.id.clone();
+                                    Callback::from(move |_| navigator.push(&Route::EditIncident { id: id.clone() }))
+                                }>
+                                    { "✏ Modifier" }
+                                </button>
+                            }
+                            <button onclick={
+                                let navigator = navigator.clone();
+                                let id = incident.id.clone();
+                                Callback::from(move |_| navigator.push(&Route::ViewIncident { id: id.clone() }))
+                            }>
+                                { "🔍 Voir les détails" }
+                            </button>
+                            if auth::can_delete() {
+                                <button onclick={
+                                    let delete_incident = delete_incident.clone();
+                                    let id = incident.id.clone();
+                                    let version = incident.version;
+                                    Callback::from(move |_| {
+                                        if window().unwrap().confirm_with_message("Voulez-vous vraiment supprimer cet incident ?").unwrap_or(false) {
+                                            delete_incident.emit((id.clone(), version));
+                                        }
+                                    })
+                                }>
+                                    { "🗑 Supprimer" }
+                                </button>
+                            }
+                        </li>
+                    }) }
+                </ul>
+            }
+            <Pager page={*page} total={*total} on_page={on_page} />
+        </div>
+    }
+}
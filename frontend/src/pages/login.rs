@@ -0,0 +1,101 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+use web_sys::HtmlInputElement;
+
+use crate::api;
+use crate::auth;
+use crate::Route;
+
+#[derive(Serialize, Default, Clone)]
+struct Credentials {
+    username: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct LoginUser {
+    username: String,
+    role: String,
+}
+
+#[derive(Deserialize)]
+struct LoginResponse {
+    token: String,
+    expires_at: String,
+    user: LoginUser,
+}
+
+#[function_component(Login)]
+pub fn login() -> Html {
+    let navigator = use_navigator().unwrap();
+    let form = use_state(Credentials::default);
+    let message = use_state(|| None as Option<String>);
+
+    let on_submit = {
+        let form = form.clone();
+        let message = message.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let credentials = (*form).clone();
+            let message = message.clone();
+            let navigator = navigator.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let resp = Request::post("http://localhost:8081/auth/login")
+                    .header("Content-Type", "application/json")
+                    .json(&credentials)
+                    .unwrap()
+                    .send()
+                    .await;
+
+                match resp {
+                    Ok(response) if response.ok() => match response.json::<LoginResponse>().await {
+                        Ok(session) => {
+                            auth::save_session(&session.token, &session.user.username, &session.user.role, &session.expires_at);
+                            navigator.push(&Route::Risks);
+                        }
+                        Err(_) => message.set(Some("Réponse du serveur invalide.".to_string())),
+                    },
+                    Ok(response) if response.status() == 401 => {
+                        message.set(Some("Identifiants invalides.".to_string()));
+                    }
+                    Ok(response) => {
+                        message.set(Some(api::error_message(&response).await));
+                    }
+                    Err(_) => {
+                        message.set(Some("Erreur réseau : impossible de se connecter.".to_string()));
+                    }
+                }
+            });
+        })
+    };
+
+    let on_input = |field: &'static str| {
+        let form = form.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut data = (*form).clone();
+            match field {
+                "username" => data.username = input.value(),
+                "password" => data.password = input.value(),
+                _ => (),
+            }
+            form.set(data);
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit}>
+            <h2>{ "Connexion" }</h2>
+
+            <input type="text" placeholder="Identifiant" autocomplete="username" oninput={on_input("username")} />
+            <input type="password" placeholder="Mot de passe" autocomplete="current-password" oninput={on_input("password")} />
+            <button type="submit">{ "Se connecter" }</button>
+
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+        </form>
+    }
+}
@@ -1,4 +1,16 @@
 pub mod risk_list;
 pub mod add_risk;
 pub mod edit_risk;
-pub mod view_risk;
\ No newline at end of file
+pub mod view_risk;
+pub mod login;
+pub mod incident_list;
+pub mod add_incident;
+pub mod edit_incident;
+pub mod view_incident;
+pub mod search;
+pub mod control_list;
+pub mod add_control;
+pub mod edit_control;
+pub mod view_control;
+pub mod action_list;
+pub mod appetite_overview;
@@ -1,11 +1,17 @@
 use yew::prelude::*;
 use serde::Deserialize;
 use gloo_net::http::Request;
+use crate::api;
+use crate::auth;
 use wasm_bindgen_futures::spawn_local;
 use web_sys::console;
 use web_sys::window;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew_router::prelude::*;
 use crate::Route;
+use crate::components::pager::{total_count, Pager, PER_PAGE};
+use crate::components::status_change::STATUSES;
+use crate::components::risk_level::{use_risk_levels, RiskLevelBadge};
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct Risk {
@@ -15,39 +21,135 @@ pub struct Risk {
     pub impact: i32,
     pub probability: i32,
     pub status: String,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    pub version: i32,
     pub score: Option<i32>,
+    pub level: Option<String>,
+    pub residual_score: Option<i32>,
     pub external_id: Option<String>,
     pub category: Option<String>,
     pub location: Option<String>,
     pub regulation: Option<String>,
     pub control_measure_id: Option<String>,
+    pub next_review_at: Option<String>,
+    #[serde(default)]
+    pub review_overdue: bool,
 }
 
+// Filtres et tri de la barre de recherche, transmis tels quels à `GET /risks`
+#[derive(Clone, PartialEq)]
+struct RiskFilters {
+    q: String,
+    status: String,
+    category: String,
+    location: String,
+    regulation: String,
+    owner: String,
+    score_min: String,
+    score_max: String,
+    created_from: String,
+    created_to: String,
+    review_overdue: String,
+    sort: String,
+}
+
+impl Default for RiskFilters {
+    fn default() -> Self {
+        RiskFilters {
+            q: String::new(),
+            status: String::new(),
+            category: String::new(),
+            location: String::new(),
+            regulation: String::new(),
+            owner: String::new(),
+            score_min: String::new(),
+            score_max: String::new(),
+            created_from: String::new(),
+            created_to: String::new(),
+            review_overdue: String::new(),
+            sort: "-created_at".to_string(),
+        }
+    }
+}
+
+impl RiskFilters {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        [
+            ("q", &self.q),
+            ("status", &self.status),
+            ("category", &self.category),
+            ("location", &self.location),
+            ("regulation", &self.regulation),
+            ("owner", &self.owner),
+            ("score_min", &self.score_min),
+            ("score_max", &self.score_max),
+            ("created_from", &self.created_from),
+            ("created_to", &self.created_to),
+            ("review_overdue", &self.review_overdue),
+            ("sort", &self.sort),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.trim().is_empty())
+        .map(|(name, value)| (name, value.trim().to_string()))
+        .collect()
+    }
+}
+
+const SORTS: &[(&str, &str)] = &[
+    ("-created_at", "Plus récents"),
+    ("created_at", "Plus anciens"),
+    ("-score", "Score décroissant"),
+    ("score", "Score croissant"),
+    ("-residual_score", "Score résiduel décroissant"),
+    ("title", "Titre (A → Z)"),
+    ("-title", "Titre (Z → A)"),
+    ("status", "Statut"),
+    ("category", "Catégorie"),
+    ("-updated_at", "Dernière modification"),
+    ("next_review_at", "Prochaine revue"),
+];
+
 #[function_component(RiskList)]
 pub fn risk_list() -> Html {
-    let risks = use_state(|| vec![]);
+    let risks = use_state(Vec::<Risk>::new);
     let loading = use_state(|| true);
     let navigator = use_navigator().unwrap();
+    let levels = use_risk_levels();
+    // Saisie en cours dans la barre de filtres, appliquée à la validation du formulaire
+    let draft = use_state(RiskFilters::default);
+    let filters = use_state(RiskFilters::default);
+    let page = use_state(|| 1u32);
+    let total = use_state(|| 0u32);
+    let error = use_state(|| None as Option<String>);
 
     let delete_risk = {
         let risks = risks.clone();
-        Callback::from(move |id: String| {
+        let total = total.clone();
+        Callback::from(move |(id, version): (String, i32)| {
             let risks = risks.clone();
+            let total = total.clone();
             spawn_local(async move {
                 let res = Request::delete(&format!("http://localhost:8081/risks/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .header("If-Match", &format!("\"{}\"", version))
                     .send()
                     .await;
                 if let Ok(response) = res {
                     if response.ok() {
-                        let updated: Vec<Risk> = risks.iter().cloned().filter(|r: &Risk| r.id != id).collect();
+                        let updated: Vec<Risk> = risks.iter().filter(|r| r.id != id).cloned().collect();
                         risks.set(updated);
+                        total.set(total.saturating_sub(1));
                         if let Some(win) = window() {
                             let _ = win.alert_with_message("Risque supprimé avec succès !");
                         }
+                    } else if response.status() == 412 {
+                        if let Some(win) = window() {
+                            let _ = win.alert_with_message("Ce risque a été modifié entre-temps : rechargez la liste avant de le supprimer.");
+                        }
                     } else {
-                        console::log_1(&"Échec de la suppression".into());
+                        let message = api::error_message(&response).await;
+                        if let Some(win) = window() {
+                            let _ = win.alert_with_message(&format!("Échec de la suppression : {}", message));
+                        }
                     }
                 }
             });
@@ -57,18 +159,35 @@ pub fn risk_list() -> Html {
     {
         let risks = risks.clone();
         let loading = loading.clone();
-        use_effect_with((), move |_| {
+        let total = total.clone();
+        let error = error.clone();
+        use_effect_with(((*filters).clone(), *page), move |(filters, page)| {
+            let mut params = filters.params();
+            params.push(("page", page.to_string()));
+            params.push(("per_page", PER_PAGE.to_string()));
+            loading.set(true);
             wasm_bindgen_futures::spawn_local(async move {
                 match Request::get("http://localhost:8081/risks")
+                    .query(params.iter().map(|(name, value)| (*name, value.as_str())))
+                    .header("Authorization", &auth::bearer())
                     .send()
                     .await
                 {
-                    Ok(resp) => {
+                    Ok(resp) if resp.ok() => {
+                        total.set(total_count(&resp));
                         match resp.json::<Vec<Risk>>().await {
-                            Ok(data) => risks.set(data),
+                            Ok(data) => {
+                                error.set(None);
+                                risks.set(data);
+                            }
                             Err(err) => console::log_1(&format!("Erreur JSON: {:?}", err).into()),
                         }
                     }
+                    Ok(resp) => {
+                        error.set(Some(api::error_message(&resp).await));
+                        risks.set(Vec::new());
+                        total.set(0);
+                    }
                     Err(err) => {
                         web_sys::console::log_1(&format!("Erreur: {:?}", err).into());
                     }
@@ -79,30 +198,123 @@ pub fn risk_list() -> Html {
         });
     }
 
+    // Met à jour un champ de la saisie en cours
+    let edit = |apply: fn(&mut RiskFilters, String)| {
+        let draft = draft.clone();
+        move |value: String| {
+            let mut updated = (*draft).clone();
+            apply(&mut updated, value);
+            draft.set(updated);
+        }
+    };
+    let on_input = |apply: fn(&mut RiskFilters, String)| {
+        let edit = edit(apply);
+        Callback::from(move |e: InputEvent| edit(e.target_unchecked_into::<HtmlInputElement>().value()))
+    };
+    let on_select = |apply: fn(&mut RiskFilters, String)| {
+        let edit = edit(apply);
+        Callback::from(move |e: Event| edit(e.target_unchecked_into::<HtmlSelectElement>().value()))
+    };
+
+    let on_search = {
+        let draft = draft.clone();
+        let filters = filters.clone();
+        let page = page.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            filters.set((*draft).clone());
+            page.set(1);
+        })
+    };
+    let on_reset = {
+        let draft = draft.clone();
+        let filters = filters.clone();
+        let page = page.clone();
+        Callback::from(move |_: MouseEvent| {
+            draft.set(RiskFilters::default());
+            filters.set(RiskFilters::default());
+            page.set(1);
+        })
+    };
+    let on_page = {
+        let page = page.clone();
+        Callback::from(move |next: u32| page.set(next))
+    };
+
     html! {
         <div>
             <h2>{ "Liste des Risques" }</h2>
+            <form class="d-flex flex-wrap gap-2 mb-3" onsubmit={on_search}>
+                <input type="search" placeholder="Rechercher" value={draft.q.clone()} oninput={on_input(|f, v| f.q = v)} />
+                <select onchange={on_select(|f, v| f.status = v)}>
+                    <option value="" selected={draft.status.is_empty()}>{ "Tous les statuts" }</option>
+                    { for STATUSES.iter().map(|status| html! {
+                        <option value={*status} selected={draft.status == *status}>{ *status }</option>
+                    }) }
+                </select>
+                <input type="text" placeholder="Catégorie" value={draft.category.clone()} oninput={on_input(|f, v| f.category = v)} />
+                <input type="text" placeholder="Localisation" value={draft.location.clone()} oninput={on_input(|f, v| f.location = v)} />
+                <input type="text" placeholder="Règlement" value={draft.regulation.clone()} oninput={on_input(|f, v| f.regulation = v)} />
+                <input type="text" placeholder="Propriétaire" value={draft.owner.clone()} oninput={on_input(|f, v| f.owner = v)} />
+                <input type="number" placeholder="Score min" value={draft.score_min.clone()} oninput={on_input(|f, v| f.score_min = v)} />
+                <input type="number" placeholder="Score max" value={draft.score_max.clone()} oninput={on_input(|f, v| f.score_max = v)} />
+                <label>{ "Créé du " }
+                    <input type="date" value={draft.created_from.clone()} oninput={on_input(|f, v| f.created_from = v)} />
+                </label>
+                <label>{ " au " }
+                    <input type="date" value={draft.created_to.clone()} oninput={on_input(|f, v| f.created_to = v)} />
+                </label>
+                <select onchange={on_select(|f, v| f.review_overdue = v)}>
+                    <option value="" selected={draft.review_overdue.is_empty()}>{ "Toutes les revues" }</option>
+                    <option value="true" selected={draft.review_overdue == "true"}>{ "Revue en retard" }</option>
+                </select>
+                <select onchange={on_select(|f, v| f.sort = v)}>
+                    { for SORTS.iter().map(|(value, label)| html! {
+                        <option value={*value} selected={draft.sort == *value}>{ *label }</option>
+                    }) }
+                </select>
+                <button type="submit">{ "🔎 Filtrer" }</button>
+                <button type="button" onclick={on_reset}>{ "Réinitialiser" }</button>
+            </form>
+            if let Some(msg) = &*error {
+                <p>{ msg }</p>
+            }
             if *loading {
                 <p>{ "Chargement en cours..." }</p>
+            } else if risks.is_empty() {
+                <p>{ "Aucun risque ne correspond à ces critères." }</p>
             } else {
                 <ul>
-                    { for risks.iter().cloned().map(|risk| html! {
+                    { for risks.iter().map(|risk| html! {
                         <li key={risk.id.clone()}>
                             <strong>{ format!("{} [{}]", &risk.title, risk.status) }</strong>
+                            { " " }<RiskLevelBadge level={risk.level.clone()} levels={(*levels).clone()} />
+                            if risk.review_overdue {
+                                { " " }<span class="badge bg-danger">{ "Revue en retard" }</span>
+                            }
                             <p>{ format!("Réf. externe : {}", risk.external_id.clone().unwrap_or_default()) }</p>
                             <p>{ format!("Catégorie : {}", risk.category.clone().unwrap_or_default()) }</p>
                             <p>{ format!("Localisation : {}", risk.location.clone().unwrap_or_default()) }</p>
                             <p>{ format!("Règlement : {}", risk.regulation.clone().unwrap_or_default()) }</p>
                             <p>{ format!("Mesure de contrôle : {}", risk.control_measure_id.clone().unwrap_or_default()) }</p>
-                            <p>{ format!("Impact : {} | Probabilité : {} | Score : {}", risk.impact, risk.probability, risk.score.unwrap_or(0)) }</p>
+                            <p>{ format!(
+                                "Impact : {} | Probabilité : {} | Score : {} | Score résiduel : {}",
+                                risk.impact,
+                                risk.probability,
+                                risk.score.map(|score| score.to_string()).unwrap_or_else(|| "–".to_string()),
+                                risk.residual_score.map(|score| score.to_string()).unwrap_or_else(|| "–".to_string()),
+                            ) }</p>
                             <p>{ format!("Description : {}", risk.description.clone().unwrap_or_default()) }</p>
-                            <button onclick={
-                                let navigator = navigator.clone();
-                                let id = risk.id.clone();
-                                Callback::from(move |_| navigator.push(&Route::EditRisk { id: id.clone() }))
-                            }>
-                                { "✏ Modifier" }
-                            </button>
+                            <p>{ format!("Prochaine revue : {}", risk.next_review_at.as_deref().map(|d| d.get(..10).unwrap_or(d)).unwrap_or("–")) }</p>
+                            if auth::can_edit() {
+                                <button onclick={
+                                    let navigator = navigator.clone();
+                                    let id = risk.id.clone();
+                                    Callback::from(move |_| navigator.push(&Route::EditRisk { id: id.clone() }))
+                                }>
+                                    { "✏ Modifier" }
+                                </button>
+                            }
                             <button onclick={
                                 let navigator = navigator.clone();
                                 let id = risk.id.clone();
@@ -110,21 +322,25 @@ pub fn risk_list() -> Html {
                             }>
                                 { "🔍 Voir les détails" }
                             </button>
-                            <button onclick={
-                                let delete_risk = delete_risk.clone();
-                                let id = risk.id.clone();
-                                Callback::from(move |_| {
-                                    if web_sys::window().unwrap().confirm_with_message("Voulez-vous vraiment supprimer ce risque ?").unwrap_or(false) {
-                                        delete_risk.emit(id.clone());
-                                    }
-                                })
-                            }>
-                                { "🗑 Supprimer" }
-                            </button>
+                            if auth::can_delete() {
+                                <button onclick={
+                                    let delete_risk = delete_risk.clone();
+                                    let id = risk.id.clone();
+                                    let version = risk.version;
+                                    Callback::from(move |_| {
+                                        if web_sys::window().unwrap().confirm_with_message("Voulez-vous vraiment supprimer ce risque ?").unwrap_or(false) {
+                                            delete_risk.emit((id.clone(), version));
+                                        }
+                                    })
+                                }>
+                                    { "🗑 Supprimer" }
+                                </button>
+                            }
                         </li>
                     }) }
                 </ul>
             }
+            <Pager page={*page} total={*total} on_page={on_page} />
         </div>
     }
 }
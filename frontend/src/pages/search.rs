@@ -0,0 +1,114 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+use crate::auth;
+use crate::incident::status_label;
+use crate::Route;
+
+// Paramètres de l'URL `/search?q=...`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct SearchParams {
+    pub q: String,
+}
+
+#[derive(Deserialize, Clone, PartialEq)]
+struct SearchHit {
+    kind: String,
+    id: String,
+    title: String,
+    status: String,
+    highlight: String,
+}
+
+// Extrait renvoyé par l'API : seuls les termes entre <mark> et </mark> sont mis en évidence,
+// le reste est affiché comme du texte
+fn render_highlight(highlight: &str) -> Html {
+    let mut parts = Vec::new();
+    for (index, chunk) in highlight.split("<mark>").enumerate() {
+        match chunk.split_once("</mark>") {
+            Some((marked, rest)) if index > 0 => {
+                parts.push(html! { <mark>{ marked }</mark> });
+                parts.push(html! { { rest } });
+            }
+            _ => parts.push(html! { { chunk } }),
+        }
+    }
+    html! { <>{ for parts }</> }
+}
+
+#[function_component(Search)]
+pub fn search() -> Html {
+    let location = use_location().unwrap();
+    let text = location.query::<SearchParams>().map(|p| p.q).unwrap_or_default();
+    let hits = use_state(Vec::<SearchHit>::new);
+    let loading = use_state(|| false);
+    let message = use_state(|| None as Option<String>);
+
+    {
+        let hits = hits.clone();
+        let loading = loading.clone();
+        let message = message.clone();
+        use_effect_with(text.clone(), move |text| {
+            let text = text.trim().to_string();
+            if text.is_empty() {
+                hits.set(Vec::new());
+            } else {
+                loading.set(true);
+                wasm_bindgen_futures::spawn_local(async move {
+                    let response = Request::get("http://localhost:8081/search")
+                        .query([("q", text.as_str())])
+                        .header("Authorization", &auth::bearer())
+                        .send()
+                        .await;
+
+                    match response {
+                        Ok(resp) if resp.ok() => {
+                            message.set(None);
+                            hits.set(resp.json::<Vec<SearchHit>>().await.unwrap_or_default());
+                        }
+                        Ok(resp) => message.set(Some(api::error_message(&resp).await)),
+                        Err(_) => message.set(Some("Erreur réseau : recherche impossible.".to_string())),
+                    }
+                    loading.set(false);
+                });
+            }
+            || ()
+        });
+    }
+
+    html! {
+        <div>
+            <h2>{ format!("Résultats pour « {} »", text) }</h2>
+            if let Some(msg) = &*message {
+                <p>{ msg }</p>
+            }
+            if *loading {
+                <p>{ "Recherche en cours..." }</p>
+            } else if hits.is_empty() {
+                <p>{ "Aucun résultat." }</p>
+            } else {
+                <ul>
+                    { for hits.iter().map(|hit| {
+                        let (label, route, status) = if hit.kind == "incident" {
+                            ("Incident", Route::ViewIncident { id: hit.id.clone() }, status_label(&hit.status).to_string())
+                        } else {
+                            ("Risque", Route::ViewRisk { id: hit.id.clone() }, hit.status.clone())
+                        };
+                        html! {
+                            <li key={format!("{}-{}", hit.kind, hit.id)}>
+                                <span class="badge bg-secondary">{ label }</span>
+                                { " " }
+                                <Link<Route> to={route}>{ &hit.title }</Link<Route>>
+                                { format!(" [{}]", status) }
+                                <p><small>{ render_highlight(&hit.highlight) }</small></p>
+                            </li>
+                        }
+                    }) }
+                </ul>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,87 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use crate::auth;
+
+use crate::Route;
+use crate::control::{effectiveness_label, frequency_label, type_label, Control};
+use crate::components::audit_timeline::AuditTimeline;
+use crate::components::risk_picker::use_risk_options;
+
+#[function_component(ViewControl)]
+pub fn view_control() -> Html {
+    let route = use_route::<Route>().unwrap();
+    let id = if let Route::ViewControl { id } = route {
+        id
+    } else {
+        "".to_string()
+    };
+
+    let control = use_state(|| None::<Control>);
+    let risks = use_risk_options();
+    {
+        let control = control.clone();
+        let id = id.clone();
+        use_effect_with(id.clone(), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/controls/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Control>().await {
+                        control.set(Some(data));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    html! {
+        <div>
+            <h2>{ "Détails du Contrôle" }</h2>
+            {
+                if let Some(control) = &*control {
+                    html! {
+                        <div>
+                            <p><strong>{ "Nom : " }</strong>{ &control.name }</p>
+                            <p><strong>{ "Description : " }</strong>{ control.description.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Type : " }</strong>{ type_label(&control.control_type) }</p>
+                            <p><strong>{ "Responsable : " }</strong>{ control.owner.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Fréquence : " }</strong>{ control.frequency.as_deref().map(frequency_label).unwrap_or("Non précisée") }</p>
+                            <p><strong>{ "Efficacité de conception : " }</strong>{ effectiveness_label(&control.design_effectiveness) }</p>
+                            <p><strong>{ "Efficacité de fonctionnement : " }</strong>{ effectiveness_label(&control.operating_effectiveness) }</p>
+                            <p><strong>{ "Risques couverts : " }</strong>
+                                if control.risk_ids.is_empty() {
+                                    { "Aucun" }
+                                }
+                            </p>
+                            <ul>
+                                { for control.risk_ids.iter().map(|risk_id| {
+                                    let title = risks
+                                        .iter()
+                                        .find(|risk| &risk.id == risk_id)
+                                        .map(|risk| risk.title.clone())
+                                        .unwrap_or_else(|| risk_id.clone());
+                                    html! {
+                                        <li key={risk_id.clone()}>
+                                            <Link<Route> to={Route::ViewRisk { id: risk_id.clone() }}>{ title }</Link<Route>>
+                                        </li>
+                                    }
+                                }) }
+                            </ul>
+                            if auth::can_edit() {
+                                <Link<Route> to={Route::EditControl { id: id.clone() }}>{ "✏ Modifier" }</Link<Route>>
+                            }
+                            <AuditTimeline entity="control" id={id.clone()} />
+                        </div>
+                    }
+                } else {
+                    html! { <p>{ "Chargement..." }</p> }
+                }
+            }
+        </div>
+    }
+}
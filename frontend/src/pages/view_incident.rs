@@ -0,0 +1,105 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use gloo_net::http::Request;
+use crate::auth;
+
+use crate::Route;
+use crate::incident::{severity_label, status_label, Incident};
+use crate::components::attachment_panel::AttachmentPanel;
+use crate::components::audit_timeline::AuditTimeline;
+use crate::components::risk_picker::use_risk_options;
+use crate::components::incident_status::{IncidentHistory, IncidentStatusChange};
+
+#[function_component(ViewIncident)]
+pub fn view_incident() -> Html {
+    let route = use_route::<Route>().unwrap();
+    let id = if let Route::ViewIncident { id } = route {
+        id
+    } else {
+        "".to_string()
+    };
+
+    let incident = use_state(|| None::<Incident>);
+    let risks = use_risk_options();
+    let refresh = use_state(|| 0u32);
+    {
+        let incident = incident.clone();
+        let id = id.clone();
+        use_effect_with(*refresh, move |_| {
+            let incident = incident.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get(&format!("http://localhost:8081/incidents/{}", id))
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+
+                if let Ok(resp) = response {
+                    if let Ok(data) = resp.json::<Incident>().await {
+                        incident.set(Some(data));
+                    }
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_status_changed = {
+        let refresh = refresh.clone();
+        Callback::from(move |_| refresh.set(*refresh + 1))
+    };
+
+    html! {
+        <div>
+            <h2>{ "Détails de l'Incident" }</h2>
+            {
+                if let Some(incident) = &*incident {
+                    html! {
+                        <div>
+                            <p><strong>{ "Titre : " }</strong>{ &incident.title }</p>
+                            <p><strong>{ "Description : " }</strong>{ incident.description.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Gravité : " }</strong>{ severity_label(&incident.severity) }</p>
+                            <p><strong>{ "Déclaré le : " }</strong>{ &incident.created_at }</p>
+                            <p><strong>{ "Risques liés : " }</strong>
+                                if incident.risk_ids.is_empty() {
+                                    { "Aucun" }
+                                }
+                            </p>
+                            <ul>
+                                { for incident.risk_ids.iter().map(|risk_id| {
+                                    let title = risks
+                                        .iter()
+                                        .find(|risk| &risk.id == risk_id)
+                                        .map(|risk| risk.title.clone())
+                                        .unwrap_or_else(|| risk_id.clone());
+                                    let principal = incident.related_risk_id.as_ref() == Some(risk_id);
+                                    html! {
+                                        <li key={risk_id.clone()}>
+                                            <Link<Route> to={Route::ViewRisk { id: risk_id.clone() }}>{ title }</Link<Route>>
+                                            if principal {
+                                                { " (principal)" }
+                                            }
+                                        </li>
+                                    }
+                                }) }
+                            </ul>
+                            <p><strong>{ "Statut : " }</strong>{ status_label(&incident.status) }</p>
+                            if auth::can_edit() {
+                                <IncidentStatusChange
+                                    incident_id={id.clone()}
+                                    current_status={incident.status.clone()}
+                                    version={incident.version}
+                                    on_changed={on_status_changed.clone()}
+                                />
+                            }
+                            <IncidentHistory incident_id={id.clone()} refresh={*refresh} />
+                            <AttachmentPanel entity_path={format!("incidents/{}", id)} />
+                            <AuditTimeline entity="incident" id={id.clone()} refresh={*refresh} />
+                        </div>
+                    }
+                } else {
+                    html! { <p>{ "Chargement..." }</p> }
+                }
+            }
+        </div>
+    }
+}
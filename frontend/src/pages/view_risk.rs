@@ -1,24 +1,77 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use gloo_net::http::Request;
+use crate::api;
+use crate::auth;
 use serde::Deserialize;
 
 use crate::Route;
+use crate::components::attachment_panel::AttachmentPanel;
+use crate::components::status_change::StatusChange;
+use crate::components::status_history::StatusHistory;
+use crate::components::audit_timeline::AuditTimeline;
+use crate::components::scoring_method::scoring_method_label;
+use crate::components::risk_level::{use_risk_levels, RiskLevelBadge};
+use crate::components::linked_incidents::LinkedIncidents;
+use crate::components::linked_controls::LinkedControls;
+use crate::components::treatment_plans::TreatmentPlans;
+use crate::components::probability_suggestion::ProbabilitySuggestion;
+use crate::components::evaluation_history::EvaluationHistory;
 
 #[derive(Deserialize, Clone, Debug)]
 struct Risk {
-    id: String,
     title: String,
     description: Option<String>,
     impact: i32,
     probability: i32,
     status: String,
+    version: i32,
     external_id: Option<String>,
     category: Option<String>,
     location: Option<String>,
     regulation: Option<String>,
     control_measure_id: Option<String>,
+    risk_map: Option<String>,
+    threat_map: Option<String>,
+    technology: Option<String>,
+    team: Option<String>,
+    business_unit: Option<String>,
+    owner: Option<String>,
+    owner_delegate: Option<String>,
+    source: Option<String>,
+    evaluation_method: Option<String>,
+    evaluation_notes: Option<String>,
+    additional_notes: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    assets: Vec<String>,
+    #[serde(default)]
+    stakeholders: Vec<String>,
+    scoring_method: String,
     score: Option<i32>,
+    level: Option<String>,
+    residual_impact: Option<i32>,
+    residual_probability: Option<i32>,
+    residual_score: Option<i32>,
+    residual_level: Option<String>,
+    target_impact: Option<i32>,
+    target_probability: Option<i32>,
+    target_score: Option<i32>,
+    target_level: Option<String>,
+    last_reviewed_at: Option<String>,
+    next_review_at: Option<String>,
+    #[serde(default)]
+    review_overdue: bool,
+}
+
+fn or_dash(value: Option<i32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "–".to_string())
+}
+
+// Date seule d'un horodatage ISO 8601
+fn date_or_dash(value: &Option<String>) -> String {
+    value.as_deref().map(|d| d.get(..10).unwrap_or(d).to_string()).unwrap_or_else(|| "–".to_string())
 }
 
 #[function_component(ViewRisk)]
@@ -31,13 +84,16 @@ pub fn view_risk() -> Html {
     };
 
     let risk = use_state(|| None::<Risk>);
+    let levels = use_risk_levels();
+    let refresh = use_state(|| 0u32);
     {
         let risk = risk.clone();
         let id = id.clone();
-        use_effect_with((), move |_| {
+        use_effect_with(*refresh, move |_| {
             let risk = risk.clone();
             wasm_bindgen_futures::spawn_local(async move {
                 let response = Request::get(&format!("http://localhost:8081/risks/{}", id))
+                    .header("Authorization", &auth::bearer())
                     .send()
                     .await;
 
@@ -51,6 +107,44 @@ pub fn view_risk() -> Html {
         });
     }
 
+    let on_status_changed = {
+        let refresh = refresh.clone();
+        Callback::from(move |_| refresh.set(*refresh + 1))
+    };
+
+    // Enregistre la revue périodique et recharge le risque pour afficher la nouvelle échéance
+    let on_reviewed = {
+        let id = id.clone();
+        let refresh = refresh.clone();
+        Callback::from(move |version: i32| {
+            let id = id.clone();
+            let refresh = refresh.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::post(&format!("http://localhost:8081/risks/{}/review", id))
+                    .header("Authorization", &auth::bearer())
+                    .header("If-Match", &format!("\"{}\"", version))
+                    .send()
+                    .await;
+                match response {
+                    Ok(resp) if resp.ok() => refresh.set(*refresh + 1),
+                    Ok(resp) if resp.status() == 412 => {
+                        if let Some(win) = web_sys::window() {
+                            let _ = win.alert_with_message("Ce risque a été modifié entre-temps : il a été rechargé, vérifiez-le avant de le marquer comme revu.");
+                        }
+                        refresh.set(*refresh + 1);
+                    }
+                    Ok(resp) => {
+                        let message = api::error_message(&resp).await;
+                        if let Some(win) = web_sys::window() {
+                            let _ = win.alert_with_message(&format!("Échec de l'enregistrement de la revue : {}", message));
+                        }
+                    }
+                    Err(err) => web_sys::console::log_1(&format!("Erreur: {:?}", err).into()),
+                }
+            });
+        })
+    };
+
     html! {
         <div>
             <h2>{ "Détails du Risque" }</h2>
@@ -65,10 +159,85 @@ pub fn view_risk() -> Html {
                             <p><strong>{ "Localisation : " }</strong>{ risk.location.clone().unwrap_or_default() }</p>
                             <p><strong>{ "Règlement : " }</strong>{ risk.regulation.clone().unwrap_or_default() }</p>
                             <p><strong>{ "Mesure de contrôle : " }</strong>{ risk.control_measure_id.clone().unwrap_or_default() }</p>
-                            <p><strong>{ "Impact : " }</strong>{ risk.impact }</p>
-                            <p><strong>{ "Probabilité : " }</strong>{ risk.probability }</p>
-                            <p><strong>{ "Score : " }</strong>{ risk.score.unwrap_or(0) }</p>
+                            <table class="table table-sm" style="width: auto;">
+                                <thead>
+                                    <tr>
+                                        <th></th>
+                                        <th>{ "Impact" }</th>
+                                        <th>{ "Probabilité" }</th>
+                                        <th>{ "Score" }</th>
+                                        <th>{ "Niveau" }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <tr>
+                                        <th>{ "Inhérent" }</th>
+                                        <td>{ risk.impact }</td>
+                                        <td>{ risk.probability }</td>
+                                        <td>{ risk.score.map(|score| score.to_string()).unwrap_or_else(|| "non évalué".to_string()) }</td>
+                                        <td><RiskLevelBadge level={risk.level.clone()} levels={(*levels).clone()} /></td>
+                                    </tr>
+                                    <tr>
+                                        <th>{ "Résiduel" }</th>
+                                        <td>{ or_dash(risk.residual_impact) }</td>
+                                        <td>{ or_dash(risk.residual_probability) }</td>
+                                        <td>{ or_dash(risk.residual_score) }</td>
+                                        <td><RiskLevelBadge level={risk.residual_level.clone()} levels={(*levels).clone()} /></td>
+                                    </tr>
+                                    <tr>
+                                        <th>{ "Cible" }</th>
+                                        <td>{ or_dash(risk.target_impact) }</td>
+                                        <td>{ or_dash(risk.target_probability) }</td>
+                                        <td>{ or_dash(risk.target_score) }</td>
+                                        <td><RiskLevelBadge level={risk.target_level.clone()} levels={(*levels).clone()} /></td>
+                                    </tr>
+                                </tbody>
+                            </table>
+                            <p><strong>{ "Méthode de score : " }</strong>{ scoring_method_label(&risk.scoring_method) }</p>
                             <p><strong>{ "Statut : " }</strong>{ &risk.status }</p>
+                            if auth::can_change_status() {
+                                <StatusChange
+                                    risk_id={id.clone()}
+                                    current_status={risk.status.clone()}
+                                    version={risk.version}
+                                    on_changed={on_status_changed.clone()}
+                                />
+                            }
+                            <p>
+                                <strong>{ "Dernière revue : " }</strong>{ date_or_dash(&risk.last_reviewed_at) }
+                                { " | " }<strong>{ "Prochaine revue : " }</strong>{ date_or_dash(&risk.next_review_at) }
+                                if risk.review_overdue {
+                                    { " " }<span class="badge bg-danger">{ "Revue en retard" }</span>
+                                }
+                                if auth::can_edit() {
+                                    { " " }<button onclick={
+                                        let version = risk.version;
+                                        on_reviewed.reform(move |_: MouseEvent| version)
+                                    }>{ "✔ Marquer comme revu" }</button>
+                                }
+                            </p>
+                            <p><strong>{ "Cartographie des risques : " }</strong>{ risk.risk_map.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Cartographie des menaces : " }</strong>{ risk.threat_map.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Technologie : " }</strong>{ risk.technology.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Équipe : " }</strong>{ risk.team.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Unité métier : " }</strong>{ risk.business_unit.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Propriétaire : " }</strong>{ risk.owner.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Délégué du propriétaire : " }</strong>{ risk.owner_delegate.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Source : " }</strong>{ risk.source.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Méthode d'évaluation : " }</strong>{ risk.evaluation_method.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Notes d'évaluation : " }</strong>{ risk.evaluation_notes.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Notes complémentaires : " }</strong>{ risk.additional_notes.clone().unwrap_or_default() }</p>
+                            <p><strong>{ "Tags : " }</strong>{ risk.tags.join(", ") }</p>
+                            <p><strong>{ "Actifs : " }</strong>{ risk.assets.join(", ") }</p>
+                            <p><strong>{ "Parties prenantes : " }</strong>{ risk.stakeholders.join(", ") }</p>
+                            <StatusHistory risk_id={id.clone()} refresh={*refresh} />
+                            <EvaluationHistory risk_id={id.clone()} refresh={*refresh} on_evaluated={on_status_changed.clone()} />
+                            <LinkedControls risk_id={id.clone()} on_changed={on_status_changed.clone()} />
+                            <TreatmentPlans risk_id={id.clone()} refresh={*refresh} />
+                            <LinkedIncidents risk_id={id.clone()} />
+                            <ProbabilitySuggestion risk_id={id.clone()} refresh={*refresh} />
+                            <AttachmentPanel entity_path={format!("risks/{}", id)} />
+                            <AuditTimeline entity="risk" id={id.clone()} refresh={*refresh} />
                         </div>
                     }
                 } else {
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+// Action d'un plan de traitement, telle que renvoyée par l'API (`models::treatment::ActionItem`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct ActionItem {
+    pub id: String,
+    pub plan_id: String,
+    pub risk_id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_id: Option<String>,
+    pub assignee_username: Option<String>,
+    pub due_date: Option<String>,
+    pub progress: i32,
+    pub status: String,
+    pub mandatory: bool,
+    pub overdue: bool,
+    pub version: i32,
+}
+
+// Plan de traitement et ses actions (`models::treatment::TreatmentPlanDetail`)
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct TreatmentPlan {
+    pub id: String,
+    pub title: String,
+    pub strategy: String,
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub due_date: Option<String>,
+    pub version: i32,
+    #[serde(default)]
+    pub actions: Vec<ActionItem>,
+}
+
+pub const STRATEGIES: &[&str] = &["Mitigate", "Avoid", "Transfer", "Accept"];
+
+pub const ACTION_STATUSES: &[&str] = &["Open", "InProgress", "Done", "Cancelled"];
+
+pub fn strategy_label(strategy: &str) -> &str {
+    match strategy {
+        "Mitigate" => "Réduire",
+        "Avoid" => "Éviter",
+        "Transfer" => "Transférer",
+        "Accept" => "Accepter",
+        other => other,
+    }
+}
+
+pub fn action_status_label(status: &str) -> &str {
+    match status {
+        "Open" => "À faire",
+        "InProgress" => "En cours",
+        "Done" => "Terminée",
+        "Cancelled" => "Annulée",
+        other => other,
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use gloo_net::http::Request;
+use serde::Deserialize;
+use serde_json::Value;
+use yew::prelude::*;
+
+use crate::api::ApiError;
+use crate::auth;
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+pub struct ScaleRange {
+    pub min: i32,
+    pub max: i32,
+}
+
+// Règles de `GET /validation/rules` ; valeurs par défaut du backend tant qu'elles ne sont pas chargées
+#[derive(Deserialize, Clone, PartialEq)]
+pub struct ValidationRules {
+    pub impact_scale: ScaleRange,
+    pub probability_scale: ScaleRange,
+    pub evaluation_scale: ScaleRange,
+    pub title_min_length: usize,
+    pub title_max_length: usize,
+    pub incident_severities: Vec<String>,
+    pub incident_statuses: Vec<String>,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        ValidationRules {
+            impact_scale: ScaleRange { min: 1, max: 5 },
+            probability_scale: ScaleRange { min: 1, max: 5 },
+            evaluation_scale: ScaleRange { min: 1, max: 10 },
+            title_min_length: 3,
+            title_max_length: 200,
+            incident_severities: Vec::new(),
+            incident_statuses: Vec::new(),
+        }
+    }
+}
+
+#[hook]
+pub fn use_validation_rules() -> ValidationRules {
+    let rules = use_state(ValidationRules::default);
+    {
+        let rules = rules.clone();
+        use_effect_with((), move |_| {
+            wasm_bindgen_futures::spawn_local(async move {
+                let response = Request::get("http://localhost:8081/validation/rules")
+                    .header("Authorization", &auth::bearer())
+                    .send()
+                    .await;
+                if let Ok(response) = response {
+                    if let Ok(fetched) = response.json::<ValidationRules>().await {
+                        rules.set(fetched);
+                    }
+                }
+            });
+            || ()
+        });
+    }
+    (*rules).clone()
+}
+
+// Message d'erreur par champ, affiché sous la saisie correspondante
+pub type FieldErrors = HashMap<String, String>;
+
+// Erreurs par champ d'une réponse 422 (`details.fields`)
+pub fn field_errors(error: &ApiError) -> FieldErrors {
+    let Some(Value::Object(fields)) = error.details.as_ref().and_then(|d| d.get("fields")) else {
+        return FieldErrors::new();
+    };
+    fields
+        .iter()
+        .filter_map(|(field, messages)| {
+            let first = messages.as_array()?.first()?.as_str()?;
+            Some((field.clone(), first.to_string()))
+        })
+        .collect()
+}
+
+fn check_length(errors: &mut FieldErrors, field: &str, value: &str, min: usize, max: usize) {
+    let length = value.trim().chars().count();
+    if length < min {
+        errors.insert(field.to_string(), format!("Au moins {} caractères requis", min));
+    } else if length > max {
+        errors.insert(field.to_string(), format!("Au plus {} caractères autorisés", max));
+    }
+}
+
+fn check_range(errors: &mut FieldErrors, field: &str, value: i32, scale: ScaleRange) {
+    if value < scale.min || value > scale.max {
+        errors.insert(field.to_string(), format!("Doit être compris entre {} et {}", scale.min, scale.max));
+    }
+}
+
+// Mêmes contrôles que `NewRisk::validate` côté backend
+pub fn validate_risk(title: &str, impact: i32, probability: i32, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_length(&mut errors, "title", title, rules.title_min_length, rules.title_max_length);
+    check_range(&mut errors, "impact", impact, rules.impact_scale);
+    check_range(&mut errors, "probability", probability, rules.probability_scale);
+    errors
+}
+
+// Mêmes contrôles que `RiskEvaluationInput::validate` côté backend
+pub fn validate_evaluation(severity: i32, likelihood: i32, detectability: i32, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_range(&mut errors, "severity", severity, rules.evaluation_scale);
+    check_range(&mut errors, "likelihood", likelihood, rules.evaluation_scale);
+    check_range(&mut errors, "detectability", detectability, rules.evaluation_scale);
+    errors
+}
+
+// Risque résiduel ou cible : impact et probabilité renseignés ensemble, chacun sur son échelle
+pub fn check_score_pair(
+    errors: &mut FieldErrors,
+    (impact_field, impact): (&str, Option<i32>),
+    (probability_field, probability): (&str, Option<i32>),
+    rules: &ValidationRules,
+) {
+    match (impact, probability) {
+        (Some(impact), Some(probability)) => {
+            check_range(errors, impact_field, impact, rules.impact_scale);
+            check_range(errors, probability_field, probability, rules.probability_scale);
+        }
+        (Some(_), None) => {
+            errors.insert(probability_field.to_string(), "À renseigner avec l'impact correspondant".to_string());
+        }
+        (None, Some(_)) => {
+            errors.insert(impact_field.to_string(), "À renseigner avec la probabilité correspondante".to_string());
+        }
+        (None, None) => {}
+    }
+}
+
+// Mêmes contrôles que `NewIncident::validate` côté backend
+pub fn validate_incident(title: &str, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_length(&mut errors, "title", title, rules.title_min_length, rules.title_max_length);
+    errors
+}
+
+// Mêmes contrôles que `NewControl::validate` côté backend
+pub fn validate_control(name: &str, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_length(&mut errors, "name", name, rules.title_min_length, rules.title_max_length);
+    errors
+}
+
+// Mêmes contrôles que `NewActionItem::validate` côté backend
+pub fn validate_action(title: &str, progress: i32, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_length(&mut errors, "title", title, rules.title_min_length, rules.title_max_length);
+    check_range(&mut errors, "progress", progress, ScaleRange { min: 0, max: 100 });
+    errors
+}
+
+// Contrôles de `NewTreatmentPlan::validate` côté backend ; l'échéance passée est signalée par l'API
+pub fn validate_plan(title: &str, owner: &str, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_length(&mut errors, "title", title, rules.title_min_length, rules.title_max_length);
+    if owner.trim().is_empty() {
+        errors.insert("owner".to_string(), "Le responsable du plan est obligatoire".to_string());
+    }
+    errors
+}
+
+// Mêmes contrôles que `NewRiskAppetite::validate` côté backend
+pub fn validate_appetite(category: &str, appetite: i32, tolerance: i32, rules: &ValidationRules) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    check_length(&mut errors, "category", category, 1, rules.title_max_length);
+    if appetite < 0 {
+        errors.insert("appetite".to_string(), "Doit être positif ou nul".to_string());
+    }
+    if tolerance < appetite {
+        errors.insert("tolerance".to_string(), "Doit être supérieure ou égale à l'appétence".to_string());
+    }
+    errors
+}
+
+#[derive(Properties, PartialEq)]
+pub struct FieldErrorProps {
+    pub errors: FieldErrors,
+    pub field: &'static str,
+}
+
+#[function_component(FieldError)]
+pub fn field_error(props: &FieldErrorProps) -> Html {
+    match props.errors.get(props.field) {
+        Some(message) => html! { <small class="field-error" style="color: #c0392b;">{ message }</small> },
+        None => html! {},
+    }
+}